@@ -0,0 +1,262 @@
+use crate::{household::Household, trading::Trader};
+use model::{
+    entity::{agent::Agent, Entity},
+    market::{exchange::exchange_pair, fill::Fill, offer::OfferType, Market},
+    ware::Ware,
+    world::World,
+};
+use rand::{seq::SliceRandom, RngCore};
+
+/// The agent of entities without a role of their own.
+/// Sells all tradable wares at the sell prices of the entity, exchanges foreign currencies,
+/// and buys for its unmet demands and its household within its money and storage.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StandardAgent;
+
+impl Agent for StandardAgent {
+    fn decide_offers(
+        &mut self,
+        entity: &Entity,
+        _market: &Market,
+        rng: &mut dyn RngCore,
+    ) -> Vec<(Ware, OfferType, Ware)> {
+        let (tradable_wares, unmet_demands) = entity.tradable_wares_and_unmet_demands();
+        let currency = entity.currency();
+        let mut offers = Vec::new();
+
+        for tradable_ware in tradable_wares.iter() {
+            if tradable_ware.ware_type() == currency {
+                continue;
+            }
+
+            if tradable_ware.is_currency() {
+                // Exchange foreign currencies for the own currency.
+                // The base is the more valuable currency, so its price is at least one unit of the quote.
+                let (base, quote) = exchange_pair(tradable_ware.ware_type(), currency);
+                if base == tradable_ware.ware_type() {
+                    let price = entity.sell_prices().single_price_in(base, quote).max(1);
+                    offers.push((tradable_ware, OfferType::Sell, Ware::new(quote, price)));
+                } else {
+                    let price = entity.buy_prices().single_price_in(base, quote).max(1);
+                    let amount = tradable_ware.amount() / price;
+                    if amount > 0 {
+                        offers.push((
+                            Ware::new(base, amount),
+                            OfferType::Buy,
+                            Ware::new(quote, price),
+                        ));
+                    } else {
+                        // Holdings worth less than one unit of the base are all bid for a single unit,
+                        // rather than being stranded in the foreign currency.
+                        offers.push((Ware::new(base, 1), OfferType::Buy, tradable_ware));
+                    }
+                }
+                continue;
+            }
+
+            let price_per_ware = entity
+                .sell_prices()
+                .single_price_as_ware_in(&tradable_ware, currency);
+            offers.push((tradable_ware, OfferType::Sell, price_per_ware));
+        }
+
+        // Entities may spend their undrawn credit in addition to their cash.
+        let mut money = tradable_wares.ware_amount(currency) + entity.available_credit(currency);
+        // Entities only bid for what they have room to store.
+        let mut space = entity.wares().clone();
+        let mut unmet_demands: Vec<_> = unmet_demands.iter().collect();
+        unmet_demands.shuffle(rng);
+
+        for unmet_demand in unmet_demands.iter() {
+            if unmet_demand.is_currency() {
+                continue;
+            }
+
+            let price_per_ware = entity
+                .buy_prices()
+                .single_price_as_ware_in(&unmet_demand, currency);
+            let max_buy = money / price_per_ware.amount();
+            let mut unmet_demand = unmet_demand.clone();
+            *unmet_demand.amount_mut() = unmet_demand
+                .amount()
+                .min(max_buy)
+                .min(space.room_for(unmet_demand.ware_type()));
+            if unmet_demand.amount() > 0 {
+                money -= unmet_demand.amount() * price_per_ware.amount();
+                space.push_ware_unlimited(unmet_demand.clone());
+                offers.push((unmet_demand, OfferType::Buy, price_per_ware));
+            }
+        }
+
+        for (mut ware, price_per_ware) in entity.household_buy_offers(money) {
+            *ware.amount_mut() = ware.amount().min(space.room_for(ware.ware_type()));
+            if ware.amount() == 0 {
+                continue;
+            }
+            space.push_ware_unlimited(ware.clone());
+            offers.push((ware, OfferType::Buy, price_per_ware));
+        }
+
+        offers
+    }
+
+    fn box_clone(&self) -> Box<dyn Agent> {
+        Box::new(*self)
+    }
+}
+
+pub trait Agents {
+    fn notify_agents(&mut self, fills: &[Fill]);
+}
+
+impl Agents for World {
+    /// Tells the agents of the buyer and the seller of each fill about it.
+    fn notify_agents(&mut self, fills: &[Fill]) {
+        for fill in fills {
+            for &entity_id in &[fill.buyer(), fill.seller()] {
+                let entity = match self.try_get_entity_mut(entity_id) {
+                    Some(entity) => entity,
+                    None => continue,
+                };
+                if let Some(mut agent) = entity.take_agent() {
+                    agent.on_fill(entity, fill);
+                    entity.set_agent(Some(agent));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{agent::StandardAgent, simulation::Simulation};
+    use model::{
+        entity::{agent::Agent, recipe::Recipe, utility::Utility, Entity},
+        market::{fill::Fill, offer::OfferType, Market},
+        ware::{Ware, WareType},
+        world::World,
+    };
+    use rand::{RngCore, SeedableRng};
+    use rand_pcg::Pcg64Mcg;
+    use std::{
+        str::FromStr,
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        },
+    };
+
+    /// Sells all its food at a fixed price, never produces, and counts its fills.
+    #[derive(Clone, Debug)]
+    struct FixedSeller {
+        fills: Arc<AtomicU32>,
+    }
+
+    impl Agent for FixedSeller {
+        fn decide_offers(
+            &mut self,
+            entity: &Entity,
+            _market: &Market,
+            _rng: &mut dyn RngCore,
+        ) -> Vec<(Ware, OfferType, Ware)> {
+            let food = entity.wares().ware_amount(WareType::Food);
+            if food == 0 {
+                return Vec::new();
+            }
+            vec![(
+                Ware::new(WareType::Food, food),
+                OfferType::Sell,
+                Ware::money(5),
+            )]
+        }
+
+        fn decide_production(&mut self, _entity: &Entity) -> Vec<usize> {
+            Vec::new()
+        }
+
+        fn on_fill(&mut self, _entity: &Entity, _fill: &Fill) {
+            self.fills.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn box_clone(&self) -> Box<dyn Agent> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_standard_agent_sells_tradable_wares() {
+        let mut entity = Entity::new("Farmer".to_owned(), Vec::new());
+        entity.add_ware(Ware::new(WareType::Food, 5));
+        entity.sell_prices_mut().set_single_price(WareType::Food, 7);
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        assert_eq!(
+            vec![(
+                Ware::new(WareType::Food, 5),
+                OfferType::Sell,
+                Ware::money(7)
+            )],
+            StandardAgent.decide_offers(&entity, &Market::new(), &mut rng)
+        );
+    }
+
+    #[test]
+    fn test_standard_agent_exchanges_small_holdings() {
+        let mut entity = Entity::new("Trader".to_owned(), Vec::new());
+        entity.set_currency(WareType::Gold);
+        entity.add_ware(Ware::money(23));
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        assert_eq!(
+            vec![(
+                Ware::new(WareType::Gold, 2),
+                OfferType::Buy,
+                Ware::money(10)
+            )],
+            StandardAgent.decide_offers(&entity, &Market::new(), &mut rng)
+        );
+
+        let mut entity = Entity::new("Trader".to_owned(), Vec::new());
+        entity.set_currency(WareType::Gold);
+        entity.add_ware(Ware::money(3));
+        assert_eq!(
+            vec![(Ware::new(WareType::Gold, 1), OfferType::Buy, Ware::money(3))],
+            StandardAgent.decide_offers(&entity, &Market::new(), &mut rng)
+        );
+    }
+
+    #[test]
+    fn test_custom_agent_decides_offers_and_production() {
+        let mut world = World::new();
+        let farmer = world.create_entity("Farmer", &[Recipe::from_str("() -> (2x Food)").unwrap()]);
+        let eater = world.create_entity("Eater", &[]);
+        let fills = Arc::new(AtomicU32::new(0));
+        let entity = world.get_entity_mut(farmer);
+        entity.add_ware(Ware::new(WareType::Food, 4));
+        entity.set_agent(Some(Box::new(FixedSeller {
+            fills: fills.clone(),
+        })));
+        let entity = world.get_entity_mut(eater);
+        entity.set_utility(Utility::CobbDouglas(vec![(WareType::Food, 1.0)]));
+        entity.buy_prices_mut().set_single_price(WareType::Food, 10);
+        entity.add_ware(Ware::money(100));
+
+        // Cloning a world clones the agents of its entities.
+        let mut simulation = Simulation::new(world.clone(), 0);
+        let mut farmer_fills = 0;
+        for _ in 0..3 {
+            farmer_fills += simulation
+                .step()
+                .iter()
+                .filter(|fill| fill.seller() == farmer || fill.buyer() == farmer)
+                .count() as u32;
+        }
+
+        // The farmer sold its food at its own price and did not produce more.
+        assert!(farmer_fills > 0);
+        assert_eq!(farmer_fills, fills.load(Ordering::Relaxed));
+        let entity = simulation.world().get_entity(farmer);
+        assert_eq!(0, entity.wares().ware_amount(WareType::Food));
+        assert_eq!(20, entity.wares().ware_amount(WareType::Money));
+        assert!(entity.agent().is_some());
+        assert!(world.get_entity(farmer).agent().is_some());
+    }
+}
@@ -0,0 +1,212 @@
+use model::{market::fill::Fill, world::World};
+
+pub trait Auctioneer {
+    fn run_auctions(&mut self) -> Vec<Fill>;
+}
+
+impl Auctioneer for World {
+    /// Ends the current round of all open auctions, and settles the sales of the auctions that closed.
+    /// Closed auctions are removed, and give their lot back to the seller and the held bids back to the bidders
+    /// right before the sale is settled.
+    /// Sales are recorded in the market of the region of the seller, like trades in the market.
+    /// If the winner cannot pay or the seller cannot deliver, there is no sale.
+    /// Returns the settled sales.
+    fn run_auctions(&mut self) -> Vec<Fill> {
+        let auction_ids: Vec<_> = self.auctions().map(|(auction_id, _)| auction_id).collect();
+        let mut fills = Vec::new();
+        for auction_id in auction_ids {
+            let auction = self.auction_mut(auction_id).unwrap();
+            auction.end_round();
+            if !auction.is_closed() {
+                continue;
+            }
+
+            let auction = self.remove_auction(auction_id).unwrap();
+            if let Some(fill) = auction.fill() {
+                if self.settle(&fill).is_ok() {
+                    let region = self.get_entity(auction.seller()).region();
+                    let market = self.market_in_mut(region);
+                    market.record_trade(&fill);
+                    market.set_last_price(
+                        fill.ware().ware_type(),
+                        fill.price_per_ware().ware_type(),
+                        fill.price_per_ware().amount(),
+                    );
+                    fills.push(fill);
+                }
+            }
+        }
+        fills
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::auction::Auctioneer;
+    use model::{
+        market::auction::{Auction, AuctionFormat},
+        ware::{Ware, WareType},
+        world::{EntityId, World},
+    };
+
+    fn world_with_lot() -> (World, Vec<EntityId>) {
+        let mut world = World::new();
+        let seller = world.create_entity("Seller", &[]);
+        world
+            .get_entity_mut(seller)
+            .add_ware(Ware::new(WareType::Well, 1));
+        let mut entity_ids = vec![seller];
+        for &cash in &[50, 30, 20] {
+            let bidder = world.create_entity("Bidder", &[]);
+            world.get_entity_mut(bidder).add_ware(Ware::money(cash));
+            entity_ids.push(bidder);
+        }
+        (world, entity_ids)
+    }
+
+    #[test]
+    fn test_english_auction() {
+        let (mut world, ids) = world_with_lot();
+        let lot = Ware::new(WareType::Well, 1);
+        let auction_id = world
+            .create_auction(Auction::new(
+                ids[0],
+                lot,
+                Ware::money(10),
+                AuctionFormat::English(5),
+            ))
+            .unwrap();
+        // The auction holds the lot.
+        assert_eq!(
+            0,
+            world.get_entity(ids[0]).wares().ware_amount(WareType::Well)
+        );
+
+        assert_eq!(Err(()), world.bid(auction_id, ids[1], 9));
+        world.bid(auction_id, ids[1], 10).unwrap();
+        assert_eq!(Err(()), world.bid(auction_id, ids[2], 12));
+        world.bid(auction_id, ids[2], 15).unwrap();
+        assert!(world.run_auctions().is_empty());
+        world.bid(auction_id, ids[1], 25).unwrap();
+        // Only the highest bid is held, and the outbid bidder has its money back.
+        let money = |world: &World, entity_id| {
+            world
+                .get_entity(entity_id)
+                .wares()
+                .ware_amount(WareType::Money)
+        };
+        assert_eq!(25, money(&world, ids[1]));
+        assert_eq!(30, money(&world, ids[2]));
+        assert_eq!(Err(()), world.bid(auction_id, ids[2], 35));
+        assert!(world.run_auctions().is_empty());
+
+        let fills = world.run_auctions();
+        assert_eq!(1, fills.len());
+        assert_eq!(ids[1], fills[0].buyer());
+        assert_eq!(
+            1,
+            world.get_entity(ids[1]).wares().ware_amount(WareType::Well)
+        );
+        assert_eq!(25, money(&world, ids[0]));
+        assert_eq!(25, money(&world, ids[1]));
+        assert_eq!(
+            Some(25),
+            world.market().last_price(WareType::Well, WareType::Money)
+        );
+        assert!(world.auction(auction_id).is_none());
+    }
+
+    #[test]
+    fn test_dutch_and_sealed_bid_auctions() {
+        let (mut world, ids) = world_with_lot();
+        let lot = Ware::new(WareType::Well, 1);
+        let dutch = world
+            .create_auction(Auction::new(
+                ids[0],
+                lot.clone(),
+                Ware::money(10),
+                AuctionFormat::Dutch(40, 15),
+            ))
+            .unwrap();
+        assert!(world.run_auctions().is_empty());
+        assert_eq!(Err(()), world.bid(dutch, ids[3], 25));
+        world.bid(dutch, ids[1], 25).unwrap();
+        let fills = world.run_auctions();
+        assert_eq!(&Ware::money(25), fills[0].price_per_ware());
+        assert_eq!(ids[1], fills[0].buyer());
+
+        // The seller sold its well, so the auction fails.
+        assert!(world
+            .create_auction(Auction::new(
+                ids[0],
+                lot.clone(),
+                Ware::money(10),
+                AuctionFormat::FirstPrice
+            ))
+            .is_err());
+        let first_price = world
+            .create_auction(Auction::new(
+                ids[1],
+                lot,
+                Ware::money(10),
+                AuctionFormat::FirstPrice,
+            ))
+            .unwrap();
+        world.bid(first_price, ids[0], 22).unwrap();
+        world.bid(first_price, ids[3], 18).unwrap();
+        // Both sealed bids are held, so the bidders cannot spend the money elsewhere.
+        assert!(world
+            .get_entity_mut(ids[0])
+            .remove_ware(Ware::money(20))
+            .is_err());
+        let fills = world.run_auctions();
+        assert_eq!(ids[0], fills[0].buyer());
+        assert_eq!(&Ware::money(22), fills[0].price_per_ware());
+        assert_eq!(
+            20,
+            world
+                .get_entity(ids[3])
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+        assert!(world.auction(first_price).is_none());
+    }
+
+    #[test]
+    fn test_auction_without_sale_returns_the_lot() {
+        let (mut world, ids) = world_with_lot();
+        let lot = Ware::new(WareType::Well, 1);
+        let auction_id = world
+            .create_auction(Auction::new(
+                ids[0],
+                lot.clone(),
+                Ware::money(40),
+                AuctionFormat::FirstPrice,
+            ))
+            .unwrap();
+        assert!(world.get_entity_mut(ids[0]).remove_ware(lot).is_err());
+        world.bid(auction_id, ids[1], 30).unwrap();
+        assert_eq!(
+            20,
+            world
+                .get_entity(ids[1])
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+
+        // The bid is below the reserve price, so the lot and the bid go back.
+        assert!(world.run_auctions().is_empty());
+        assert_eq!(0, world.auctions().count());
+        assert_eq!(
+            1,
+            world.get_entity(ids[0]).wares().ware_amount(WareType::Well)
+        );
+        assert_eq!(
+            50,
+            world
+                .get_entity(ids[1])
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+    }
+}
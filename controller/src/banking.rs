@@ -0,0 +1,519 @@
+use model::{
+    entity::{
+        bank::{CreditLine, Loan},
+        liability::Liability,
+    },
+    ware::{Ware, WareAmount, WareType},
+    world::{EntityId, Tick, World},
+};
+use std::collections::HashSet;
+
+/// The priority of secured loans in a liquidation.
+const SECURED_PRIORITY: u32 = 0;
+/// The priority of unsecured loans and credit lines in a liquidation.
+const UNSECURED_PRIORITY: u32 = 1;
+
+pub trait Banking {
+    fn deposit(
+        &mut self,
+        depositor: EntityId,
+        bank: EntityId,
+        amount: WareAmount,
+    ) -> Result<(), ()>;
+    fn withdraw(
+        &mut self,
+        depositor: EntityId,
+        bank: EntityId,
+        amount: WareAmount,
+    ) -> Result<(), ()>;
+    fn grant_loan(
+        &mut self,
+        borrower: EntityId,
+        bank: EntityId,
+        principal: WareAmount,
+        period: Tick,
+        installments: u32,
+        collateral: Option<Ware>,
+    ) -> Result<(), ()>;
+    fn open_credit_line(
+        &mut self,
+        borrower: EntityId,
+        bank: EntityId,
+        limit: WareAmount,
+    ) -> Result<(), ()>;
+    fn credit_score(&self, entity_id: EntityId, currency: WareType) -> f64;
+    fn update_banks(&mut self);
+}
+
+impl Banking for World {
+    /// Moves the given amount of the currency of the bank from the depositor to the bank.
+    fn deposit(
+        &mut self,
+        depositor: EntityId,
+        bank: EntityId,
+        amount: WareAmount,
+    ) -> Result<(), ()> {
+        let currency = bank_currency(self, bank)?;
+        let cash = self
+            .try_get_entity_mut(depositor)
+            .ok_or(())?
+            .remove_ware(Ware::new(currency, amount))?;
+        let bank = self.get_entity_mut(bank);
+        bank.add_ware(cash);
+        bank.bank_mut().unwrap().deposit(depositor, amount);
+        Ok(())
+    }
+
+    /// Pays the given amount of the deposit of the depositor back from the cash of the bank.
+    fn withdraw(
+        &mut self,
+        depositor: EntityId,
+        bank: EntityId,
+        amount: WareAmount,
+    ) -> Result<(), ()> {
+        let currency = bank_currency(self, bank)?;
+        if !self.contains_entity(depositor)
+            || self
+                .get_entity(bank)
+                .bank()
+                .unwrap()
+                .deposit_balance(depositor)
+                < amount
+        {
+            return Err(());
+        }
+
+        let bank = self.get_entity_mut(bank);
+        let cash = bank.remove_ware(Ware::new(currency, amount))?;
+        bank.bank_mut().unwrap().withdraw(depositor, amount)?;
+        self.get_entity_mut(depositor).add_ware(cash);
+        Ok(())
+    }
+
+    /// Lends the given principal in the currency of the bank at its loan rate,
+    /// to be repaid in equal installments, one every `period` ticks.
+    /// A loan with collateral is granted if the borrower holds the collateral and its value,
+    /// times the loan-to-value ratio of the bank, covers the principal.
+    /// A loan without collateral is granted if the credit score of the borrower,
+    /// times the credit ratio of the bank, covers the principal.
+    fn grant_loan(
+        &mut self,
+        borrower: EntityId,
+        bank_id: EntityId,
+        principal: WareAmount,
+        period: Tick,
+        installments: u32,
+        collateral: Option<Ware>,
+    ) -> Result<(), ()> {
+        let currency = bank_currency(self, bank_id)?;
+        let bank = self.get_entity(bank_id).bank().unwrap();
+        let entity = self.try_get_entity(borrower).ok_or(())?;
+        let limit = match &collateral {
+            Some(collateral) => {
+                if entity.wares().ware_amount(collateral.ware_type()) < collateral.amount() {
+                    return Err(());
+                }
+                f64::from(collateral.amount())
+                    * self
+                        .market_in(entity.region())
+                        .value_in(collateral.ware_type(), currency)
+                    * bank.loan_to_value()
+            }
+            None => self.credit_score(borrower, currency) * bank.credit_ratio(),
+        };
+        if f64::from(principal) > limit {
+            return Err(());
+        }
+
+        let loan = Loan::new(
+            borrower,
+            Ware::new(currency, principal),
+            bank.loan_rate(),
+            self.tick(),
+            period,
+            installments,
+            collateral,
+        );
+        let bank = self.get_entity_mut(bank_id);
+        let cash = bank.remove_ware(Ware::new(currency, principal))?;
+        bank.bank_mut().unwrap().add_loan(loan);
+        self.get_entity_mut(borrower).add_ware(cash);
+        Ok(())
+    }
+
+    /// Opens a credit line in the currency of the bank at its loan rate,
+    /// if the credit score of the borrower times the credit ratio of the bank covers the limit.
+    /// Replaces any previous credit line of the borrower, which must be fully repaid.
+    fn open_credit_line(
+        &mut self,
+        borrower: EntityId,
+        bank_id: EntityId,
+        limit: WareAmount,
+    ) -> Result<(), ()> {
+        let currency = bank_currency(self, bank_id)?;
+        let bank = self.get_entity(bank_id).bank().unwrap();
+        let entity = self.try_get_entity(borrower).ok_or(())?;
+        if entity
+            .credit_line()
+            .is_some_and(|credit_line| credit_line.drawn() > 0)
+            || f64::from(limit) > self.credit_score(borrower, currency) * bank.credit_ratio()
+        {
+            return Err(());
+        }
+
+        let credit_line = CreditLine::new(bank_id, Ware::new(currency, limit), bank.loan_rate());
+        self.get_entity_mut(borrower)
+            .set_credit_line(Some(credit_line));
+        Ok(())
+    }
+
+    /// The net worth of the entity in the given currency:
+    /// the value of its wares minus its outstanding loans, drawn credit and liabilities.
+    fn credit_score(&self, entity_id: EntityId, currency: WareType) -> f64 {
+        let entity = match self.try_get_entity(entity_id) {
+            Some(entity) => entity,
+            None => return 0.0,
+        };
+        // Wares are valued in the market of the region of the entity.
+        let market = self.market_in(entity.region());
+        let value =
+            |ware: &Ware| f64::from(ware.amount()) * market.value_in(ware.ware_type(), currency);
+
+        let loans: f64 = self
+            .entities()
+            .filter_map(|entity| entity.bank())
+            .flat_map(|bank| bank.loans())
+            .filter(|loan| loan.borrower() == entity_id)
+            .map(|loan| value(&Ware::new(loan.principal().ware_type(), loan.outstanding())))
+            .sum();
+        let drawn = entity.credit_line().map_or(0.0, |credit_line| {
+            value(&Ware::new(
+                credit_line.limit().ware_type(),
+                credit_line.drawn(),
+            ))
+        });
+        let liabilities: f64 = entity
+            .liabilities()
+            .iter()
+            .map(|liability| value(liability.amount()))
+            .sum();
+
+        market.value_of(entity.wares(), currency) - loans - drawn - liabilities
+    }
+
+    /// Pays the interest on deposits, and charges the interest and due installments of loans and credit lines
+    /// as liabilities due at the next tick. Borrowers repay drawn credit with the cash they do not owe otherwise.
+    /// The loans and credit lines of bankrupt borrowers are due at once, and those of removed borrowers are written off.
+    /// Credit lines of banks that left the world are closed without repayment.
+    fn update_banks(&mut self) {
+        let tick = self.tick();
+        let bankrupt: HashSet<_> = self
+            .iter_entities()
+            .filter(|(_, entity)| entity.is_bankrupt())
+            .map(|(entity_id, _)| entity_id)
+            .collect();
+        let borrowers: HashSet<_> = self
+            .iter_entities()
+            .map(|(entity_id, _)| entity_id)
+            .collect();
+        let mut liabilities = Vec::new();
+
+        for (bank_id, entity) in self.iter_entities_mut() {
+            let bank = match entity.bank_mut() {
+                Some(bank) => bank,
+                None => continue,
+            };
+
+            bank.pay_deposit_interest();
+            for loan in bank.loans_mut() {
+                let priority = if loan.is_secured() {
+                    SECURED_PRIORITY
+                } else {
+                    UNSECURED_PRIORITY
+                };
+                let amount = if bankrupt.contains(&loan.borrower()) {
+                    loan.accrue_interest() + loan.accelerate()
+                } else if borrowers.contains(&loan.borrower()) {
+                    loan.accrue_interest() + loan.take_due_installments(tick)
+                } else {
+                    loan.accelerate();
+                    0
+                };
+
+                if amount > 0 {
+                    let currency = loan.principal().ware_type();
+                    liabilities.push((
+                        loan.borrower(),
+                        Liability::new(
+                            Some(bank_id),
+                            Ware::new(currency, amount),
+                            tick + 1,
+                            priority,
+                        ),
+                    ));
+                }
+            }
+            bank.loans_mut().retain(|loan| !loan.is_repaid());
+        }
+
+        let mut repayments = Vec::new();
+        for (entity_id, entity) in self.iter_entities_mut() {
+            let credit_line = match entity.credit_line() {
+                Some(credit_line) => credit_line.clone(),
+                None => continue,
+            };
+            let currency = credit_line.limit().ware_type();
+            let bank_id = credit_line.bank();
+            if !borrowers.contains(&bank_id) {
+                entity.set_credit_line(None);
+                continue;
+            }
+
+            if entity.is_bankrupt() {
+                let amount = credit_line.drawn();
+                entity.set_credit_line(None);
+                if amount > 0 {
+                    liabilities.push((
+                        entity_id,
+                        Liability::new(
+                            Some(bank_id),
+                            Ware::new(currency, amount),
+                            tick + 1,
+                            UNSECURED_PRIORITY,
+                        ),
+                    ));
+                }
+                continue;
+            }
+
+            let owed: WareAmount = entity
+                .liabilities()
+                .iter()
+                .filter(|liability| liability.amount().ware_type() == currency)
+                .map(|liability| liability.amount().amount())
+                .sum();
+            let spare = entity.wares().ware_amount(currency).saturating_sub(owed);
+            let repayment = spare.min(credit_line.drawn());
+            let credit_line = entity.credit_line_mut().unwrap();
+            let interest = credit_line.accrue_interest();
+            credit_line.repay(repayment);
+            if repayment > 0 {
+                let cash = entity.remove_ware(Ware::new(currency, repayment)).unwrap();
+                repayments.push((bank_id, cash));
+            }
+            if interest > 0 {
+                liabilities.push((
+                    entity_id,
+                    Liability::new(
+                        Some(bank_id),
+                        Ware::new(currency, interest),
+                        tick + 1,
+                        UNSECURED_PRIORITY,
+                    ),
+                ));
+            }
+        }
+
+        for (bank_id, cash) in repayments {
+            if let Some(bank) = self.try_get_entity_mut(bank_id) {
+                bank.add_ware(cash);
+            }
+        }
+        for (borrower, liability) in liabilities {
+            if let Some(borrower) = self.try_get_entity_mut(borrower) {
+                borrower.add_liability(liability);
+            }
+        }
+    }
+}
+
+/// The currency of the given bank. Fails if the entity is not a bank.
+fn bank_currency(world: &World, bank: EntityId) -> Result<WareType, ()> {
+    match world.try_get_entity(bank) {
+        Some(entity) if entity.is_bank() => Ok(entity.currency()),
+        _ => Err(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{banking::Banking, bankruptcy::Insolvency, simulation::Simulation};
+    use model::{
+        entity::{bank::Bank, recipe::Recipe},
+        templates::EATING_RECIPE,
+        ware::{Ware, WareType},
+        world::{Estate, World},
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn test_deposits_and_loans() {
+        let mut world = World::new();
+        let bank = world.create_entity("Bank", &[]);
+        let depositor = world.create_entity("Depositor", &[]);
+        let borrower = world.create_entity("Borrower", &[]);
+        world
+            .get_entity_mut(bank)
+            .set_bank(Bank::new(0.1, 0.1, 0.5, 1.0));
+        world.get_entity_mut(bank).add_ware(Ware::money(100));
+        world.get_entity_mut(depositor).add_ware(Ware::money(50));
+        world
+            .get_entity_mut(borrower)
+            .add_ware(Ware::new(WareType::Plough, 2));
+
+        world.deposit(depositor, bank, 50).unwrap();
+        assert!(world.deposit(depositor, borrower, 0).is_err());
+        assert!(world
+            .grant_loan(
+                borrower,
+                bank,
+                21,
+                1,
+                2,
+                Some(Ware::new(WareType::Plough, 2))
+            )
+            .is_err());
+        world
+            .grant_loan(
+                borrower,
+                bank,
+                20,
+                1,
+                2,
+                Some(Ware::new(WareType::Plough, 2)),
+            )
+            .unwrap();
+        assert_eq!(
+            130,
+            world.get_entity(bank).wares().ware_amount(WareType::Money)
+        );
+
+        world.update_banks();
+        world.advance_tick();
+        world.update_banks();
+        let amounts: Vec<_> = world
+            .get_entity(borrower)
+            .liabilities()
+            .iter()
+            .map(|liability| liability.amount().amount())
+            .collect();
+        assert_eq!(vec![2, 12], amounts);
+
+        world.advance_tick();
+        assert!(world.pay_liabilities().is_empty());
+        assert_eq!(
+            6,
+            world
+                .get_entity(borrower)
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+        assert_eq!(
+            144,
+            world.get_entity(bank).wares().ware_amount(WareType::Money)
+        );
+        assert_eq!(
+            10,
+            world.get_entity(bank).bank().unwrap().loans()[0].outstanding()
+        );
+
+        assert_eq!(
+            60,
+            world
+                .get_entity(bank)
+                .bank()
+                .unwrap()
+                .deposit_balance(depositor)
+        );
+        assert!(world.withdraw(depositor, bank, 61).is_err());
+        world.withdraw(depositor, bank, 60).unwrap();
+        assert_eq!(
+            60,
+            world
+                .get_entity(depositor)
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+    }
+
+    #[test]
+    fn test_credit_lines_pay_for_trades() {
+        let mut world = World::new();
+        let bank = world.create_entity("Bank", &[]);
+        let buyer = world.create_entity("Buyer", &[Recipe::from_str(EATING_RECIPE).unwrap()]);
+        let seller = world.create_entity("Seller", &[]);
+        world
+            .get_entity_mut(bank)
+            .set_bank(Bank::new(0.0, 0.2, 0.5, 1.0));
+        world.get_entity_mut(bank).add_ware(Ware::money(100));
+        world
+            .get_entity_mut(buyer)
+            .add_ware(Ware::new(WareType::Plough, 1));
+        world
+            .get_entity_mut(seller)
+            .add_ware(Ware::new(WareType::Food, 1));
+
+        assert!(world.open_credit_line(buyer, bank, 21).is_err());
+        world.open_credit_line(buyer, bank, 20).unwrap();
+
+        let mut simulation = Simulation::new(world, 0);
+        simulation.step();
+        let world = simulation.world();
+        let credit_line = world.get_entity(buyer).credit_line().unwrap();
+
+        assert_eq!(5, credit_line.drawn());
+        assert_eq!(
+            95,
+            world.get_entity(bank).wares().ware_amount(WareType::Money)
+        );
+        assert_eq!(
+            5,
+            world
+                .get_entity(seller)
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+        assert_eq!(1, world.get_entity(buyer).liabilities().len());
+        assert!(world.get_entity(buyer).health().is_none());
+    }
+
+    #[test]
+    fn test_removing_a_bank_settles_its_books() {
+        let mut world = World::new();
+        world.set_estate(Estate::Destroy);
+        let bank = world.create_entity("Bank", &[]);
+        let depositor = world.create_entity("Depositor", &[]);
+        let borrower = world.create_entity("Borrower", &[]);
+        world
+            .get_entity_mut(bank)
+            .set_bank(Bank::new(0.0, 0.2, 0.5, 1.0));
+        world.get_entity_mut(bank).add_ware(Ware::money(10));
+        world.get_entity_mut(depositor).add_ware(Ware::money(30));
+        world
+            .get_entity_mut(borrower)
+            .add_ware(Ware::new(WareType::Plough, 1));
+        world.deposit(depositor, bank, 30).unwrap();
+        world.open_credit_line(borrower, bank, 20).unwrap();
+        world.draw_credit(borrower, Ware::money(5)).unwrap();
+
+        world.remove_entity(bank);
+        assert_eq!(
+            30,
+            world
+                .get_entity(depositor)
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+        assert!(world.get_entity(borrower).credit_line().is_none());
+
+        // The borrower keeps its cash, since there is no bank left to repay.
+        world.update_banks();
+        assert_eq!(
+            5,
+            world
+                .get_entity(borrower)
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use model::{
+    entity::{agent::Agent, liability::Liability, Entity},
+    event::Event,
+    market::{fill::Fill, offer::OfferType, Market},
+    ware::{Storage, Ware, WareAmount, WareStore, WareType},
+    world::{EntityId, Resolution, World},
+};
+use rand::RngCore;
+
+/// The share of the market value at which bankrupt entities auction their wares.
+const LIQUIDATION_DISCOUNT: f64 = 0.5;
+
+/// The agent of a bankrupt entity, which auctions its inventory and buys nothing.
+/// It keeps the agent the entity had before, which takes over again if the entity is restructured.
+#[derive(Clone, Debug)]
+pub struct LiquidationAgent {
+    agent: Option<Box<dyn Agent>>,
+}
+
+impl Agent for LiquidationAgent {
+    /// Sells all wares except currencies at prices discounted from their market values,
+    /// such that the inventory sells quickly.
+    fn decide_offers(
+        &mut self,
+        entity: &Entity,
+        market: &Market,
+        _rng: &mut dyn RngCore,
+    ) -> Vec<(Ware, OfferType, Ware)> {
+        let currency = entity.currency();
+        entity
+            .wares()
+            .iter()
+            .filter(|ware| !ware.is_currency())
+            .map(|ware| {
+                let price = (market.value_in(ware.ware_type(), currency) * LIQUIDATION_DISCOUNT)
+                    .floor() as WareAmount;
+                let price_per_ware = Ware::new(currency, price.max(1));
+                (ware, OfferType::Sell, price_per_ware)
+            })
+            .collect()
+    }
+
+    fn decide_production(&mut self, entity: &Entity) -> Vec<usize> {
+        match &mut self.agent {
+            Some(agent) => agent.decide_production(entity),
+            None => (0..entity.recipes().len()).collect(),
+        }
+    }
+
+    fn on_fill(&mut self, entity: &Entity, fill: &Fill) {
+        if let Some(agent) = &mut self.agent {
+            agent.on_fill(entity, fill);
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn Agent> {
+        Box::new(self.clone())
+    }
+}
+
+/// Marks the entity as bankrupt, and hands its trading to a liquidation agent.
+fn declare_bankruptcy(entity: &mut Entity) {
+    entity.set_bankrupt(true);
+    let agent = entity.take_agent();
+    entity.set_agent(Some(Box::new(LiquidationAgent { agent })));
+}
+
+/// Clears the bankruptcy of the entity, and gives its trading back to the agent it had before.
+fn restructure(entity: &mut Entity) {
+    entity.set_bankrupt(false);
+    if let Some(liquidation) = entity.agent_as_mut::<LiquidationAgent>() {
+        let agent = liquidation.agent.take();
+        entity.set_agent(agent);
+    }
+}
+
+pub trait Insolvency {
+    fn pay_liabilities(&mut self) -> Vec<EntityId>;
+    fn liquidate_bankrupt_entities(&mut self) -> Vec<EntityId>;
+}
+
+impl Insolvency for World {
+    /// Charges the fixed costs and storage fees of all entities and pays their due liabilities.
+    /// Entities that cannot pay all of their due liabilities pay nothing and are marked bankrupt.
+    /// Returns the ids of the entities that went bankrupt.
+    fn pay_liabilities(&mut self) -> Vec<EntityId> {
+        let tick = self.tick();
+        let mut payments = Vec::new();
+        let mut bankrupt = Vec::new();
+
+        for (entity_id, entity) in self
+            .iter_entities_mut()
+            .filter(|(_, entity)| !entity.is_bankrupt())
+        {
+            for fixed_cost in entity.fixed_costs().to_vec() {
+                entity.add_liability(Liability::new(None, fixed_cost, tick, 0));
+            }
+            let storage_fee = entity
+                .wares()
+                .storage()
+                .and_then(Storage::fee)
+                .map(|fee| fee.clone() * entity.wares().volume());
+            if let Some(storage_fee) = storage_fee.filter(|fee| fee.amount() > 0) {
+                entity.add_liability(Liability::new(None, storage_fee, tick, 0));
+            }
+
+            let mut due = WareStore::new();
+            for liability in entity.liabilities() {
+                if liability.is_due(tick) {
+                    due.push_ware_unlimited(liability.amount().clone());
+                }
+            }
+
+            if entity.remove_wares(due).is_ok() {
+                payments.extend(entity.take_due_liabilities(tick));
+            } else {
+                declare_bankruptcy(entity);
+                bankrupt.push(entity_id);
+            }
+        }
+
+        for liability in payments {
+            pay(self, liability.creditor(), liability.amount().clone());
+        }
+        for &entity_id in &bankrupt {
+            self.log_event(Event::Bankruptcy(entity_id));
+        }
+        bankrupt
+    }
+
+    /// Pays the currency of all bankrupt entities to their creditors in the order of priority,
+    /// and closes or restructures them according to the resolution policy of the world.
+    /// Creditors with the same priority are paid in proportion to their claims.
+    /// Returns the ids of the liquidated entities.
+    fn liquidate_bankrupt_entities(&mut self) -> Vec<EntityId> {
+        let bankrupt: Vec<_> = self
+            .iter_entities()
+            .filter(|(_, entity)| entity.is_bankrupt())
+            .map(|(entity_id, _)| entity_id)
+            .collect();
+
+        for &entity_id in &bankrupt {
+            let entity = self.get_entity_mut(entity_id);
+            let mut liabilities = entity.take_liabilities();
+            liabilities.sort_by_key(Liability::priority);
+
+            let mut payments = Vec::new();
+            for claims in liabilities.chunk_by(|a, b| a.priority() == b.priority()) {
+                for currency in WareType::currencies() {
+                    payments.extend(pay_claims(entity, claims, currency));
+                }
+            }
+            for (creditor, payment) in &payments {
+                pay(self, *creditor, payment.clone());
+            }
+
+            let closed = self.resolution() == Resolution::Close;
+            if closed {
+                self.remove_entity(entity_id);
+            } else {
+                restructure(self.get_entity_mut(entity_id));
+            }
+            self.log_event(Event::Liquidation {
+                entity_id,
+                payments,
+                closed,
+            });
+        }
+        bankrupt
+    }
+}
+
+/// Pays the claims in the given currency from the holdings of the entity,
+/// in proportion to the claims if the holdings do not cover all of them.
+fn pay_claims(
+    entity: &mut Entity,
+    claims: &[Liability],
+    currency: WareType,
+) -> Vec<(Option<EntityId>, Ware)> {
+    let claims: Vec<_> = claims
+        .iter()
+        .filter(|claim| claim.amount().ware_type() == currency)
+        .collect();
+    let total: u64 = claims
+        .iter()
+        .map(|claim| u64::from(claim.amount().amount()))
+        .sum();
+    let available = u64::from(entity.wares().ware_amount(currency));
+
+    let mut payments = Vec::new();
+    for claim in claims {
+        let amount = if available >= total {
+            claim.amount().amount()
+        } else {
+            (u64::from(claim.amount().amount()) * available / total) as WareAmount
+        };
+        if amount > 0 {
+            let payment = entity.remove_ware(Ware::new(currency, amount)).unwrap();
+            payments.push((claim.creditor(), payment));
+        }
+    }
+    payments
+}
+
+/// Gives the payment to the creditor, or to no one if there is none or it left the world.
+/// A payment the creditor has no room for is recorded as lost by it.
+fn pay(world: &mut World, creditor: Option<EntityId>, payment: Ware) {
+    if let Some(creditor) = creditor.and_then(|creditor| world.try_get_entity_mut(creditor)) {
+        creditor.add_ware(payment);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        bankruptcy::{declare_bankruptcy, Insolvency, LiquidationAgent},
+        simulation::Simulation,
+    };
+    use model::{
+        entity::{liability::Liability, utility::Utility},
+        event::Event,
+        ware::{Storage, Ware, WareType},
+        world::{Resolution, World},
+    };
+
+    #[test]
+    fn test_fixed_costs_and_bankruptcy() {
+        let mut world = World::new();
+        world.set_resolution(Resolution::Restructure);
+        let firm = world.create_entity("Firm", &[]);
+        world.get_entity_mut(firm).add_ware(Ware::money(3));
+        world
+            .get_entity_mut(firm)
+            .set_fixed_costs(vec![Ware::money(2)]);
+
+        assert!(world.pay_liabilities().is_empty());
+        assert_eq!(
+            1,
+            world.get_entity(firm).wares().ware_amount(WareType::Money)
+        );
+        assert_eq!(vec![firm], world.pay_liabilities());
+        assert!(world.get_entity(firm).is_bankrupt());
+        assert!(world
+            .get_entity(firm)
+            .agent_as::<LiquidationAgent>()
+            .is_some());
+        assert_eq!(&[(0, Event::Bankruptcy(firm))], world.events());
+
+        assert_eq!(vec![firm], world.liquidate_bankrupt_entities());
+        let firm = world.get_entity(firm);
+        assert!(!firm.is_bankrupt());
+        assert!(firm.agent().is_none());
+        assert!(firm.liabilities().is_empty());
+        assert_eq!(0, firm.wares().ware_amount(WareType::Money));
+    }
+
+    #[test]
+    fn test_storage_fees() {
+        let mut world = World::new();
+        let warehouse = world.create_entity("Warehouse", &[]);
+        let mut storage = Storage::new(None);
+        storage.set_fee(Some(Ware::money(1)));
+        let entity = world.get_entity_mut(warehouse);
+        entity.set_storage(Some(storage));
+        entity.add_ware(Ware::money(5));
+        entity.add_ware(Ware::new(WareType::Plough, 1));
+        entity.add_ware(Ware::new(WareType::Food, 1));
+
+        assert!(world.pay_liabilities().is_empty());
+        assert_eq!(
+            2,
+            world
+                .get_entity(warehouse)
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+        assert_eq!(vec![warehouse], world.pay_liabilities());
+    }
+
+    #[test]
+    fn test_liquidation_pays_creditors_in_priority_order() {
+        let mut world = World::new();
+        let debtor = world.create_entity("Debtor", &[]);
+        let senior = world.create_entity("Senior", &[]);
+        let juniors = [
+            world.create_entity("Junior 1", &[]),
+            world.create_entity("Junior 2", &[]),
+        ];
+        let buyer = world.create_entity("Buyer", &[]);
+        world
+            .get_entity_mut(buyer)
+            .set_utility(Utility::CobbDouglas(vec![(WareType::Food, 1.0)]));
+        world.get_entity_mut(buyer).add_ware(Ware::money(100));
+
+        let entity = world.get_entity_mut(debtor);
+        entity.add_ware(Ware::new(WareType::Food, 4));
+        entity.add_liability(Liability::new(Some(juniors[0]), Ware::money(4), 5, 1));
+        entity.add_liability(Liability::new(Some(senior), Ware::money(6), 0, 0));
+        entity.add_liability(Liability::new(Some(juniors[1]), Ware::money(4), 5, 1));
+        declare_bankruptcy(entity);
+
+        let mut simulation = Simulation::new(world, 0);
+        simulation.step();
+        let world = simulation.world();
+        let money = |entity_id| {
+            world
+                .get_entity(entity_id)
+                .wares()
+                .ware_amount(WareType::Money)
+        };
+
+        assert!(!world.contains_entity(debtor));
+        assert!(world.get_entity(buyer).utility_level() > 0.0);
+        assert_eq!(92, money(buyer));
+        assert_eq!(6, money(senior));
+        assert_eq!(1, money(juniors[0]));
+        assert_eq!(1, money(juniors[1]));
+        assert_eq!(
+            &[(
+                0,
+                Event::Liquidation {
+                    entity_id: debtor,
+                    payments: vec![
+                        (Some(senior), Ware::money(6)),
+                        (Some(juniors[0]), Ware::money(1)),
+                        (Some(juniors[1]), Ware::money(1)),
+                    ],
+                    closed: true,
+                }
+            )],
+            world.events()
+        );
+    }
+}
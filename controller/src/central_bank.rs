@@ -0,0 +1,291 @@
+use model::{
+    entity::{
+        agent::Agent,
+        central_bank::{CentralBank, OpenMarketOperation},
+        Entity,
+    },
+    market::{offer::OfferType, Market},
+    ware::{Ware, WareAmount, WareStore},
+    world::World,
+};
+use rand::RngCore;
+use std::collections::HashMap;
+
+/// The agent of the central bank, which offers its open market operations of this tick.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OpenMarketAgent;
+
+impl Agent for OpenMarketAgent {
+    fn decide_offers(
+        &mut self,
+        entity: &Entity,
+        _market: &Market,
+        _rng: &mut dyn RngCore,
+    ) -> Vec<(Ware, OfferType, Ware)> {
+        entity
+            .central_bank()
+            .map_or(&[][..], CentralBank::operations)
+            .iter()
+            .map(|operation| {
+                (
+                    operation.ware().clone(),
+                    operation.offer_type(),
+                    operation.price_per_ware().clone(),
+                )
+            })
+            .collect()
+    }
+
+    fn box_clone(&self) -> Box<dyn Agent> {
+        Box::new(*self)
+    }
+}
+
+pub trait MonetaryPolicy {
+    fn helicopter_drop(&mut self, amount: WareAmount) -> Result<(), ()>;
+    fn prepare_open_market_operations(&mut self);
+    fn update_price_index(&mut self);
+    fn update_monetary_policy(&mut self);
+}
+
+impl MonetaryPolicy for World {
+    /// Creates the given amount of the currency of the central bank for every household.
+    /// Fails if the world has no central bank.
+    fn helicopter_drop(&mut self, amount: WareAmount) -> Result<(), ()> {
+        let central_bank_id = self.central_bank_id().ok_or(())?;
+        let currency = self.get_entity(central_bank_id).currency();
+
+        let mut households = 0;
+        for entity in self.entities_mut().filter(|entity| entity.is_household()) {
+            entity.add_ware(Ware::new(currency, amount));
+            households += 1;
+        }
+        self.get_entity_mut(central_bank_id)
+            .central_bank_mut()
+            .unwrap()
+            .record_created(amount * households);
+        Ok(())
+    }
+
+    /// Creates the money for the buy operations of the central bank, and limits its sell operations
+    /// to the wares it holds, such that its agent can offer them in this tick.
+    fn prepare_open_market_operations(&mut self) {
+        let central_bank_id = match self.central_bank_id() {
+            Some(central_bank_id) => central_bank_id,
+            None => return,
+        };
+        let entity = self.get_entity_mut(central_bank_id);
+        let currency = entity.currency();
+        let operations = entity.central_bank_mut().unwrap().take_operations();
+
+        for operation in operations {
+            debug_assert_eq!(currency, operation.price_per_ware().ware_type());
+
+            let mut ware = operation.ware().clone();
+            match operation.offer_type() {
+                OfferType::Buy => {
+                    let payment = operation.price_per_ware().clone() * ware.amount();
+                    entity
+                        .central_bank_mut()
+                        .unwrap()
+                        .record_created(payment.amount());
+                    entity.add_ware(payment);
+                }
+                OfferType::Sell => {
+                    *ware.amount_mut() = ware
+                        .amount()
+                        .min(entity.wares().ware_amount(ware.ware_type()));
+                }
+            }
+            if ware.amount() > 0 {
+                entity
+                    .central_bank_mut()
+                    .unwrap()
+                    .add_operation(OpenMarketOperation::new(
+                        ware,
+                        operation.offer_type(),
+                        operation.price_per_ware().clone(),
+                    ));
+            }
+        }
+    }
+
+    /// Records the cost of the basket of the price index at the current market prices.
+    fn update_price_index(&mut self) {
+        let price_index = self.price_index();
+        if price_index.basket().is_empty() {
+            return;
+        }
+
+        let mut basket = WareStore::new();
+        for ware in price_index.basket() {
+            basket.push_ware_unlimited(ware.clone());
+        }
+        let cost = self.market().value_of(&basket, price_index.currency());
+        self.price_index_mut().record(cost);
+    }
+
+    /// Ends the open market operations of this tick and destroys the money the central bank received.
+    /// Then sets the policy rate by its rule,
+    /// from the inflation of the price index and the unemployment rate.
+    /// Banks that follow the policy rate update their rates and those of their credit lines.
+    fn update_monetary_policy(&mut self) {
+        let central_bank_id = match self.central_bank_id() {
+            Some(central_bank_id) => central_bank_id,
+            None => return,
+        };
+        let inflation = self.price_index().inflation();
+        let unemployment = self.labor_stats().unemployment_rate();
+
+        let entity = self.get_entity_mut(central_bank_id);
+        let currency = entity.currency();
+        let money = entity.wares().ware_amount(currency);
+        if money > 0 {
+            entity.remove_ware(Ware::new(currency, money)).unwrap();
+        }
+        let central_bank = entity.central_bank_mut().unwrap();
+        central_bank.take_operations();
+        central_bank.record_destroyed(money);
+        if let (Some(rule), Some(inflation)) = (central_bank.rule(), inflation) {
+            let policy_rate = rule.rate(inflation, unemployment);
+            central_bank.set_policy_rate(policy_rate);
+        }
+        let policy_rate = central_bank.policy_rate();
+
+        let mut loan_rates = HashMap::new();
+        for (bank_id, entity) in self.iter_entities_mut() {
+            if let Some(bank) = entity.bank_mut().filter(|bank| bank.spread().is_some()) {
+                bank.follow_policy_rate(policy_rate);
+                loan_rates.insert(bank_id, bank.loan_rate());
+            }
+        }
+        for entity in self.entities_mut() {
+            if let Some(credit_line) = entity.credit_line_mut() {
+                if let Some(&loan_rate) = loan_rates.get(&credit_line.bank()) {
+                    credit_line.set_rate(loan_rate);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{central_bank::MonetaryPolicy, simulation::Simulation};
+    use model::{
+        entity::{
+            bank::{Bank, CreditLine},
+            central_bank::{CentralBank, OpenMarketOperation, TaylorRule},
+            utility::Utility,
+        },
+        market::offer::OfferType,
+        price_index::PriceIndex,
+        ware::{Ware, WareType},
+        world::World,
+    };
+
+    #[test]
+    fn test_central_bank_creates_and_destroys_money() {
+        let mut world = World::new();
+        let central_bank = world.create_entity("Central Bank", &[]);
+        let seller = world.create_entity("Seller", &[]);
+        let household = world.create_entity("Household", &[]);
+        let mut policy = CentralBank::new(0.01);
+        policy.add_operation(OpenMarketOperation::new(
+            Ware::new(WareType::Food, 2),
+            OfferType::Buy,
+            Ware::money(6),
+        ));
+        world.get_entity_mut(central_bank).set_central_bank(policy);
+        world
+            .get_entity_mut(seller)
+            .add_ware(Ware::new(WareType::Food, 3));
+        world
+            .get_entity_mut(household)
+            .set_utility(Utility::CobbDouglas(vec![(WareType::Water, 1.0)]));
+
+        world.helicopter_drop(10).unwrap();
+        assert_eq!(
+            10,
+            world
+                .get_entity(household)
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+
+        let mut simulation = Simulation::new(world, 0);
+        simulation.step();
+        let world = simulation.world();
+        let entity = world.get_entity(central_bank);
+        let policy = entity.central_bank().unwrap();
+
+        assert_eq!(2, entity.wares().ware_amount(WareType::Food));
+        assert_eq!(0, entity.wares().ware_amount(WareType::Money));
+        assert_eq!(
+            10,
+            world
+                .get_entity(seller)
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+        assert_eq!(22, policy.created());
+        assert_eq!(2, policy.destroyed());
+        assert_eq!(20, policy.net_issuance());
+        assert!(policy.operations().is_empty());
+    }
+
+    #[test]
+    fn test_taylor_rule_sets_the_rates_of_banks() {
+        let mut world = World::new();
+        let central_bank = world.create_entity("Central Bank", &[]);
+        let bank = world.create_entity("Bank", &[]);
+        let borrower = world.create_entity("Borrower", &[]);
+        let mut policy = CentralBank::new(0.01);
+        policy.set_rule(Some(TaylorRule::new(0.01, 0.02, 0.5, 0.0, 0.5)));
+        world.get_entity_mut(central_bank).set_central_bank(policy);
+        let mut commercial = Bank::new(0.0, 0.05, 0.5, 1.0);
+        commercial.set_spread(Some(0.02));
+        world.get_entity_mut(bank).set_bank(commercial);
+        world
+            .get_entity_mut(borrower)
+            .set_credit_line(Some(CreditLine::new(bank, Ware::money(10), 0.05)));
+        world.set_price_index(PriceIndex::new(
+            vec![Ware::new(WareType::Food, 1)],
+            WareType::Money,
+        ));
+
+        world
+            .market_mut()
+            .set_last_price(WareType::Food, WareType::Money, 10);
+        world.update_price_index();
+        world.update_monetary_policy();
+        assert_eq!(
+            0.01,
+            world
+                .get_entity(central_bank)
+                .central_bank()
+                .unwrap()
+                .policy_rate()
+        );
+
+        world
+            .market_mut()
+            .set_last_price(WareType::Food, WareType::Money, 11);
+        world.update_price_index();
+        world.update_monetary_policy();
+        let policy_rate = world
+            .get_entity(central_bank)
+            .central_bank()
+            .unwrap()
+            .policy_rate();
+        let bank = world.get_entity(bank).bank().unwrap();
+
+        assert!((policy_rate - 0.15).abs() < 1e-9);
+        assert_eq!(policy_rate, bank.deposit_rate());
+        assert_eq!(policy_rate + 0.02, bank.loan_rate());
+        assert_eq!(
+            bank.loan_rate(),
+            world.get_entity(borrower).credit_line().unwrap().rate()
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use model::{
+    entity::liability::Liability,
+    event::Event,
+    market::fill::Fill,
+    ware::WareStore,
+    world::{EntityId, World},
+};
+use std::collections::HashMap;
+
+/// The priority of contract penalties in a liquidation.
+const PENALTY_PRIORITY: u32 = 1;
+
+pub trait Contracting {
+    fn execute_contracts(&mut self) -> Vec<Fill>;
+}
+
+impl Contracting for World {
+    /// Executes the deliveries of all supply contracts due at the current tick, and returns them as fills.
+    /// A supplier that lacks the ware, or else a customer that cannot pay or store it, breaches the contract:
+    /// nothing is delivered, and it owes the penalty to the other party, due at once.
+    /// Then sets the commitments of all entities for the deliveries due in the next tick.
+    fn execute_contracts(&mut self) -> Vec<Fill> {
+        let tick = self.tick();
+        let active: Vec<_> = self
+            .contracts()
+            .active(tick)
+            .map(|(contract_id, contract)| (contract_id, contract.clone()))
+            .collect();
+
+        let mut fills = Vec::new();
+        for (contract_id, contract) in active {
+            let (supplier, customer) = (contract.supplier(), contract.customer());
+            let fill = Fill::new(
+                contract.ware().clone(),
+                contract.price_per_ware().clone(),
+                customer,
+                supplier,
+            );
+            let has_ware = self.try_get_entity(supplier).is_some_and(|entity| {
+                entity.wares().ware_amount(contract.ware().ware_type()) >= contract.ware().amount()
+            });
+            let (breaching, counterparty) = if !has_ware {
+                (supplier, customer)
+            } else if self.settle(&fill).is_ok() {
+                self.contracts_mut()
+                    .get_mut(contract_id)
+                    .unwrap()
+                    .record_delivery();
+                fills.push(fill);
+                continue;
+            } else {
+                (customer, supplier)
+            };
+
+            self.contracts_mut()
+                .get_mut(contract_id)
+                .unwrap()
+                .record_breach();
+            let creditor = self.try_get_entity(counterparty).map(|_| counterparty);
+            if let Some(entity) = self
+                .try_get_entity_mut(breaching)
+                .filter(|_| creditor.is_some() && contract.penalty().amount() > 0)
+            {
+                entity.add_liability(Liability::new(
+                    creditor,
+                    contract.penalty().clone(),
+                    tick,
+                    PENALTY_PRIORITY,
+                ));
+            }
+            self.log_event(Event::Breach {
+                contract_id,
+                entity_id: breaching,
+            });
+        }
+
+        let mut commitments: HashMap<EntityId, (WareStore, WareStore)> = HashMap::new();
+        for (_, contract) in self.contracts().active(tick + 1) {
+            commitments
+                .entry(contract.supplier())
+                .or_default()
+                .0
+                .push_ware_unlimited(contract.ware().clone());
+            commitments
+                .entry(contract.customer())
+                .or_default()
+                .1
+                .push_ware_unlimited(contract.ware().clone());
+        }
+        for (entity_id, entity) in self.iter_entities_mut() {
+            let (deliveries, supplies) = commitments.remove(&entity_id).unwrap_or_default();
+            entity.set_commitments(deliveries, supplies);
+        }
+        fills
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{contract::Contracting, trading::Trader};
+    use model::{
+        contract::SupplyContract,
+        entity::recipe::Recipe,
+        event::Event,
+        ware::{Ware, WareType},
+        world::World,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn test_supply_contract() {
+        let mut world = World::new();
+        let supplier = world.create_entity("Farmer", &[]);
+        let customer = world.create_entity(
+            "Baker",
+            &[Recipe::from_str("(4x Food) -> (1x Water)").unwrap()],
+        );
+        world
+            .get_entity_mut(supplier)
+            .add_ware(Ware::new(WareType::Food, 10));
+        world.get_entity_mut(customer).add_ware(Ware::money(100));
+        let food = Ware::new(WareType::Food, 2);
+        let contract_id = world
+            .create_contract(SupplyContract::new(
+                supplier,
+                customer,
+                food.clone(),
+                Ware::money(5),
+                0,
+                3,
+                Ware::money(20),
+            ))
+            .unwrap();
+        assert!(world
+            .create_contract(SupplyContract::new(
+                supplier,
+                supplier,
+                food,
+                Ware::money(5),
+                0,
+                3,
+                Ware::money(20),
+            ))
+            .is_err());
+
+        assert_eq!(1, world.execute_contracts().len());
+        let (tradable_wares, _) = world
+            .get_entity(supplier)
+            .tradable_wares_and_unmet_demands();
+        assert_eq!(6, tradable_wares.ware_amount(WareType::Food));
+        let (_, unmet_demands) = world
+            .get_entity(customer)
+            .tradable_wares_and_unmet_demands();
+        // The first delivery arrived, but the next one comes too late for this tick's recipe.
+        assert_eq!(2, unmet_demands.ware_amount(WareType::Food));
+        assert_eq!(
+            90,
+            world
+                .get_entity(customer)
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+
+        for _ in 0..3 {
+            world.advance_tick();
+            world.execute_contracts();
+        }
+        let contract = world.contracts().get(contract_id).unwrap();
+        assert_eq!(3, contract.deliveries());
+        assert_eq!(0, contract.breaches());
+        assert_eq!(
+            6,
+            world
+                .get_entity(customer)
+                .wares()
+                .ware_amount(WareType::Food)
+        );
+        assert_eq!(
+            0,
+            world
+                .get_entity(supplier)
+                .committed_deliveries()
+                .ware_amount(WareType::Food)
+        );
+    }
+
+    #[test]
+    fn test_contract_breach() {
+        let mut world = World::new();
+        let supplier = world.create_entity("Farmer", &[]);
+        let customer = world.create_entity("Baker", &[]);
+        world
+            .get_entity_mut(supplier)
+            .add_ware(Ware::new(WareType::Food, 3));
+        world.get_entity_mut(customer).add_ware(Ware::money(10));
+        let contract_id = world
+            .create_contract(SupplyContract::new(
+                supplier,
+                customer,
+                Ware::new(WareType::Food, 2),
+                Ware::money(5),
+                0,
+                5,
+                Ware::money(7),
+            ))
+            .unwrap();
+
+        // The customer pays once, then runs out of money, and the supplier runs out of food.
+        assert_eq!(1, world.execute_contracts().len());
+        world
+            .get_entity_mut(supplier)
+            .add_ware(Ware::new(WareType::Food, 1));
+        world.advance_tick();
+        assert!(world.execute_contracts().is_empty());
+        world.get_entity_mut(customer).add_ware(Ware::money(10));
+        world
+            .get_entity_mut(supplier)
+            .remove_ware(Ware::new(WareType::Food, 2))
+            .unwrap();
+        world.advance_tick();
+        assert!(world.execute_contracts().is_empty());
+
+        assert_eq!(2, world.contracts().get(contract_id).unwrap().breaches());
+        assert_eq!(
+            &[Some(supplier)],
+            &world
+                .get_entity(customer)
+                .liabilities()
+                .iter()
+                .map(|liability| liability.creditor())
+                .collect::<Vec<_>>()[..]
+        );
+        assert_eq!(
+            &(
+                2,
+                Event::Breach {
+                    contract_id,
+                    entity_id: supplier
+                }
+            ),
+            world.events().last().unwrap()
+        );
+        assert_eq!(
+            Ware::money(7),
+            *world.get_entity(supplier).liabilities()[0].amount()
+        );
+    }
+}
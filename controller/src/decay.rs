@@ -0,0 +1,114 @@
+use crate::production::round_randomly;
+use model::{
+    ware::{Ware, WareStore},
+    world::World,
+};
+use rand::Rng;
+
+pub trait Spoilage {
+    fn decay_wares<R: Rng>(&mut self, rng: &mut R) -> WareStore;
+}
+
+impl Spoilage for World {
+    /// Removes the wares that spoiled in this tick from all entities, records them per entity, and returns their sum.
+    /// Wares of a type with a shelf life are tracked in lots from the tick they arrive,
+    /// and lots that reached the shelf life spoil. Then the decay rate applies to the rest, taken from the oldest lots.
+    /// The amount lost to the decay rate is rounded randomly.
+    fn decay_wares<R: Rng>(&mut self, rng: &mut R) -> WareStore {
+        let tick = self.tick();
+        let decay = self.decay().to_vec();
+
+        let mut spoiled = WareStore::new();
+        for entity in self.entities_mut() {
+            for &(ware_type, decay) in &decay {
+                if let Some(shelf_life) = decay.shelf_life() {
+                    entity.stamp_lots(ware_type, tick);
+                    let expired = tick
+                        .checked_sub(shelf_life)
+                        .map_or(0, |stored| entity.spoil_lots_before(ware_type, stored + 1));
+                    spoiled.push_ware_unlimited(Ware::new(ware_type, expired));
+                }
+
+                let amount = entity.wares().ware_amount(ware_type);
+                if amount == 0 || decay.rate() == 0.0 {
+                    continue;
+                }
+                let lost = round_randomly(f64::from(amount) * decay.rate(), rng);
+                let lost = Ware::new(ware_type, lost.min(amount));
+                entity.spoil(lost.clone()).unwrap();
+                spoiled.push_ware_unlimited(lost);
+            }
+        }
+        spoiled
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{decay::Spoilage, simulation::Simulation};
+    use model::{
+        entity::recipe::Recipe,
+        ware::{Decay, Lot, Ware, WareType},
+        world::World,
+    };
+    use rand::SeedableRng;
+    use rand_pcg::Pcg64Mcg;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_decay_rate() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        let mut world = World::new();
+        world.set_decay(WareType::Food, Decay::new(0.25, None));
+        let entity_id = world.create_entity("Storehouse", &[]);
+        world
+            .get_entity_mut(entity_id)
+            .add_ware(Ware::new(WareType::Food, 8));
+        world
+            .get_entity_mut(entity_id)
+            .add_ware(Ware::new(WareType::Water, 8));
+
+        let spoiled = world.decay_wares(&mut rng);
+        let entity = world.get_entity(entity_id);
+        assert_eq!(2, spoiled.ware_amount(WareType::Food));
+        assert_eq!(6, entity.wares().ware_amount(WareType::Food));
+        assert_eq!(8, entity.wares().ware_amount(WareType::Water));
+        assert_eq!(2, entity.spoiled().ware_amount(WareType::Food));
+
+        for _ in 0..20 {
+            world.decay_wares(&mut rng);
+        }
+        let entity = world.get_entity(entity_id);
+        assert_eq!(0, entity.wares().ware_amount(WareType::Food));
+        assert_eq!(8, entity.spoiled().ware_amount(WareType::Food));
+    }
+
+    #[test]
+    fn test_shelf_life() {
+        let mut world = World::new();
+        world.set_decay(WareType::Food, Decay::new(0.0, Some(2)));
+        let farmer = world.create_entity("Farmer", &[Recipe::from_str("() -> (2x Food)").unwrap()]);
+        world
+            .get_entity_mut(farmer)
+            .sell_prices_mut()
+            .set_single_price(WareType::Food, 100);
+
+        let mut simulation = Simulation::new(world, 0);
+        simulation.step();
+        simulation.step();
+        let entity = simulation.world().get_entity(farmer);
+        assert_eq!(
+            vec![Lot::new(0, 2), Lot::new(1, 2)],
+            entity.wares().lots(WareType::Food)
+        );
+
+        simulation.step();
+        let entity = simulation.world().get_entity(farmer);
+        assert_eq!(
+            vec![Lot::new(1, 2), Lot::new(2, 2)],
+            entity.wares().lots(WareType::Food)
+        );
+        assert_eq!(4, entity.wares().ware_amount(WareType::Food));
+        assert_eq!(2, entity.spoiled().ware_amount(WareType::Food));
+    }
+}
@@ -0,0 +1,341 @@
+use model::{
+    entity::{
+        agent::Agent,
+        government::{FiscalStats, Transfer},
+        liability::Liability,
+        Entity,
+    },
+    market::{fill::Fill, offer::OfferType, Market},
+    ware::{Ware, WareAmount, WareType},
+    world::{EntityId, World},
+};
+use rand::RngCore;
+use std::collections::{HashMap, HashSet};
+
+/// The priority of taxes in a liquidation.
+const TAX_PRIORITY: u32 = 0;
+
+/// The agent of the government, which buys its purchases.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProcurementAgent;
+
+impl Agent for ProcurementAgent {
+    /// Buys the purchases of the government, spending at most the currency it holds.
+    fn decide_offers(
+        &mut self,
+        entity: &Entity,
+        _market: &Market,
+        _rng: &mut dyn RngCore,
+    ) -> Vec<(Ware, OfferType, Ware)> {
+        let government = match entity.government() {
+            Some(government) => government,
+            None => return Vec::new(),
+        };
+
+        let mut budget = entity.wares().ware_amount(entity.currency());
+        let mut offers = Vec::new();
+        for (ware, price_per_ware) in government.purchases() {
+            let amount = ware.amount().min(budget / price_per_ware.amount());
+            if amount > 0 {
+                budget -= amount * price_per_ware.amount();
+                offers.push((
+                    Ware::new(ware.ware_type(), amount),
+                    OfferType::Buy,
+                    price_per_ware.clone(),
+                ));
+            }
+        }
+        offers
+    }
+
+    fn box_clone(&self) -> Box<dyn Agent> {
+        Box::new(*self)
+    }
+}
+
+pub trait FiscalPolicy {
+    fn levy_taxes(&mut self, fills: &[Fill]);
+    fn pay_transfers(&mut self, fills: &[Fill]);
+}
+
+impl FiscalPolicy for World {
+    /// Charges the taxes of this tick as liabilities to the government, due at once.
+    /// Sales and income taxes apply to the given fills that were paid in the currency of the government,
+    /// wealth taxes to the holdings of that currency, and head taxes to households.
+    /// The government and the central bank pay no taxes.
+    fn levy_taxes(&mut self, fills: &[Fill]) {
+        let government_id = match self.government_id() {
+            Some(government_id) => government_id,
+            None => return,
+        };
+        let tick = self.tick();
+        let entity = self.get_entity_mut(government_id);
+        let currency = entity.currency();
+        let government = entity.government_mut().unwrap();
+        government.apply_scheduled_taxes(tick);
+        let taxes = government.taxes().clone();
+
+        let mut stats = FiscalStats::default();
+        let mut charges: HashMap<EntityId, WareAmount> = HashMap::new();
+        let mut incomes: HashMap<EntityId, WareAmount> = HashMap::new();
+        for fill in fills
+            .iter()
+            .filter(|fill| fill.price_per_ware().ware_type() == currency)
+        {
+            let total_price = fill.total_price().amount();
+            if fill.buyer() != government_id {
+                let sales_tax = (f64::from(total_price) * taxes.sales_tax()).floor() as WareAmount;
+                stats.record_sales_tax(sales_tax);
+                *charges.entry(fill.buyer()).or_default() += sales_tax;
+            }
+            if fill.seller() != government_id {
+                *incomes.entry(fill.seller()).or_default() += total_price;
+            }
+        }
+        for (seller, income) in incomes {
+            let income_tax = taxes.income_tax(income);
+            stats.record_income_tax(income_tax);
+            *charges.entry(seller).or_default() += income_tax;
+        }
+
+        for (entity_id, entity) in self
+            .iter_entities()
+            .filter(|(_, entity)| !entity.is_government() && !entity.is_central_bank())
+        {
+            let wealth_tax = (f64::from(entity.wares().ware_amount(currency)) * taxes.wealth_tax())
+                .floor() as WareAmount;
+            stats.record_wealth_tax(wealth_tax);
+            let mut charge = wealth_tax;
+            if entity.is_household() {
+                stats.record_head_tax(taxes.head_tax());
+                charge += taxes.head_tax();
+            }
+            *charges.entry(entity_id).or_default() += charge;
+        }
+
+        for (entity_id, charge) in charges {
+            if let Some(entity) = self.try_get_entity_mut(entity_id).filter(|_| charge > 0) {
+                entity.add_liability(Liability::new(
+                    Some(government_id),
+                    Ware::new(currency, charge),
+                    tick,
+                    TAX_PRIORITY,
+                ));
+            }
+        }
+        *self
+            .get_entity_mut(government_id)
+            .government_mut()
+            .unwrap()
+            .stats_mut() = stats;
+    }
+
+    /// Pays the transfers of the government from the currency it holds.
+    /// If it holds too little, all transfers are scaled down alike.
+    /// The wares the government bought in the given fills are consumed.
+    fn pay_transfers(&mut self, fills: &[Fill]) {
+        let government_id = match self.government_id() {
+            Some(government_id) => government_id,
+            None => return,
+        };
+        let entity = self.get_entity(government_id);
+        let currency = entity.currency();
+        let cash = entity.wares().ware_amount(currency);
+        let transfers = entity.government().unwrap().transfers().to_vec();
+
+        let employed: HashSet<_> = fills
+            .iter()
+            .filter(|fill| fill.ware().ware_type() == WareType::Labor)
+            .map(|fill| fill.seller())
+            .collect();
+        let mut payments = Vec::new();
+        for transfer in transfers {
+            for (entity_id, entity) in self
+                .iter_entities()
+                .filter(|(entity_id, _)| *entity_id != government_id)
+            {
+                match transfer {
+                    Transfer::BasicIncome(amount) if entity.is_household() => {
+                        payments.push((entity_id, amount));
+                    }
+                    Transfer::UnemploymentBenefit(amount)
+                        if entity.is_worker() && !employed.contains(&entity_id) =>
+                    {
+                        payments.push((entity_id, amount));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let total: u64 = payments.iter().map(|(_, amount)| u64::from(*amount)).sum();
+        let mut transferred = 0;
+        for (entity_id, amount) in payments {
+            let amount = if total > u64::from(cash) {
+                (u64::from(amount) * u64::from(cash) / total) as WareAmount
+            } else {
+                amount
+            };
+            if amount > 0 {
+                let payment = self
+                    .get_entity_mut(government_id)
+                    .remove_ware(Ware::new(currency, amount))
+                    .unwrap();
+                self.get_entity_mut(entity_id).add_ware(payment);
+                transferred += amount;
+            }
+        }
+
+        let purchases: WareAmount = fills
+            .iter()
+            .filter(|fill| fill.buyer() == government_id)
+            .map(|fill| fill.total_price().amount())
+            .sum();
+        let entity = self.get_entity_mut(government_id);
+        let consumed: Vec<_> = entity
+            .wares()
+            .iter()
+            .filter(|ware| !ware.is_currency())
+            .collect();
+        for ware in consumed {
+            entity.remove_ware(ware).unwrap();
+        }
+        let stats = entity.government_mut().unwrap().stats_mut();
+        stats.record_transfers(transferred);
+        stats.record_purchases(purchases);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::simulation::Simulation;
+    use model::{
+        entity::{
+            government::{Government, Taxes, Transfer},
+            utility::Utility,
+        },
+        ware::{Ware, WareType},
+        world::World,
+    };
+
+    #[test]
+    fn test_taxes_and_basic_income() {
+        let mut world = World::new();
+        let government = world.create_entity("Government", &[]);
+        let household = world.create_entity("Household", &[]);
+        let seller = world.create_entity("Seller", &[]);
+        let mut taxes = Taxes::new();
+        taxes.set_sales_tax(0.1);
+        taxes.set_income_tax(vec![(0, 0.2)]);
+        taxes.set_head_tax(1);
+        let mut policy = Government::new(taxes);
+        policy.add_transfer(Transfer::BasicIncome(2));
+        world.get_entity_mut(government).set_government(policy);
+        world
+            .get_entity_mut(household)
+            .set_utility(Utility::CobbDouglas(vec![(WareType::Food, 1.0)]));
+        world.get_entity_mut(household).add_ware(Ware::money(50));
+        world
+            .get_entity_mut(seller)
+            .add_ware(Ware::new(WareType::Food, 2));
+
+        let mut simulation = Simulation::new(world, 0);
+        simulation.step();
+        let world = simulation.world();
+        let money = |entity_id| {
+            world
+                .get_entity(entity_id)
+                .wares()
+                .ware_amount(WareType::Money)
+        };
+        let stats = world.get_entity(government).government().unwrap().stats();
+
+        assert_eq!(40, money(household));
+        assert_eq!(8, money(seller));
+        assert_eq!(2, money(government));
+        assert_eq!(
+            (1, 2, 0, 1),
+            (
+                stats.sales_tax(),
+                stats.income_tax(),
+                stats.wealth_tax(),
+                stats.head_tax()
+            )
+        );
+        assert_eq!(2, stats.transfers());
+        assert_eq!(2, stats.balance());
+    }
+
+    #[test]
+    fn test_scheduled_taxes_and_unemployment_benefits() {
+        let mut world = World::new();
+        let government = world.create_entity("Government", &[]);
+        let worker = world.create_entity("Worker", &[]);
+        let mut policy = Government::new(Taxes::new());
+        let mut taxes = Taxes::new();
+        taxes.set_head_tax(3);
+        policy.schedule_taxes(1, taxes);
+        policy.add_transfer(Transfer::UnemploymentBenefit(4));
+        world.get_entity_mut(government).set_government(policy);
+        world.get_entity_mut(government).add_ware(Ware::money(10));
+        world
+            .get_entity_mut(worker)
+            .set_utility(Utility::CobbDouglas(vec![(WareType::Water, 1.0)]));
+        world.get_entity_mut(worker).set_labor(8);
+
+        let mut simulation = Simulation::new(world, 0);
+        simulation.step();
+        let money = |simulation: &Simulation, entity_id| {
+            simulation
+                .world()
+                .get_entity(entity_id)
+                .wares()
+                .ware_amount(WareType::Money)
+        };
+        assert_eq!(4, money(&simulation, worker));
+
+        simulation.step();
+        assert_eq!(5, money(&simulation, worker));
+        assert_eq!(5, money(&simulation, government));
+
+        simulation
+            .world_mut()
+            .get_entity_mut(government)
+            .government_mut()
+            .unwrap()
+            .clear_transfers();
+        simulation.step();
+        assert_eq!(2, money(&simulation, worker));
+        assert_eq!(8, money(&simulation, government));
+    }
+
+    #[test]
+    fn test_government_purchases() {
+        let mut world = World::new();
+        let government = world.create_entity("Government", &[]);
+        let seller = world.create_entity("Seller", &[]);
+        let mut policy = Government::new(Taxes::new());
+        policy.add_purchase(Ware::new(WareType::Water, 10), Ware::money(2));
+        world.get_entity_mut(government).set_government(policy);
+        world.get_entity_mut(government).add_ware(Ware::money(9));
+        world
+            .get_entity_mut(seller)
+            .add_ware(Ware::new(WareType::Water, 10));
+
+        let mut simulation = Simulation::new(world, 0);
+        simulation.step();
+        let world = simulation.world();
+        let entity = world.get_entity(government);
+
+        assert_eq!(4, entity.government().unwrap().stats().purchases());
+        assert_eq!(5, entity.wares().ware_amount(WareType::Money));
+        assert_eq!(0, entity.wares().ware_amount(WareType::Water));
+        assert_eq!(
+            6,
+            world
+                .get_entity(seller)
+                .wares()
+                .ware_amount(WareType::Water)
+        );
+    }
+}
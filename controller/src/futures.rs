@@ -0,0 +1,375 @@
+use model::{
+    event::Event,
+    market::{
+        fill::Fill,
+        futures::{Forward, FuturesMarket},
+        offer::OfferType,
+    },
+    ware::{Ware, WareAmount},
+    world::{EntityId, World},
+};
+use rand::{seq::SliceRandom, Rng};
+use std::cmp::Reverse;
+
+pub trait FuturesBook {
+    fn match_orders<R, P>(&mut self, rng: &mut R, open: P) -> Vec<Forward>
+    where
+        R: Rng,
+        P: FnMut(&Forward) -> Result<(), OfferType>;
+}
+
+impl FuturesBook for FuturesMarket {
+    /// Matches the highest buy orders with the lowest sell orders for each ware type, currency and delivery tick,
+    /// and returns the contracts that were opened with their margins set.
+    /// Orders with equal prices are served in random order, and contracts are made at the price of the sell order.
+    /// Orders are not matched with orders of the same entity.
+    /// Each contract is opened right away. If opening fails, the failing side loses all its orders,
+    /// and no volume of the counterparty's order is consumed.
+    /// Filled orders are reduced, and removed once they are filled completely.
+    fn match_orders<R, P>(&mut self, rng: &mut R, mut open: P) -> Vec<Forward>
+    where
+        R: Rng,
+        P: FnMut(&Forward) -> Result<(), OfferType>,
+    {
+        let orders = self.orders().to_vec();
+        let mut contract_keys: Vec<_> = orders.iter().map(|order| order.contract()).collect();
+        contract_keys.sort();
+        contract_keys.dedup();
+
+        let mut remaining: Vec<WareAmount> = orders.iter().map(|order| order.amount()).collect();
+        let mut excluded = Vec::new();
+        let mut contracts = Vec::new();
+        for key in contract_keys {
+            let of_type = |offer_type| -> Vec<_> {
+                (0..orders.len())
+                    .filter(|&i| {
+                        orders[i].contract() == key && orders[i].offer_type() == offer_type
+                    })
+                    .collect()
+            };
+            // Shuffle first, such that the stable sort orders offers with equal prices randomly.
+            let mut buys = of_type(OfferType::Buy);
+            buys.shuffle(rng);
+            buys.sort_by_key(|&i| Reverse(orders[i].price_per_ware().amount()));
+            let mut sells = of_type(OfferType::Sell);
+            sells.shuffle(rng);
+            sells.sort_by_key(|&i| orders[i].price_per_ware().amount());
+
+            for buy in buys {
+                // Sell orders of the buyer itself are skipped, but stay available to later buy orders.
+                for &sell in &sells {
+                    if remaining[buy] == 0
+                        || excluded.contains(&orders[buy].entity_id())
+                        || orders[buy].price_per_ware().amount()
+                            < orders[sell].price_per_ware().amount()
+                    {
+                        break;
+                    }
+                    if remaining[sell] == 0
+                        || orders[buy].entity_id() == orders[sell].entity_id()
+                        || excluded.contains(&orders[sell].entity_id())
+                    {
+                        continue;
+                    }
+
+                    let amount = remaining[buy].min(remaining[sell]);
+                    let mut contract = Forward::new(
+                        Ware::new(key.0, amount),
+                        orders[sell].price_per_ware().clone(),
+                        key.2,
+                        orders[buy].entity_id(),
+                        orders[sell].entity_id(),
+                    );
+                    let margin = self.margin_for(contract.total_price().amount());
+                    contract.set_margins(margin, margin);
+                    match open(&contract) {
+                        Ok(()) => {
+                            remaining[buy] -= amount;
+                            remaining[sell] -= amount;
+                            contracts.push(contract);
+                        }
+                        Err(OfferType::Buy) => excluded.push(orders[buy].entity_id()),
+                        Err(OfferType::Sell) => excluded.push(orders[sell].entity_id()),
+                    }
+                }
+            }
+        }
+
+        for (order, remaining) in self.orders_mut().iter_mut().zip(remaining) {
+            let filled = order.amount() - remaining;
+            order.fill(filled);
+        }
+        for entity_id in excluded {
+            self.remove_orders_of(entity_id);
+        }
+        contracts
+    }
+}
+
+pub trait Futures {
+    fn clear_futures<R: Rng>(&mut self, rng: &mut R) -> Vec<Forward>;
+    fn settle_forwards(&mut self) -> Vec<Fill>;
+}
+
+impl Futures for World {
+    /// Drops the futures orders whose delivery tick has come, and matches the rest.
+    /// Both parties of a match post their margin from their wares, and the contract is opened.
+    /// A party that cannot post its margin loses all its orders, and the match is dropped
+    /// without consuming the order of the counterparty.
+    /// Returns the contracts that were opened.
+    fn clear_futures<R: Rng>(&mut self, rng: &mut R) -> Vec<Forward> {
+        let tick = self.tick();
+        self.futures_mut().remove_stale_orders(tick);
+
+        // The futures market is taken out of the world while the parties post their margins.
+        let mut futures = std::mem::take(self.futures_mut());
+        let opened = futures.match_orders(rng, |contract| {
+            let buyer_margin = contract.buyer_margin();
+            post_margin(self, contract.buyer(), &buyer_margin).map_err(|_| OfferType::Buy)?;
+            post_margin(self, contract.seller(), &contract.seller_margin()).map_err(|_| {
+                self.get_entity_mut(contract.buyer()).add_ware(buyer_margin);
+                OfferType::Sell
+            })
+        });
+        for contract in &opened {
+            futures.add_contract(contract.clone());
+        }
+        *self.futures_mut() = futures;
+        self.futures_mut().remove_stale_orders(tick);
+        opened
+    }
+
+    /// Settles the forward contracts due for delivery at the current tick, and returns their deliveries as fills.
+    /// Both parties get their margin back, then the seller delivers the ware and the buyer pays the contract price,
+    /// drawing on its credit line if needed.
+    /// A seller that lacks the ware, or else a buyer that cannot pay or store it, defaults:
+    /// the contract is cancelled, and its margin goes to the counterparty.
+    fn settle_forwards(&mut self) -> Vec<Fill> {
+        let tick = self.tick();
+        let mut fills = Vec::new();
+        for contract in self.futures_mut().take_due_contracts(tick) {
+            let (buyer, seller) = (contract.buyer(), contract.seller());
+            for (entity_id, margin) in &[
+                (buyer, contract.buyer_margin()),
+                (seller, contract.seller_margin()),
+            ] {
+                if let Some(entity) = self.try_get_entity_mut(*entity_id) {
+                    entity.add_ware(margin.clone());
+                }
+            }
+
+            let fill = Fill::new(
+                contract.ware().clone(),
+                contract.price_per_ware().clone(),
+                buyer,
+                seller,
+            );
+            let has_ware = self.try_get_entity(seller).is_some_and(|entity| {
+                entity.wares().ware_amount(contract.ware().ware_type()) >= contract.ware().amount()
+            });
+            let (defaulter, counterparty, forfeited) = if !has_ware {
+                (seller, buyer, contract.seller_margin())
+            } else if self.settle(&fill).is_ok() {
+                fills.push(fill);
+                continue;
+            } else {
+                (buyer, seller, contract.buyer_margin())
+            };
+
+            if let Some(entity) = self.try_get_entity_mut(defaulter) {
+                entity.remove_ware(forfeited.clone()).unwrap();
+            }
+            if let Some(entity) = self.try_get_entity_mut(counterparty) {
+                entity.add_ware(forfeited.clone());
+            }
+            self.log_event(Event::ForwardDefault {
+                entity_id: defaulter,
+                counterparty,
+                forfeited,
+            });
+        }
+        fills
+    }
+}
+
+/// Removes the margin from the wares of the entity. Fails if the entity does not exist or lacks the margin.
+fn post_margin(world: &mut World, entity_id: EntityId, margin: &Ware) -> Result<(), ()> {
+    world
+        .try_get_entity_mut(entity_id)
+        .ok_or(())?
+        .remove_ware(margin.clone())
+        .map(|_| ())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::futures::Futures;
+    use model::{
+        event::Event,
+        market::{futures::FuturesMarket, offer::OfferType},
+        ware::{Ware, WareType},
+        world::World,
+    };
+    use rand::SeedableRng;
+    use rand_pcg::Pcg64Mcg;
+
+    #[test]
+    fn test_forward_delivery() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        let mut world = World::new();
+        *world.futures_mut() = FuturesMarket::new(0.1);
+        let buyer = world.create_entity("Buyer", &[]);
+        let seller = world.create_entity("Seller", &[]);
+        world.get_entity_mut(buyer).add_ware(Ware::money(100));
+        world.get_entity_mut(seller).add_ware(Ware::money(10));
+        world
+            .get_entity_mut(seller)
+            .add_ware(Ware::new(WareType::Food, 10));
+        let food = Ware::new(WareType::Food, 5);
+        world
+            .create_futures_order(buyer, food.clone(), OfferType::Buy, Ware::money(6), 2)
+            .unwrap();
+        world
+            .create_futures_order(seller, food.clone(), OfferType::Sell, Ware::money(5), 2)
+            .unwrap();
+        world
+            .create_futures_order(seller, food.clone(), OfferType::Sell, Ware::money(8), 2)
+            .unwrap();
+        assert_eq!(
+            Err(()),
+            world.create_futures_order(seller, food, OfferType::Sell, Ware::money(8), 0)
+        );
+
+        let opened = world.clear_futures(&mut rng);
+        assert_eq!(1, opened.len());
+        assert_eq!(Ware::money(3), opened[0].buyer_margin());
+        assert_eq!(1, world.futures().orders().len());
+        assert_eq!(
+            Some(5),
+            world
+                .futures()
+                .last_price(WareType::Food, WareType::Money, 2)
+        );
+        let money = |world: &World, entity_id| {
+            world
+                .get_entity(entity_id)
+                .wares()
+                .ware_amount(WareType::Money)
+        };
+        assert_eq!(97, money(&world, buyer));
+        assert_eq!(7, money(&world, seller));
+
+        world.advance_tick();
+        assert!(world.settle_forwards().is_empty());
+        world.advance_tick();
+        let fills = world.settle_forwards();
+        assert_eq!(1, fills.len());
+        assert_eq!(75, money(&world, buyer));
+        assert_eq!(35, money(&world, seller));
+        assert_eq!(
+            5,
+            world.get_entity(buyer).wares().ware_amount(WareType::Food)
+        );
+        assert!(world.futures().contracts().is_empty());
+        assert!(world.events().is_empty());
+    }
+
+    #[test]
+    fn test_forward_default() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        let mut world = World::new();
+        *world.futures_mut() = FuturesMarket::new(0.1);
+        let buyer = world.create_entity("Buyer", &[]);
+        let seller = world.create_entity("Seller", &[]);
+        let broke = world.create_entity("Broke", &[]);
+        world.get_entity_mut(buyer).add_ware(Ware::money(100));
+        world.get_entity_mut(seller).add_ware(Ware::money(10));
+        let food = Ware::new(WareType::Food, 5);
+        world
+            .create_futures_order(buyer, food.clone(), OfferType::Buy, Ware::money(5), 1)
+            .unwrap();
+        world
+            .create_futures_order(seller, food.clone(), OfferType::Sell, Ware::money(5), 1)
+            .unwrap();
+        world
+            .create_futures_order(broke, food.clone(), OfferType::Buy, Ware::money(5), 3)
+            .unwrap();
+        world
+            .create_futures_order(broke, food.clone(), OfferType::Buy, Ware::money(5), 4)
+            .unwrap();
+        world
+            .create_futures_order(seller, food, OfferType::Sell, Ware::money(5), 3)
+            .unwrap();
+
+        // The broke entity cannot post margin, so it loses its orders.
+        assert_eq!(1, world.clear_futures(&mut rng).len());
+        assert!(world
+            .futures()
+            .orders()
+            .iter()
+            .all(|order| order.entity_id() != broke));
+        // The sell order that was matched with the broke entity is not consumed.
+        assert!(world
+            .futures()
+            .orders()
+            .iter()
+            .any(|order| order.entity_id() == seller
+                && order.delivery() == 3
+                && order.amount() == 5));
+
+        world.advance_tick();
+        assert!(world.settle_forwards().is_empty());
+        assert_eq!(
+            103,
+            world.get_entity(buyer).wares().ware_amount(WareType::Money)
+        );
+        assert_eq!(
+            7,
+            world
+                .get_entity(seller)
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+        assert_eq!(
+            &(
+                1,
+                Event::ForwardDefault {
+                    entity_id: seller,
+                    counterparty: buyer,
+                    forfeited: Ware::money(3),
+                }
+            ),
+            world.events().first().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_no_self_match() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        let mut world = World::new();
+        *world.futures_mut() = FuturesMarket::new(0.1);
+        let trader = world.create_entity("Trader", &[]);
+        let seller = world.create_entity("Seller", &[]);
+        world.get_entity_mut(trader).add_ware(Ware::money(100));
+        world.get_entity_mut(seller).add_ware(Ware::money(10));
+        let food = Ware::new(WareType::Food, 5);
+        world
+            .create_futures_order(trader, food.clone(), OfferType::Buy, Ware::money(6), 1)
+            .unwrap();
+        world
+            .create_futures_order(trader, food.clone(), OfferType::Sell, Ware::money(4), 1)
+            .unwrap();
+        world
+            .create_futures_order(seller, food, OfferType::Sell, Ware::money(6), 1)
+            .unwrap();
+
+        // The cheaper sell order of the trader is skipped instead of being matched with its own buy order.
+        let opened = world.clear_futures(&mut rng);
+        assert_eq!(1, opened.len());
+        assert_eq!(seller, opened[0].seller());
+        assert_eq!(5, opened[0].ware().amount());
+        assert_eq!(1, world.futures().orders().len());
+        assert_eq!(trader, world.futures().orders()[0].entity_id());
+        assert_eq!(5, world.futures().orders()[0].amount());
+    }
+}
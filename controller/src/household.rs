@@ -0,0 +1,137 @@
+use model::{
+    entity::Entity,
+    ware::{Ware, WareAmount},
+};
+
+/// The price levels, relative to the buy prices, at which households place buy offers.
+const PRICE_LEVELS: [f64; 5] = [1.5, 1.25, 1.0, 0.75, 0.5];
+
+pub trait Household {
+    fn household_buy_offers(&self, budget: WareAmount) -> Vec<(Ware, Ware)>;
+}
+
+impl Household for Entity {
+    /// Returns buy offers as pairs of the ware and the price per ware, spending at most the given budget.
+    /// Offers are placed at several price levels around the buy prices, from the highest level down.
+    /// At each level, the household offers to buy what its utility function demands at that price,
+    /// in addition to what it holds and what it offered at higher levels.
+    /// If filling all offers would exceed the budget, all offers are scaled down.
+    fn household_buy_offers(&self, budget: WareAmount) -> Vec<(Ware, Ware)> {
+        let utility = match self.utility() {
+            Some(utility) => utility,
+            None => return Vec::new(),
+        };
+
+        let currency = self.currency();
+        let reference_price =
+            |ware_type| f64::from(self.buy_prices().single_price_in(ware_type, currency));
+        let ware_types = utility.ware_types();
+        let mut offered: Vec<_> = ware_types
+            .iter()
+            .map(|&ware_type| self.wares().ware_amount(ware_type))
+            .collect();
+        let mut offers = Vec::new();
+
+        for level in PRICE_LEVELS.iter() {
+            for (&ware_type, offered) in ware_types.iter().zip(offered.iter_mut()) {
+                let price = ((reference_price(ware_type) * level).round() as WareAmount).max(1);
+                let demand = utility.demand(
+                    ware_type,
+                    f64::from(price),
+                    f64::from(budget),
+                    reference_price,
+                );
+
+                if demand > *offered {
+                    offers.push((
+                        Ware::new(ware_type, demand - *offered),
+                        Ware::new(currency, price),
+                    ));
+                    *offered = demand;
+                }
+            }
+        }
+
+        let total_price: u64 = offers
+            .iter()
+            .map(|(ware, price_per_ware)| {
+                u64::from(ware.amount()) * u64::from(price_per_ware.amount())
+            })
+            .sum();
+        if total_price > u64::from(budget) {
+            let scale = f64::from(budget) / total_price as f64;
+            for (ware, _) in offers.iter_mut() {
+                *ware.amount_mut() = (f64::from(ware.amount()) * scale).floor() as WareAmount;
+            }
+            offers.retain(|(ware, _)| ware.amount() > 0);
+        }
+
+        offers
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{household::Household, simulation::Simulation};
+    use model::{
+        entity::{utility::Utility, Entity},
+        ware::{Ware, WareType},
+        world::World,
+    };
+
+    #[test]
+    fn test_household_buy_offers() {
+        let mut household = Entity::new("Household".to_owned(), Vec::new());
+        household.set_utility(Utility::CobbDouglas(vec![
+            (WareType::Food, 1.0),
+            (WareType::Water, 1.0),
+        ]));
+
+        let offers = household.household_buy_offers(100);
+        let food_offers: Vec<_> = offers
+            .iter()
+            .filter(|(ware, _)| ware.ware_type() == WareType::Food)
+            .collect();
+
+        assert!(food_offers.len() > 1);
+        assert!(food_offers
+            .windows(2)
+            .all(|offers| offers[0].1.amount() > offers[1].1.amount()));
+        assert!(
+            food_offers
+                .iter()
+                .map(|(ware, _)| ware.amount())
+                .sum::<u32>()
+                <= 50 / 3
+        );
+        assert!(
+            offers
+                .iter()
+                .map(|(ware, price)| u64::from(ware.amount()) * u64::from(price.amount()))
+                .sum::<u64>()
+                <= 100
+        );
+    }
+
+    #[test]
+    fn test_households_buy_and_consume() {
+        let mut world = World::new();
+        let household = world.create_entity("Household", &[]);
+        let farmer = world.create_entity("Farmer", &[]);
+        world
+            .get_entity_mut(household)
+            .set_utility(Utility::CobbDouglas(vec![(WareType::Food, 1.0)]));
+        world.get_entity_mut(household).add_ware(Ware::money(20));
+        world
+            .get_entity_mut(farmer)
+            .add_ware(Ware::new(WareType::Food, 10));
+
+        let mut simulation = Simulation::new(world, 0);
+        simulation.step();
+
+        let household = simulation.world().get_entity(household);
+        assert_eq!(0, household.wares().ware_amount(WareType::Food));
+        assert!(household.wares().ware_amount(WareType::Money) < 20);
+        assert!(household.utility_level() > 0.0);
+    }
+}
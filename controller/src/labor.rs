@@ -0,0 +1,142 @@
+use model::{
+    market::{fill::Fill, stats::LaborStats},
+    ware::{Ware, WareAmount, WareType},
+    world::World,
+};
+use std::collections::HashSet;
+
+pub trait LaborMarket {
+    fn supply_labor(&mut self);
+    fn expire_labor(&mut self, fills: &[Fill]);
+}
+
+impl LaborMarket for World {
+    /// Gives every worker its hours of labor for this tick.
+    fn supply_labor(&mut self) {
+        for entity in self.entities_mut().filter(|entity| entity.is_worker()) {
+            let hours = entity.labor();
+            entity.add_ware(Ware::new(WareType::Labor, hours));
+        }
+    }
+
+    /// Removes all perishable wares that were not used in this tick,
+    /// and records the labor market statistics from the given fills of this tick.
+    fn expire_labor(&mut self, fills: &[Fill]) {
+        let labor_fills: Vec<_> = fills
+            .iter()
+            .filter(|fill| fill.ware().ware_type() == WareType::Labor)
+            .collect();
+        let employed: HashSet<_> = labor_fills.iter().map(|fill| fill.seller()).collect();
+
+        let mut workers = 0;
+        let mut employed_workers = 0;
+        let mut hours_supplied = 0;
+        for (entity_id, entity) in self.iter_entities().filter(|(_, e)| e.is_worker()) {
+            workers += 1;
+            hours_supplied += entity.labor();
+            if employed.contains(&entity_id) {
+                employed_workers += 1;
+            }
+        }
+
+        let hours_employed: WareAmount = labor_fills.iter().map(|fill| fill.ware().amount()).sum();
+        let wages: f64 = labor_fills
+            .iter()
+            .map(|fill| {
+                let total_price = fill.total_price();
+                f64::from(total_price.amount())
+                    * self
+                        .market()
+                        .value_in(total_price.ware_type(), WareType::Money)
+            })
+            .sum();
+        let wage = if hours_employed > 0 {
+            Some(wages / f64::from(hours_employed))
+        } else {
+            None
+        };
+        self.set_labor_stats(LaborStats::new(
+            workers,
+            employed_workers,
+            hours_supplied,
+            hours_employed.min(hours_supplied),
+            wage,
+        ));
+
+        for entity in self.entities_mut() {
+            let perished: Vec<_> = entity
+                .wares()
+                .iter()
+                .filter(|ware| ware.ware_type().is_perishable())
+                .collect();
+            for ware in perished {
+                entity.remove_ware(ware).unwrap();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::simulation::Simulation;
+    use model::{
+        entity::{recipe::Recipe, utility::Utility},
+        templates::{FOOD_CREATOR_RECIPE, HUMAN_LABOR},
+        ware::{Ware, WareType},
+        world::World,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn test_labor_market() {
+        let mut world = World::new();
+        let workers: Vec<_> = (0..3)
+            .map(|i| {
+                let worker = world.create_entity(&format!("Worker {}", i), &[]);
+                let entity = world.get_entity_mut(worker);
+                entity.set_utility(Utility::CobbDouglas(vec![(WareType::Food, 1.0)]));
+                entity.set_labor(HUMAN_LABOR);
+                worker
+            })
+            .collect();
+        let farmer =
+            world.create_entity("Farmer", &[Recipe::from_str(FOOD_CREATOR_RECIPE).unwrap()]);
+        world.get_entity_mut(farmer).add_ware(Ware::money(100));
+
+        let mut simulation = Simulation::new(world, 0);
+        simulation.step();
+        let world = simulation.world();
+        let labor_stats = world.labor_stats();
+
+        assert_eq!(3, labor_stats.workers());
+        assert_eq!(1, labor_stats.employed_workers());
+        assert_eq!(3 * HUMAN_LABOR, labor_stats.hours_supplied());
+        assert_eq!(2, labor_stats.hours_employed());
+        assert!((labor_stats.unemployment_rate() - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(Some(2.0), labor_stats.wage());
+        assert_eq!(
+            2,
+            world
+                .market()
+                .trade_stats(WareType::Labor, WareType::Money)
+                .volume()
+        );
+        assert_eq!(
+            1,
+            world.get_entity(farmer).wares().ware_amount(WareType::Food)
+        );
+        assert_eq!(
+            4,
+            workers
+                .iter()
+                .map(|&worker| world
+                    .get_entity(worker)
+                    .wares()
+                    .ware_amount(WareType::Money))
+                .sum::<u32>()
+        );
+        assert!(world
+            .entities()
+            .all(|entity| entity.wares().ware_amount(WareType::Labor) == 0));
+    }
+}
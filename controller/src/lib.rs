@@ -1,4 +1,25 @@
 extern crate rand;
 extern crate rand_pcg;
+extern crate rhai;
 
+pub mod agent;
+pub mod auction;
+pub mod banking;
+pub mod bankruptcy;
+pub mod central_bank;
+pub mod contract;
+pub mod decay;
+pub mod fiscal;
+pub mod futures;
+pub mod household;
+pub mod labor;
+pub mod market_maker;
+pub mod merchant;
+pub mod population;
+pub mod production;
+pub mod resource;
+pub mod script;
+pub mod shock;
+pub mod simulation;
 pub mod trading;
+pub mod transport;
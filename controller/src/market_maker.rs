@@ -0,0 +1,162 @@
+use model::{
+    entity::{market_maker::MarketMaker, Entity},
+    market::{fill::Fill, offer::OfferType},
+    world::World,
+};
+
+pub trait LiquidityProvision {
+    fn record_market_making(&mut self, fills: &[Fill]);
+}
+
+impl LiquidityProvision for World {
+    /// Records the given fills in which market makers bought or sold a ware they quote in their stats.
+    fn record_market_making(&mut self, fills: &[Fill]) {
+        for fill in fills {
+            let sides = [
+                (fill.buyer(), OfferType::Buy),
+                (fill.seller(), OfferType::Sell),
+            ];
+            for &(entity_id, offer_type) in &sides {
+                let quote = self
+                    .try_get_entity_mut(entity_id)
+                    .and_then(Entity::agent_as_mut::<MarketMaker>)
+                    .and_then(|market_maker| market_maker.quote_mut(fill.ware().ware_type()));
+                if let Some(quote) = quote {
+                    quote.stats_mut().record_trade(
+                        offer_type,
+                        fill.ware().amount(),
+                        fill.price_per_ware().amount(),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::simulation::Simulation;
+    use model::{
+        entity::{
+            agent::Agent,
+            market_maker::{MarketMaker, Quote},
+            recipe::Recipe,
+            utility::Utility,
+            Entity,
+        },
+        market::{offer::OfferType, Market},
+        ware::{Ware, WareType},
+        world::World,
+    };
+    use rand::SeedableRng;
+    use rand_pcg::Pcg64Mcg;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_quotes_follow_inventory() {
+        let mut entity = Entity::new("Market maker".to_owned(), Vec::new());
+        let mut market_maker =
+            MarketMaker::new(vec![Quote::new(WareType::Food, 10, 0.2, 1.0, 10, 5, 3)]);
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        let mut offers = |market_maker: &mut MarketMaker, entity: &Entity| {
+            market_maker.decide_offers(entity, &Market::new(), &mut rng)
+        };
+        entity.add_ware(Ware::money(100));
+        entity.add_ware(Ware::new(WareType::Food, 10));
+        assert_eq!(
+            vec![
+                (Ware::new(WareType::Food, 3), OfferType::Buy, Ware::money(9)),
+                (
+                    Ware::new(WareType::Food, 3),
+                    OfferType::Sell,
+                    Ware::money(11)
+                ),
+            ],
+            offers(&mut market_maker, &entity)
+        );
+
+        // At the top of its band it only sells, with a wider spread.
+        entity.add_ware(Ware::new(WareType::Food, 5));
+        assert_eq!(
+            vec![(
+                Ware::new(WareType::Food, 3),
+                OfferType::Sell,
+                Ware::money(12)
+            )],
+            offers(&mut market_maker, &entity)
+        );
+
+        market_maker.set_enabled(WareType::Food, false).unwrap();
+        assert!(offers(&mut market_maker, &entity).is_empty());
+    }
+
+    #[test]
+    fn test_market_maker_provides_liquidity() {
+        let mut world = World::new();
+        let farmer = world.create_entity("Farmer", &[Recipe::from_str("() -> (2x Food)").unwrap()]);
+        let market_maker = world.create_entity("Market maker", &[]);
+        world
+            .get_entity_mut(farmer)
+            .sell_prices_mut()
+            .set_single_price(WareType::Food, 9);
+        let entity = world.get_entity_mut(market_maker);
+        entity.set_agent(Some(Box::new(MarketMaker::new(vec![Quote::new(
+            WareType::Food,
+            10,
+            0.2,
+            0.0,
+            4,
+            4,
+            2,
+        )]))));
+        entity.add_ware(Ware::money(100));
+
+        // Without buyers, the market maker buys the food of the farmer up to the top of its band.
+        let mut simulation = Simulation::new(world, 0);
+        for _ in 0..6 {
+            simulation.step();
+        }
+        let stats = |world: &World| {
+            world
+                .get_entity(market_maker)
+                .agent_as::<MarketMaker>()
+                .unwrap()
+                .quote(WareType::Food)
+                .unwrap()
+                .stats()
+                .clone()
+        };
+        assert_eq!(8, stats(simulation.world()).bought());
+        assert_eq!(-72, stats(simulation.world()).cash_flow());
+
+        // Once the farmer is gone, a buyer can still buy from the market maker.
+        simulation.world_mut().remove_entity(farmer);
+        let eater = simulation.world_mut().create_entity("Eater", &[]);
+        let entity = simulation.world_mut().get_entity_mut(eater);
+        entity.set_utility(Utility::CobbDouglas(vec![(WareType::Food, 1.0)]));
+        entity.buy_prices_mut().set_single_price(WareType::Food, 11);
+        entity.add_ware(Ware::money(100));
+        let fills = simulation.step();
+
+        assert!(!fills.is_empty());
+        assert!(
+            fills
+                .iter()
+                .all(|fill| fill.seller() == market_maker
+                    && fill.price_per_ware() == &Ware::money(11))
+        );
+        let stats = stats(simulation.world());
+        assert!(stats.sold() > 0);
+        assert_eq!(
+            100 + stats.cash_flow(),
+            i64::from(
+                simulation
+                    .world()
+                    .get_entity(market_maker)
+                    .wares()
+                    .ware_amount(WareType::Money)
+            )
+        );
+        assert_eq!(8 + i64::from(stats.sold()), stats.pnl(10));
+    }
+}
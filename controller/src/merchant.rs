@@ -0,0 +1,294 @@
+use crate::transport::Transport;
+use model::{
+    entity::merchant::{Merchant, Voyage},
+    market::offer::OfferType,
+    ware::WareAmount,
+    world::{EntityId, World},
+};
+
+pub trait Arbitrage {
+    fn plan_voyages(&mut self);
+}
+
+impl Arbitrage for World {
+    /// Advances the voyages of all merchants that are not travelling, using the offers left open in the last tick.
+    /// A merchant that bought its cargo sets out for the destination, or sells where it is if it cannot pay the transport.
+    /// In the destination it lowers its sell price to the best open bid, and once its cargo is sold the voyage
+    /// is complete and its profit recorded. A merchant without a voyage plans the most profitable one.
+    fn plan_voyages(&mut self) {
+        let merchant_ids: Vec<_> = self
+            .iter_entities()
+            .filter(|(_, entity)| {
+                entity.agent_as::<Merchant>().is_some()
+                    && !entity.is_travelling()
+                    && !entity.is_bankrupt()
+            })
+            .map(|(entity_id, _)| entity_id)
+            .collect();
+
+        for entity_id in merchant_ids {
+            let entity = self.get_entity(entity_id);
+            let region = entity.region();
+            let currency = entity.currency();
+            let cash = entity.wares().ware_amount(currency);
+            if let Some(voyage) = entity.agent_as::<Merchant>().unwrap().voyage().cloned() {
+                let held = entity.wares().ware_amount(voyage.ware_type());
+                if region == voyage.destination() {
+                    if held > 0 {
+                        let best_bid = self
+                            .market_in(region)
+                            .depth(voyage.ware_type(), currency, OfferType::Buy)
+                            .first()
+                            .map(|&(price, _)| price);
+                        if let Some(best_bid) = best_bid {
+                            let voyage = Voyage::new(
+                                voyage.ware_type(),
+                                voyage.destination(),
+                                voyage.amount(),
+                                voyage.buy_price(),
+                                best_bid,
+                                voyage.capital(),
+                            );
+                            merchant_mut(self, entity_id).set_voyage(Some(voyage));
+                        }
+                        continue;
+                    }
+                    merchant_mut(self, entity_id).complete_voyage(cash);
+                } else if held > 0 {
+                    if self.travel(entity_id, voyage.destination()).is_err() {
+                        merchant_mut(self, entity_id)
+                            .voyage_mut()
+                            .unwrap()
+                            .set_destination(region);
+                    }
+                    continue;
+                } else {
+                    // Nothing was bought, so the voyage is given up.
+                    merchant_mut(self, entity_id).set_voyage(None);
+                }
+            }
+
+            if let Some(voyage) = best_voyage(self, entity_id) {
+                merchant_mut(self, entity_id).set_voyage(Some(voyage));
+            }
+        }
+    }
+}
+
+fn merchant_mut(world: &mut World, entity_id: EntityId) -> &mut Merchant {
+    world
+        .get_entity_mut(entity_id)
+        .agent_as_mut::<Merchant>()
+        .unwrap()
+}
+
+/// The voyage from the region of the merchant with the highest expected profit,
+/// matching the open sell offers there with the open buy offers in the other regions it can reach.
+fn best_voyage(world: &World, entity_id: EntityId) -> Option<Voyage> {
+    let entity = world.get_entity(entity_id);
+    let merchant = entity.agent_as::<Merchant>()?;
+    let currency = entity.currency();
+    let region = entity.region();
+    let cash = entity.wares().ware_amount(currency);
+    let budget = cash.min(merchant.risk_limit());
+
+    let mut best: Option<(f64, Voyage)> = None;
+    for &ware_type in merchant.ware_types() {
+        let asks = world
+            .market_in(region)
+            .depth(ware_type, currency, OfferType::Sell);
+        let capacity = merchant
+            .inventory_cap()
+            .saturating_sub(entity.wares().ware_amount(ware_type))
+            .min(entity.wares().room_for(ware_type));
+        for destination in (0..world.regions().len()).filter(|&destination| destination != region) {
+            let route = match world.route(region, destination) {
+                Some(route) => route,
+                None => continue,
+            };
+            let bids = world
+                .market_in(destination)
+                .depth(ware_type, currency, OfferType::Buy);
+            let matched = match_depth(
+                &asks,
+                &bids,
+                route.cost(),
+                merchant.min_margin(),
+                capacity,
+                budget,
+            );
+            if let Some((profit, amount, buy_price, sell_price)) = matched {
+                if best
+                    .as_ref()
+                    .is_none_or(|(best_profit, _)| profit > *best_profit)
+                {
+                    let voyage =
+                        Voyage::new(ware_type, destination, amount, buy_price, sell_price, cash);
+                    best = Some((profit, voyage));
+                }
+            }
+        }
+    }
+    best.map(|(_, voyage)| voyage)
+}
+
+/// Matches the given sell offers of one market with the buy offers of another, best prices first,
+/// as long as the margin per ware after the transport cost is at least the minimum margin times the buy price.
+/// At most `capacity` wares are matched, and only as many as the budget can pay for including transport.
+/// All wares are bought at the highest matched ask and sold at the lowest matched bid,
+/// so matching deeper levels can lower the profit. The match stops at the depth with the highest profit.
+/// Returns the expected profit, the amount, the highest buy price and the lowest sell price,
+/// or None if nothing can be traded.
+fn match_depth(
+    asks: &[(WareAmount, WareAmount)],
+    bids: &[(WareAmount, WareAmount)],
+    cost: WareAmount,
+    min_margin: f64,
+    capacity: WareAmount,
+    budget: WareAmount,
+) -> Option<(f64, WareAmount, WareAmount, WareAmount)> {
+    let mut asks = asks.to_vec();
+    let mut bids = bids.to_vec();
+    let (mut i, mut j) = (0, 0);
+    let mut amount = 0;
+    let mut best: Option<(f64, WareAmount, WareAmount, WareAmount)> = None;
+    while let (Some(&(ask, ask_amount)), Some(&(bid, bid_amount))) = (asks.get(i), bids.get(j)) {
+        let margin = f64::from(bid) - f64::from(ask) - f64::from(cost);
+        if margin <= 0.0 || margin < min_margin * f64::from(ask) {
+            break;
+        }
+
+        let affordable = budget / (ask + cost).max(1);
+        let matched = ask_amount
+            .min(bid_amount)
+            .min(capacity - amount)
+            .min(affordable.saturating_sub(amount));
+        if matched == 0 {
+            break;
+        }
+        amount += matched;
+        let profit = f64::from(amount) * margin;
+        if best.is_none_or(|(best_profit, ..)| profit > best_profit) {
+            best = Some((profit, amount, ask, bid));
+        }
+        asks[i].1 -= matched;
+        bids[j].1 -= matched;
+        if asks[i].1 == 0 {
+            i += 1;
+        }
+        if bids[j].1 == 0 {
+            j += 1;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{merchant::match_depth, simulation::Simulation};
+    use model::{
+        entity::{merchant::Merchant, utility::Utility},
+        region::Route,
+        ware::{Ware, WareType},
+        world::World,
+    };
+
+    #[test]
+    fn test_match_depth() {
+        let asks = [(2, 3), (4, 10)];
+        let bids = [(10, 2), (6, 10)];
+
+        // Selling more at the second bid would lower the price of every ware sold.
+        assert_eq!(
+            Some((14.0, 2, 2, 10)),
+            match_depth(&asks, &bids, 1, 0.0, 5, 100)
+        );
+        assert_eq!(
+            Some((14.0, 2, 2, 10)),
+            match_depth(&asks, &bids, 1, 0.5, 5, 100)
+        );
+        assert_eq!(
+            Some((14.0, 2, 2, 10)),
+            match_depth(&asks, &bids, 1, 0.5, 2, 100)
+        );
+        assert_eq!(
+            Some((7.0, 1, 2, 10)),
+            match_depth(&asks, &bids, 1, 0.5, 5, 5)
+        );
+        assert_eq!(None, match_depth(&asks, &bids, 1, 4.0, 5, 100));
+    }
+
+    #[test]
+    fn test_match_deep_levels() {
+        let asks = [(2, 3), (3, 10)];
+        let bids = [(8, 4), (7, 10)];
+
+        // All ten wares are bought at 3 and sold at 7, rather than each level at its own prices.
+        assert_eq!(
+            Some((30.0, 10, 3, 7)),
+            match_depth(&asks, &bids, 1, 0.0, 10, 100)
+        );
+        assert_eq!(
+            Some((16.0, 4, 3, 8)),
+            match_depth(&asks, &bids, 1, 0.0, 4, 100)
+        );
+    }
+
+    #[test]
+    fn test_merchant_voyage() {
+        let mut world = World::new();
+        let town = world.create_region("Town");
+        world.add_route(Route::new(0, town, 1, 1)).unwrap();
+        world.add_route(Route::new(town, 0, 1, 1)).unwrap();
+        let farmer = world.create_entity("Farmer", &[]);
+        let buyer = world.create_entity("Buyer", &[]);
+        let merchant = world.create_entity("Merchant", &[]);
+        let entity = world.get_entity_mut(farmer);
+        entity.add_ware(Ware::new(WareType::Food, 20));
+        entity.sell_prices_mut().set_single_price(WareType::Food, 2);
+        let entity = world.get_entity_mut(buyer);
+        entity.set_utility(Utility::CobbDouglas(vec![(WareType::Food, 1.0)]));
+        entity.buy_prices_mut().set_single_price(WareType::Food, 10);
+        entity.add_ware(Ware::money(100));
+        entity.set_region(town);
+        let entity = world.get_entity_mut(merchant);
+        entity.set_agent(Some(Box::new(Merchant::new(
+            vec![WareType::Food],
+            0.2,
+            10,
+            40,
+        ))));
+        entity.add_ware(Ware::money(50));
+
+        let mut simulation = Simulation::new(world, 0);
+        for _ in 0..10 {
+            simulation.step();
+        }
+        let world = simulation.world();
+        let stats = world
+            .get_entity(merchant)
+            .agent_as::<Merchant>()
+            .unwrap()
+            .stats();
+
+        assert_eq!(1, stats.voyages());
+        assert!(stats.profit() > 0);
+        assert_eq!(Some(stats.profit()), stats.last_profit());
+        assert_eq!(
+            50 + stats.profit() as u32,
+            world
+                .get_entity(merchant)
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+        // The merchant only bought as much as the best bids in the town pay for most profitably.
+        assert_eq!(
+            17,
+            world.get_entity(farmer).wares().ware_amount(WareType::Food)
+        );
+        assert!(world
+            .market_in(town)
+            .last_price(WareType::Food, WareType::Money)
+            .is_some());
+    }
+}
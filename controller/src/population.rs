@@ -0,0 +1,393 @@
+use model::{
+    entity::{health::Health, Entity},
+    market::Market,
+    population::{Birth, Entry, PopulationRule},
+    ware::{Ware, WareAmount, WareType},
+    world::{EntityId, World},
+};
+use rand::Rng;
+
+pub trait Mortality {
+    fn remove_dead_entities(&mut self) -> Vec<EntityId>;
+}
+
+pub trait Population {
+    fn spawn_entities<R: Rng>(&mut self, rng: &mut R) -> Vec<EntityId>;
+    fn remove_unprofitable_entities(&mut self) -> Vec<EntityId>;
+}
+
+impl Mortality for World {
+    /// Removes all entities whose health reached zero, and returns their ids.
+    fn remove_dead_entities(&mut self) -> Vec<EntityId> {
+        let dead: Vec<_> = self
+            .iter_entities()
+            .filter(|(_, entity)| entity.is_dead())
+            .map(|(entity_id, _)| entity_id)
+            .collect();
+
+        for &entity_id in &dead {
+            self.remove_entity(entity_id);
+        }
+        dead
+    }
+}
+
+impl Population for World {
+    /// Creates the entities born or entering the market under the birth and entry rules of this world,
+    /// and returns their ids.
+    fn spawn_entities<R: Rng>(&mut self, rng: &mut R) -> Vec<EntityId> {
+        let mut rules = std::mem::take(self.population_rules_mut());
+        let mut spawned = Vec::new();
+        for rule in rules.iter_mut() {
+            match rule {
+                PopulationRule::Birth(birth) => spawned.extend(give_births(self, birth, rng)),
+                PopulationRule::Entry(entry) => spawned.extend(enter_producer(self, entry, rng)),
+                PopulationRule::Exit(_) => {}
+            }
+        }
+        *self.population_rules_mut() = rules;
+        spawned
+    }
+
+    /// Records the wealth of all producers, and removes those whose wealth fell on average
+    /// over the shortest window of the exit rules of this world. Returns the ids of the removed producers.
+    fn remove_unprofitable_entities(&mut self) -> Vec<EntityId> {
+        let window = match self
+            .population_rules()
+            .iter()
+            .filter_map(|rule| match rule {
+                PopulationRule::Exit(exit) => Some(exit.window() as usize),
+                _ => None,
+            })
+            .min()
+        {
+            Some(window) => window,
+            None => return Vec::new(),
+        };
+
+        let (entities, regions) = self.entities_regions_mut();
+        let mut unprofitable = Vec::new();
+        for (entity_id, entity) in entities.filter(|(_, entity)| entity.is_producer()) {
+            entity.record_wealth(
+                wealth(entity, regions[entity.region()].market()),
+                window + 1,
+            );
+            if entity.wealth_trend(window).is_some_and(|trend| trend < 0.0) {
+                unprofitable.push(entity_id);
+            }
+        }
+
+        for &entity_id in &unprofitable {
+            self.remove_entity(entity_id);
+        }
+        unprofitable
+    }
+}
+
+/// Gives each well-fed household a child with the chance of the birth rule.
+fn give_births<R: Rng>(world: &mut World, birth: &Birth, rng: &mut R) -> Vec<EntityId> {
+    let parents: Vec<_> = world
+        .iter_entities()
+        .filter(|(_, entity)| is_well_fed(entity, birth))
+        .map(|(entity_id, _)| entity_id)
+        .collect();
+
+    let mut children = Vec::new();
+    for parent_id in parents {
+        if !rng.gen_bool(birth.chance()) {
+            continue;
+        }
+
+        let parent = world.get_entity(parent_id);
+        let name = parent.name().to_owned();
+        let recipes = parent.recipes().to_owned();
+        let currency = parent.currency();
+        let utility = parent.utility().cloned();
+        let max_health = parent.health().map(Health::max_health);
+        let endowment = (f64::from(parent.wares().ware_amount(currency)) * birth.endowment())
+            .floor() as WareAmount;
+
+        let child_id = world.create_entity(&name, &recipes);
+        let parent = world.get_entity_mut(parent_id);
+        let endowment = parent.remove_ware(Ware::new(currency, endowment)).unwrap();
+        if parent.heir().is_none() {
+            parent.set_heir(Some(child_id));
+        }
+
+        let child = world.get_entity_mut(child_id);
+        child.set_currency(currency);
+        if let Some(utility) = utility {
+            child.set_utility(utility);
+        }
+        if let Some(max_health) = max_health {
+            child.set_health(Health::new(max_health));
+        }
+        if endowment.amount() > 0 {
+            child.add_ware(endowment);
+        }
+
+        children.push(child_id);
+    }
+    children
+}
+
+fn is_well_fed(entity: &Entity, birth: &Birth) -> bool {
+    entity.is_household()
+        && entity
+            .health()
+            .is_none_or(|health| health.health() == health.max_health())
+        && entity.utility_level() >= birth.min_utility_level()
+}
+
+/// Lets a producer enter the region of the entry rule with its chance,
+/// once the price of its ware in the market of the region was above the cost for long enough.
+/// Entry rules for regions that do not exist never open.
+fn enter_producer<R: Rng>(world: &mut World, entry: &mut Entry, rng: &mut R) -> Option<EntityId> {
+    // Entrants are created with money as their currency.
+    let currency = WareType::Money;
+    let market = world.regions().get(entry.region())?.market();
+    let price = market.last_price(entry.ware_type(), currency);
+    let cost = unit_cost(market, entry, currency);
+    entry.record_margin(match (price, cost) {
+        (Some(price), Some(cost)) => f64::from(price) > cost,
+        _ => false,
+    });
+
+    if !entry.is_open() || !rng.gen_bool(entry.chance()) {
+        return None;
+    }
+
+    entry.reset();
+    let entity_id = world.create_entity(entry.name(), entry.recipes());
+    world.get_entity_mut(entity_id).set_region(entry.region());
+    for ware in entry.endowment() {
+        world.get_entity_mut(entity_id).add_ware(ware.clone());
+    }
+    Some(entity_id)
+}
+
+/// The lowest cost of the inputs of the recipes of the entry rule per expected unit of its ware.
+fn unit_cost(market: &Market, entry: &Entry, currency: WareType) -> Option<f64> {
+    entry
+        .recipes()
+        .iter()
+        .filter_map(|recipe| {
+            let output = recipe.expected_output(entry.ware_type());
+            if output <= 0.0 {
+                return None;
+            }
+
+            let cost: f64 = recipe
+                .inputs()
+                .iter()
+                .map(|input| {
+                    f64::from(input.amount()) * market.value_in(input.ware_type(), currency)
+                })
+                .sum();
+            Some(cost / output)
+        })
+        .fold(None, |min: Option<f64>, cost| {
+            Some(min.map_or(cost, |min| min.min(cost)))
+        })
+}
+
+/// The value of the wares of the given entity in its own currency, including the wares in progress.
+fn wealth(entity: &Entity, market: &Market) -> f64 {
+    market.value_of(entity.wares(), entity.currency())
+        + market.value_of(&entity.wares_in_progress(), entity.currency())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        population::{Mortality, Population},
+        simulation::Simulation,
+    };
+    use model::{
+        entity::{health::Health, recipe::Recipe, utility::Utility},
+        market::offer::OfferType,
+        population::{Birth, Entry, Exit, PopulationRule},
+        templates::{EATING_RECIPE, FOOD_CREATOR_RECIPE, HUMAN_MAX_HEALTH},
+        ware::{Ware, WareType},
+        world::{Estate, World},
+    };
+    use rand::SeedableRng;
+    use rand_pcg::Pcg64Mcg;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_starving_entities_die() {
+        let mut world = World::new();
+        let human = world.create_entity("Human", &[Recipe::from_str(EATING_RECIPE).unwrap()]);
+        let heir = world.create_entity("Heir", &[]);
+        world.get_entity_mut(human).set_health(Health::new(2));
+        world.get_entity_mut(human).set_heir(Some(heir));
+        world
+            .get_entity_mut(human)
+            .add_ware(Ware::new(WareType::Food, 1));
+        world
+            .get_entity_mut(human)
+            .add_ware(Ware::new(WareType::Water, 3));
+
+        let mut simulation = Simulation::new(world, 0);
+        simulation.step();
+        assert_eq!(
+            Some(2),
+            simulation
+                .world()
+                .get_entity(human)
+                .health()
+                .map(Health::health)
+        );
+        simulation.step();
+        simulation.step();
+
+        assert_eq!(1, simulation.world().entities().count());
+        assert_eq!(
+            3,
+            simulation
+                .world()
+                .get_entity(heir)
+                .wares()
+                .ware_amount(WareType::Water)
+        );
+        assert!(simulation.world().market().offers().is_empty());
+    }
+
+    #[test]
+    fn test_remove_dead_entities_keeps_ids() {
+        let mut world = World::new();
+        world.set_estate(Estate::Destroy);
+        let first = world.create_entity("First", &[]);
+        let second = world.create_entity("Second", &[]);
+        let mut health = Health::new(1);
+        health.update(1);
+        world.get_entity_mut(first).set_health(health);
+        world.get_entity_mut(first).add_ware(Ware::money(10));
+        world
+            .create_offer(
+                first,
+                Ware::new(WareType::Food, 1),
+                OfferType::Buy,
+                Ware::money(5),
+            )
+            .unwrap();
+
+        assert_eq!(vec![first], world.remove_dead_entities());
+        assert_eq!("Second", world.get_entity(second).name());
+        assert!(world.try_get_entity(first).is_none());
+        assert_eq!(0, world.market().offers().len());
+        assert_eq!(
+            0,
+            world
+                .entities()
+                .map(|e| e.wares().ware_amount(WareType::Money))
+                .sum::<u32>()
+        );
+    }
+
+    #[test]
+    fn test_well_fed_households_reproduce() {
+        let mut world = World::new();
+        let parent = world.create_entity("Human", &[Recipe::from_str(EATING_RECIPE).unwrap()]);
+        let hungry = world.create_entity("Hungry", &[]);
+        for &entity_id in &[parent, hungry] {
+            let entity = world.get_entity_mut(entity_id);
+            entity.set_utility(Utility::CobbDouglas(vec![(WareType::Food, 1.0)]));
+            entity.set_health(Health::new(HUMAN_MAX_HEALTH));
+            entity.add_ware(Ware::money(10));
+        }
+        world
+            .get_entity_mut(parent)
+            .add_ware(Ware::new(WareType::Food, 5));
+        world.entities_mut().for_each(|entity| entity.consume());
+        world.add_population_rule(PopulationRule::Birth(Birth::new(1.0, 1.0, 0.5)));
+
+        let children = world.spawn_entities(&mut Pcg64Mcg::seed_from_u64(0));
+
+        assert_eq!(1, children.len());
+        let child = world.get_entity(children[0]);
+        assert_eq!("Human", child.name());
+        assert_eq!(1, child.recipes().len());
+        assert!(child.is_household());
+        assert_eq!(
+            Some(HUMAN_MAX_HEALTH),
+            child.health().map(Health::max_health)
+        );
+        assert_eq!(5, child.wares().ware_amount(WareType::Money));
+        assert_eq!(
+            5,
+            world
+                .get_entity(parent)
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+        assert_eq!(Some(children[0]), world.get_entity(parent).heir());
+    }
+
+    #[test]
+    fn test_producers_enter_and_exit() {
+        let mut world = World::new();
+        let coast = world.create_region("Coast");
+        let household = world.create_entity("Household", &[]);
+        world
+            .get_entity_mut(household)
+            .set_utility(Utility::CobbDouglas(vec![(WareType::Food, 1.0)]));
+        // Only the market of the coast pays more than the cost.
+        world
+            .market_mut()
+            .set_last_price(WareType::Food, WareType::Money, 4);
+        world.regions_mut()[coast]
+            .market_mut()
+            .set_last_price(WareType::Food, WareType::Money, 10);
+        for region in 0..=coast {
+            world.add_population_rule(PopulationRule::Entry(Entry::new(
+                WareType::Food,
+                region,
+                "Farmer",
+                vec![Recipe::from_str(FOOD_CREATOR_RECIPE).unwrap()],
+                vec![Ware::money(20)],
+                2,
+                1.0,
+            )));
+        }
+        world.add_population_rule(PopulationRule::Exit(Exit::new(3)));
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+
+        assert!(world.spawn_entities(&mut rng).is_empty());
+        let farmers = world.spawn_entities(&mut rng);
+        assert_eq!(1, farmers.len());
+        let farmer = farmers[0];
+        assert_eq!(coast, world.get_entity(farmer).region());
+        assert_eq!(
+            20,
+            world
+                .get_entity(farmer)
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+
+        // A producer that breaks even or whose wealth only fluctuates stays.
+        world.regions_mut()[coast]
+            .market_mut()
+            .set_last_price(WareType::Food, WareType::Money, 4);
+        for change in &[0, 0, 1, -1, 0] {
+            let entity = world.get_entity_mut(farmer);
+            if *change > 0 {
+                entity.add_ware(Ware::money(1));
+            } else if *change < 0 {
+                entity.remove_ware(Ware::money(1)).unwrap();
+            }
+            assert!(world.spawn_entities(&mut rng).is_empty());
+            assert!(world.remove_unprofitable_entities().is_empty());
+        }
+
+        world
+            .get_entity_mut(farmer)
+            .remove_ware(Ware::money(1))
+            .unwrap();
+        assert_eq!(farmers, world.remove_unprofitable_entities());
+        assert_eq!(1, world.entity_count());
+        assert!(world.contains_entity(household));
+    }
+}
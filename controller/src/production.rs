@@ -0,0 +1,410 @@
+use model::{
+    entity::{
+        batch::Batch,
+        recipe::{Recipe, Yield},
+        Entity,
+    },
+    resource::ResourceStock,
+    shock::Shock,
+    ware::{Ware, WareAmount, WareStore},
+    world::{Tick, World},
+};
+use rand::{
+    distributions::{Poisson, Uniform},
+    Rng,
+};
+
+pub trait RandomOutputs {
+    fn sample_outputs<R: Rng>(&self, rng: &mut R) -> Vec<Ware>;
+}
+
+impl RandomOutputs for Recipe {
+    /// Chooses an outcome by the weights of the outcomes, and draws the amounts of its outputs.
+    /// A recipe without outcomes of positive weight has no outputs.
+    fn sample_outputs<R: Rng>(&self, rng: &mut R) -> Vec<Ware> {
+        let total_weight: u32 = self.outcomes().iter().map(|outcome| outcome.weight()).sum();
+        if total_weight == 0 {
+            return Vec::new();
+        }
+
+        let mut choice = rng.gen_range(0, total_weight);
+        let outcome = self
+            .outcomes()
+            .iter()
+            .find(|outcome| {
+                if choice < outcome.weight() {
+                    true
+                } else {
+                    choice -= outcome.weight();
+                    false
+                }
+            })
+            .unwrap();
+
+        outcome
+            .outputs()
+            .iter()
+            .map(|output| Ware::new(output.ware_type(), output.amount().sample(rng)))
+            .collect()
+    }
+}
+
+pub trait RandomAmount {
+    fn sample<R: Rng>(&self, rng: &mut R) -> WareAmount;
+}
+
+impl RandomAmount for Yield {
+    fn sample<R: Rng>(&self, rng: &mut R) -> WareAmount {
+        match *self {
+            Yield::Fixed(amount) => amount,
+            Yield::Chance(amount, percent) => {
+                if rng.gen_bool(percent / 100.0) {
+                    amount
+                } else {
+                    0
+                }
+            }
+            Yield::Uniform(min, max) => rng.sample(Uniform::new_inclusive(min, max)),
+            Yield::Poisson(mean) => {
+                if mean > 0.0 {
+                    rng.sample(Poisson::new(mean)) as WareAmount
+                } else {
+                    0
+                }
+            }
+        }
+    }
+}
+
+pub trait Producer {
+    fn produce<R: Rng>(&mut self, tick: Tick, rng: &mut R);
+    fn produce_under<R: Rng>(
+        &mut self,
+        tick: Tick,
+        disabled: &[usize],
+        shocks: &[Shock],
+        resources: &mut [&mut ResourceStock],
+        rng: &mut R,
+    );
+}
+
+impl Producer for Entity {
+    /// Starts a batch of every recipe whose inputs and capital inputs are available,
+    /// consuming the inputs and wearing the capital inputs.
+    /// The outputs of a batch are drawn when it starts.
+    /// Then delivers the outputs of all batches that are complete at the given tick,
+    /// keeping outputs that do not fit in the storage of the entity in their batch,
+    /// and updates the health of the entity by the number of required recipes that starved.
+    fn produce<R: Rng>(&mut self, tick: Tick, rng: &mut R) {
+        self.produce_under(tick, &[], &[], &mut [], rng);
+    }
+
+    /// Produces like `produce`, but the recipes at the disabled indices and those disabled by a shock do not run
+    /// and count as starved if they are required,
+    /// and the outputs of a batch are scaled by the output shocks of their ware type when it starts.
+    /// Scaled outputs are rounded randomly.
+    /// Outputs of a ware type with a natural resource stock are extracted from the first such stock,
+    /// and only as much is produced as the stock holds.
+    fn produce_under<R: Rng>(
+        &mut self,
+        tick: Tick,
+        disabled: &[usize],
+        shocks: &[Shock],
+        resources: &mut [&mut ResourceStock],
+        rng: &mut R,
+    ) {
+        let mut starved_recipes = 0;
+
+        for (index, recipe) in self.recipes().to_vec().into_iter().enumerate() {
+            if disabled.contains(&index)
+                || shocks
+                    .iter()
+                    .any(|shock| *shock == Shock::Disable(recipe.clone()))
+                || recipe.capital_inputs().iter().any(|capital_input| {
+                    self.wares().ware_amount(capital_input.ware_type())
+                        < capital_input.ware().amount()
+                })
+            {
+                if recipe.is_required() {
+                    starved_recipes += 1;
+                }
+                continue;
+            }
+
+            let mut inputs = WareStore::new();
+            for input in recipe.inputs() {
+                inputs.push_ware_unlimited(input.clone());
+            }
+
+            if self.remove_wares(inputs).is_ok() {
+                for capital_input in recipe.capital_inputs() {
+                    self.wear_capital_input(capital_input);
+                }
+                let outputs = recipe
+                    .sample_outputs(rng)
+                    .into_iter()
+                    .map(|output| {
+                        let output = scale_output(output, shocks, rng);
+                        match resources
+                            .iter_mut()
+                            .find(|resource| resource.ware_type() == output.ware_type())
+                        {
+                            Some(resource) => {
+                                Ware::new(output.ware_type(), resource.extract(output.amount()))
+                            }
+                            None => output,
+                        }
+                    })
+                    .collect();
+                self.start_batch(Batch::new(outputs, tick + recipe.duration()));
+            } else if recipe.is_required() {
+                starved_recipes += 1;
+            }
+        }
+        self.update_health(starved_recipes);
+
+        // Outputs that do not fit in the storage stay in their batch until there is room.
+        for batch in self.take_completed_batches(tick) {
+            let overflow: Vec<_> = batch
+                .outputs()
+                .iter()
+                .map(|output| self.add_ware_max(output.clone()))
+                .filter(|overflow| overflow.amount() > 0)
+                .collect();
+            if !overflow.is_empty() {
+                self.start_batch(Batch::new(overflow, batch.completion()));
+            }
+        }
+    }
+}
+
+/// Scales the output by the product of the factors of the output shocks of its ware type.
+fn scale_output<R: Rng>(output: Ware, shocks: &[Shock], rng: &mut R) -> Ware {
+    let factor: f64 = shocks
+        .iter()
+        .filter_map(|shock| match shock {
+            Shock::Output(ware_type, factor) if *ware_type == output.ware_type() => Some(factor),
+            _ => None,
+        })
+        .product();
+    if factor == 1.0 {
+        return output;
+    }
+
+    let amount = round_randomly(f64::from(output.amount()) * factor, rng);
+    Ware::new(output.ware_type(), amount)
+}
+
+/// Rounds the amount down, and up instead with the chance of its fraction.
+pub fn round_randomly<R: Rng>(amount: f64, rng: &mut R) -> WareAmount {
+    let mut rounded = amount.floor() as WareAmount;
+    if rng.gen_bool(amount.fract()) {
+        rounded += 1;
+    }
+    rounded
+}
+
+pub trait Production {
+    fn produce<R: Rng>(&mut self, rng: &mut R);
+}
+
+impl Production for World {
+    fn produce<R: Rng>(&mut self, rng: &mut R) {
+        let tick = self.tick();
+        let shocks: Vec<_> = self
+            .active_shocks()
+            .iter()
+            .map(|(shock, _)| shock.clone())
+            .collect();
+        let (entities, regions, shared) = self.entities_resources_mut();
+        for (_, entity) in entities {
+            // Stocks of the region of the entity come before the shared stocks of the same ware type.
+            let mut resources: Vec<_> = regions[entity.region()]
+                .resources_mut()
+                .iter_mut()
+                .chain(shared.iter_mut())
+                .collect();
+            match entity.take_agent() {
+                Some(mut agent) => {
+                    // Recipes the agent leaves out are disabled for this tick.
+                    let chosen = agent.decide_production(entity);
+                    let disabled: Vec<_> = (0..entity.recipes().len())
+                        .filter(|index| !chosen.contains(index))
+                        .collect();
+                    entity.produce_under(tick, &disabled, &shocks, &mut resources, rng);
+                    entity.set_agent(Some(agent));
+                }
+                None => entity.produce_under(tick, &[], &shocks, &mut resources, rng),
+            }
+            entity.consume();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        production::{Producer, RandomAmount, RandomOutputs},
+        trading::Trader,
+    };
+    use model::{
+        entity::{
+            recipe::{Outcome, Output, Recipe, Yield},
+            Entity,
+        },
+        ware::{Storage, Ware, WareStore, WareType},
+    };
+    use rand::SeedableRng;
+    use rand_pcg::Pcg64Mcg;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_produce_multi_tick_recipe() {
+        let mut entity = Entity::new(
+            "Farmer".to_owned(),
+            vec![Recipe::from_str("(1x Soil; 1x Water) -> (2x Food) in 2 ticks").unwrap()],
+        );
+        entity.add_ware(Ware::new(WareType::Soil, 1));
+        entity.add_ware(Ware::new(WareType::Water, 1));
+        let mut rng: Pcg64Mcg = SeedableRng::from_seed([0; 16]);
+
+        entity.produce(0, &mut rng);
+        assert_eq!(0, entity.wares().ware_amount(WareType::Soil));
+        assert_eq!(0, entity.wares().ware_amount(WareType::Food));
+        assert_eq!(1, entity.batches().len());
+
+        entity.produce(1, &mut rng);
+        assert_eq!(0, entity.wares().ware_amount(WareType::Food));
+
+        entity.produce(2, &mut rng);
+        assert_eq!(2, entity.wares().ware_amount(WareType::Food));
+        assert!(entity.batches().is_empty());
+    }
+
+    #[test]
+    fn test_outputs_wait_for_storage() {
+        let mut entity = Entity::new(
+            "Farmer".to_owned(),
+            vec![Recipe::from_str("(1x Water) -> (2x Food)").unwrap()],
+        );
+        entity.set_storage(Some(Storage::new(Some(1))));
+        entity.add_ware(Ware::new(WareType::Water, 1));
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+
+        entity.produce(0, &mut rng);
+        assert_eq!(1, entity.wares().ware_amount(WareType::Food));
+        assert_eq!(
+            vec![Ware::new(WareType::Food, 1)],
+            entity.batches()[0].outputs()
+        );
+
+        entity.remove_ware(Ware::new(WareType::Food, 1)).unwrap();
+        entity.produce(1, &mut rng);
+        assert_eq!(1, entity.wares().ware_amount(WareType::Food));
+        assert!(entity.batches().is_empty());
+        assert_eq!(0, entity.lost().ware_amount(WareType::Food));
+    }
+
+    #[test]
+    fn test_disable_duplicate_recipe() {
+        let recipe = Recipe::from_str("() -> (1x Food)").unwrap();
+        let mut entity = Entity::new("Farmer".to_owned(), vec![recipe.clone(), recipe]);
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+
+        // Only the recipe at the disabled index stops, not its duplicate.
+        entity.produce_under(0, &[1], &[], &mut [], &mut rng);
+        assert_eq!(1, entity.wares().ware_amount(WareType::Food));
+    }
+
+    #[test]
+    fn test_batches_count_toward_demands() {
+        let mut entity = Entity::new(
+            "Baker".to_owned(),
+            vec![
+                Recipe::from_str("(1x Water) -> (1x Food) in 3 ticks").unwrap(),
+                Recipe::from_str("(1x Food) -> ()").unwrap(),
+            ],
+        );
+        entity.add_ware(Ware::new(WareType::Water, 1));
+        let mut rng: Pcg64Mcg = SeedableRng::from_seed([0; 16]);
+        entity.produce(0, &mut rng);
+
+        let mut unmet_demands = WareStore::new();
+        unmet_demands.push_ware_unlimited(Ware::new(WareType::Water, 1));
+        assert_eq!(
+            (WareStore::new(), unmet_demands),
+            entity.tradable_wares_and_unmet_demands()
+        );
+    }
+
+    #[test]
+    fn test_capital_inputs_wear_out() {
+        let mut entity = Entity::new(
+            "Farmer".to_owned(),
+            vec![Recipe::from_str("(1x Soil; @1x Plough/2) -> (1x Food)").unwrap()],
+        );
+        entity.add_ware(Ware::new(WareType::Soil, 3));
+        entity.add_ware(Ware::new(WareType::Plough, 1));
+        let mut rng: Pcg64Mcg = SeedableRng::from_seed([0; 16]);
+
+        entity.produce(0, &mut rng);
+        assert_eq!(1, entity.wares().ware_amount(WareType::Plough));
+        entity.produce(1, &mut rng);
+        assert_eq!(0, entity.wares().ware_amount(WareType::Plough));
+        entity.produce(2, &mut rng);
+        assert_eq!(2, entity.wares().ware_amount(WareType::Food));
+        assert_eq!(1, entity.wares().ware_amount(WareType::Soil));
+    }
+
+    #[test]
+    fn test_sample_outputs() {
+        let mut rng: Pcg64Mcg = SeedableRng::from_seed([0; 16]);
+        let recipe =
+            Recipe::from_str("() -> 1:(1x Food; 0x Water 100%; 2-3x Soil; ~0x Money) | 1:()")
+                .unwrap();
+        let mut outcomes = (0, 0);
+
+        for _ in 0..100 {
+            let outputs = recipe.sample_outputs(&mut rng);
+            if outputs.is_empty() {
+                outcomes.1 += 1;
+            } else {
+                outcomes.0 += 1;
+                assert_eq!(Ware::new(WareType::Food, 1), outputs[0]);
+                assert_eq!(Ware::new(WareType::Water, 0), outputs[1]);
+                assert!(outputs[2].amount() >= 2 && outputs[2].amount() <= 3);
+                assert_eq!(Ware::new(WareType::Money, 0), outputs[3]);
+            }
+        }
+        assert!(outcomes.0 > 0 && outcomes.1 > 0);
+    }
+
+    #[test]
+    fn test_sample_degenerate_outputs() {
+        let mut rng: Pcg64Mcg = SeedableRng::from_seed([0; 16]);
+        let recipe = Recipe::with_outcomes(
+            Vec::new(),
+            vec![Outcome::new(
+                0,
+                vec![Output::new(WareType::Food, Yield::Fixed(1))],
+            )],
+        );
+        assert!(recipe.sample_outputs(&mut rng).is_empty());
+
+        let amount = Yield::Uniform(u32::MAX - 1, u32::MAX).sample(&mut rng);
+        assert!(amount >= u32::MAX - 1);
+    }
+
+    #[test]
+    fn test_sample_outputs_is_reproducible() {
+        let recipe = Recipe::from_str("() -> (~3.5x Food; 1x Water 50%)").unwrap();
+        let sample = |seed| {
+            let mut rng: Pcg64Mcg = SeedableRng::from_seed([seed; 16]);
+            (0..10)
+                .map(|_| recipe.sample_outputs(&mut rng))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(sample(1), sample(1));
+    }
+}
@@ -0,0 +1,107 @@
+use model::world::World;
+
+pub trait Renewal {
+    fn regenerate_resources(&mut self);
+}
+
+impl Renewal for World {
+    /// Grows the shared natural resource stocks and the stocks of all regions by one tick of their growth.
+    fn regenerate_resources(&mut self) {
+        for resource in self.resources_mut() {
+            resource.regenerate();
+        }
+        for region in self.regions_mut() {
+            for resource in region.resources_mut() {
+                resource.regenerate();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::simulation::Simulation;
+    use model::{
+        entity::recipe::Recipe,
+        resource::{Growth, ResourceStock},
+        ware::WareType,
+        world::World,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn test_extraction_and_inflow() {
+        let mut world = World::new();
+        world.regions_mut()[0].add_resource(ResourceStock::new(
+            WareType::Water,
+            10.0,
+            10.0,
+            Growth::Inflow(2.0),
+        ));
+        let well = Recipe::from_str("() -> (4x Water)").unwrap();
+        let first = world.create_entity("First well", std::slice::from_ref(&well));
+        let second = world.create_entity("Second well", &[well]);
+
+        let mut simulation = Simulation::new(world, 0);
+        for _ in 0..3 {
+            simulation.step();
+        }
+        let world = simulation.world();
+        let water = |entity_id| {
+            world
+                .get_entity(entity_id)
+                .wares()
+                .ware_amount(WareType::Water)
+        };
+
+        assert_eq!(10, water(first));
+        assert_eq!(4, water(second));
+        let stock = &world.regions()[0].resources()[0];
+        assert_eq!(2.0, stock.amount());
+        assert_eq!(14, stock.extracted());
+    }
+
+    #[test]
+    fn test_shared_and_regional_stocks() {
+        let mut world = World::new();
+        let island = world.create_region("Island");
+        world.add_resource(ResourceStock::new(
+            WareType::Food,
+            10.0,
+            100.0,
+            Growth::Logistic(0.5),
+        ));
+        world.regions_mut()[island].add_resource(ResourceStock::new(
+            WareType::Food,
+            5.0,
+            100.0,
+            Growth::Logistic(0.5),
+        ));
+        let fishing = Recipe::from_str("() -> (10x Food)").unwrap();
+        let fisher = world.create_entity("Fisher", std::slice::from_ref(&fishing));
+        let islander = world.create_entity("Islander", &[fishing]);
+        world.get_entity_mut(islander).set_region(island);
+
+        let mut simulation = Simulation::new(world, 0);
+        simulation.step();
+        let world = simulation.world();
+
+        assert_eq!(
+            10,
+            world.get_entity(fisher).wares().ware_amount(WareType::Food)
+        );
+        assert_eq!(
+            5,
+            world
+                .get_entity(islander)
+                .wares()
+                .ware_amount(WareType::Food)
+        );
+        assert!(world.resources()[0].is_depleted());
+        assert!(world.regions()[island].resources()[0].is_depleted());
+
+        // Depleted stocks with logistic growth do not recover.
+        simulation.step();
+        assert_eq!(0.0, simulation.world().resources()[0].amount());
+    }
+}
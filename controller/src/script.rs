@@ -0,0 +1,383 @@
+use crate::agent::StandardAgent;
+use model::{
+    entity::{agent::Agent, Entity},
+    market::{offer::OfferType, Market},
+    ware::{Ware, WareType},
+};
+use rand::RngCore;
+use rhai::{module_resolvers::DummyModuleResolver, Array, Dynamic, Engine, Map, Scope, AST};
+use std::{convert::TryFrom, str::FromStr, sync::Arc};
+
+/// An agent whose decisions are made by a Rhai script.
+///
+/// The script defines `fn offers(entity, market)`, which returns an array of offers like
+/// `#{ware: "Food", amount: 2, type: "sell", price: 5}` with prices in the currency of the entity.
+/// It may define `fn production(entity)`, which returns the indices of the recipes to run, otherwise all recipes run.
+///
+/// The entity is passed as a copy with its `name`, `currency`, `wares`, `sell_prices`, `buy_prices` and `recipes`,
+/// and the market with the `bids` and `asks` of every ware type as arrays of `[price, amount]` and its `last_prices`,
+/// all in the currency of the entity.
+///
+/// Scripts are sandboxed: they cannot import modules, evaluate code or print,
+/// and every call is aborted after the given number of operations.
+/// If a call fails, the entity follows the standard strategy for it.
+#[derive(Clone, Debug)]
+pub struct ScriptAgent {
+    engine: Arc<Engine>,
+    ast: AST,
+}
+
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_EXPR_DEPTH: usize = 64;
+const MAX_STRING_SIZE: usize = 1024;
+const MAX_ARRAY_SIZE: usize = 4096;
+const MAX_MAP_SIZE: usize = 1024;
+
+impl ScriptAgent {
+    /// Compiles the script with a limit on the number of operations of each call.
+    /// Fails if the script does not compile or does not define `offers`.
+    pub fn new(script: &str, max_operations: u64) -> Result<Self, ()> {
+        let mut engine = Engine::new();
+        engine
+            .set_max_operations(max_operations.max(1))
+            .set_max_call_levels(MAX_CALL_LEVELS)
+            .set_max_expr_depths(MAX_EXPR_DEPTH, MAX_EXPR_DEPTH)
+            .set_max_string_size(MAX_STRING_SIZE)
+            .set_max_array_size(MAX_ARRAY_SIZE)
+            .set_max_map_size(MAX_MAP_SIZE)
+            .set_module_resolver(DummyModuleResolver::new())
+            .on_print(|_| {})
+            .on_debug(|_, _, _| {})
+            .disable_symbol("eval");
+
+        let ast = engine.compile(script).map_err(|_| ())?;
+        let agent = Self {
+            engine: Arc::new(engine),
+            ast,
+        };
+        if !agent.defines("offers") {
+            return Err(());
+        }
+        Ok(agent)
+    }
+
+    /// Calls `offers` of the script and returns the offers as triples of the ware, the offer type and the price per ware.
+    /// Fails if the script fails, runs out of operations or returns malformed offers.
+    pub fn script_offers(
+        &self,
+        entity: &Entity,
+        market: &Market,
+    ) -> Result<Vec<(Ware, OfferType, Ware)>, ()> {
+        let currency = entity.currency();
+        let offers: Array = self
+            .engine
+            .call_fn(
+                &mut Scope::new(),
+                &self.ast,
+                "offers",
+                (entity_view(entity), market_view(market, currency)),
+            )
+            .map_err(|_| ())?;
+
+        let mut result = Vec::new();
+        for offer in offers {
+            let offer = offer.try_cast::<Map>().ok_or(())?;
+            let field = |name: &str| offer.get(name).cloned().ok_or(());
+            let ware_type = WareType::from_str(&field("ware")?.into_string().map_err(|_| ())?)
+                .map_err(|_| ())?;
+            let amount =
+                u32::try_from(field("amount")?.as_int().map_err(|_| ())?).map_err(|_| ())?;
+            let offer_type = match field("type")?.into_string().map_err(|_| ())?.as_str() {
+                "buy" => OfferType::Buy,
+                "sell" => OfferType::Sell,
+                _ => return Err(()),
+            };
+            let price = u32::try_from(field("price")?.as_int().map_err(|_| ())?).map_err(|_| ())?;
+            if ware_type == currency || price == 0 {
+                return Err(());
+            }
+            if amount > 0 {
+                result.push((
+                    Ware::new(ware_type, amount),
+                    offer_type,
+                    Ware::new(currency, price),
+                ));
+            }
+        }
+        Ok(result)
+    }
+
+    /// Calls `production` of the script and returns the indices of the chosen recipes,
+    /// or those of all recipes if the script does not define it.
+    /// Fails if the script fails, runs out of operations or returns an index that is not a recipe of the entity.
+    pub fn script_production(&self, entity: &Entity) -> Result<Vec<usize>, ()> {
+        if !self.defines("production") {
+            return Ok((0..entity.recipes().len()).collect());
+        }
+
+        let indices: Array = self
+            .engine
+            .call_fn(
+                &mut Scope::new(),
+                &self.ast,
+                "production",
+                (entity_view(entity),),
+            )
+            .map_err(|_| ())?;
+        indices
+            .into_iter()
+            .map(|index| {
+                let index = usize::try_from(index.as_int().map_err(|_| ())?).map_err(|_| ())?;
+                if index < entity.recipes().len() {
+                    Ok(index)
+                } else {
+                    Err(())
+                }
+            })
+            .collect()
+    }
+
+    fn defines(&self, name: &str) -> bool {
+        self.ast
+            .iter_functions()
+            .any(|function| function.name == name)
+    }
+}
+
+impl Agent for ScriptAgent {
+    fn decide_offers(
+        &mut self,
+        entity: &Entity,
+        market: &Market,
+        rng: &mut dyn RngCore,
+    ) -> Vec<(Ware, OfferType, Ware)> {
+        self.script_offers(entity, market)
+            .unwrap_or_else(|_| StandardAgent.decide_offers(entity, market, rng))
+    }
+
+    fn decide_production(&mut self, entity: &Entity) -> Vec<usize> {
+        self.script_production(entity)
+            .unwrap_or_else(|_| StandardAgent.decide_production(entity))
+    }
+
+    fn box_clone(&self) -> Box<dyn Agent> {
+        Box::new(self.clone())
+    }
+}
+
+/// A read-only copy of the entity for scripts.
+fn entity_view(entity: &Entity) -> Map {
+    let currency = entity.currency();
+    let prices = |price: &dyn Fn(WareType) -> u32| -> Map {
+        WareType::iter_variants()
+            .filter(|&ware_type| ware_type != currency)
+            .map(|ware_type| {
+                (
+                    ware_type.to_string().into(),
+                    Dynamic::from_int(price(ware_type).into()),
+                )
+            })
+            .collect()
+    };
+    let recipes: Array = entity
+        .recipes()
+        .iter()
+        .map(|recipe| {
+            let mut view = Map::new();
+            view.insert(
+                "inputs".into(),
+                Dynamic::from_map(wares_view(recipe.inputs().iter().cloned())),
+            );
+            let outputs: Map = recipe
+                .outcomes()
+                .iter()
+                .flat_map(|outcome| outcome.outputs())
+                .map(|output| {
+                    let ware_type = output.ware_type();
+                    (
+                        ware_type.to_string().into(),
+                        Dynamic::from_float(recipe.expected_output(ware_type)),
+                    )
+                })
+                .collect();
+            view.insert("outputs".into(), Dynamic::from_map(outputs));
+            view.insert(
+                "duration".into(),
+                Dynamic::from_int(i64::try_from(recipe.duration()).unwrap_or(i64::MAX)),
+            );
+            view.insert("required".into(), Dynamic::from_bool(recipe.is_required()));
+            Dynamic::from_map(view)
+        })
+        .collect();
+
+    let mut view = Map::new();
+    view.insert("name".into(), entity.name().into());
+    view.insert("currency".into(), currency.to_string().into());
+    view.insert(
+        "wares".into(),
+        Dynamic::from_map(wares_view(entity.wares().iter())),
+    );
+    view.insert(
+        "sell_prices".into(),
+        Dynamic::from_map(prices(&|ware_type| {
+            entity.sell_prices().single_price_in(ware_type, currency)
+        })),
+    );
+    view.insert(
+        "buy_prices".into(),
+        Dynamic::from_map(prices(&|ware_type| {
+            entity.buy_prices().single_price_in(ware_type, currency)
+        })),
+    );
+    view.insert("recipes".into(), Dynamic::from_array(recipes));
+    view
+}
+
+/// A read-only copy of the depth and the last prices of the market in the given currency for scripts.
+fn market_view(market: &Market, currency: WareType) -> Map {
+    let depth = |offer_type: OfferType| -> Map {
+        WareType::iter_variants()
+            .filter(|&ware_type| ware_type != currency)
+            .map(|ware_type| {
+                let levels: Array = market
+                    .depth(ware_type, currency, offer_type)
+                    .into_iter()
+                    .map(|(price, amount)| {
+                        Dynamic::from_array(vec![
+                            Dynamic::from_int(price.into()),
+                            Dynamic::from_int(amount.into()),
+                        ])
+                    })
+                    .collect();
+                (ware_type.to_string().into(), Dynamic::from_array(levels))
+            })
+            .collect()
+    };
+    let last_prices: Map = WareType::iter_variants()
+        .filter_map(|ware_type| {
+            let price = market.last_price(ware_type, currency)?;
+            Some((
+                ware_type.to_string().into(),
+                Dynamic::from_int(price.into()),
+            ))
+        })
+        .collect();
+
+    let mut view = Map::new();
+    view.insert("bids".into(), Dynamic::from_map(depth(OfferType::Buy)));
+    view.insert("asks".into(), Dynamic::from_map(depth(OfferType::Sell)));
+    view.insert("last_prices".into(), Dynamic::from_map(last_prices));
+    view
+}
+
+fn wares_view(wares: impl Iterator<Item = Ware>) -> Map {
+    wares
+        .map(|ware| {
+            (
+                ware.ware_type().to_string().into(),
+                Dynamic::from_int(ware.amount().into()),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{agent::StandardAgent, script::ScriptAgent};
+    use model::{
+        arena::Index,
+        entity::{agent::Agent, recipe::Recipe, Entity},
+        market::{offer::OfferType, Market},
+        ware::{Ware, WareType},
+    };
+    use rand::SeedableRng;
+    use rand_pcg::Pcg64Mcg;
+    use std::str::FromStr;
+
+    fn farmer() -> Entity {
+        let mut entity = Entity::new(
+            "Farmer".to_owned(),
+            vec![
+                Recipe::from_str("() -> (2x Food)").unwrap(),
+                Recipe::from_str("(1x Food) -> (1x Water)").unwrap(),
+            ],
+        );
+        entity.add_ware(Ware::new(WareType::Food, 4));
+        entity.sell_prices_mut().set_single_price(WareType::Food, 7);
+        entity
+    }
+
+    #[test]
+    fn test_script_decides_offers_and_production() {
+        let agent = ScriptAgent::new(
+            r#"
+            fn offers(entity, market) {
+                let bids = market.bids.Food;
+                let price = if bids.is_empty() { entity.sell_prices.Food } else { bids[0][0] };
+                [#{ware: "Food", amount: entity.wares.Food / 2, type: "sell", price: price}]
+            }
+
+            fn production(entity) {
+                let chosen = [];
+                for i in 0..entity.recipes.len() {
+                    if "Water" in entity.recipes[i].outputs {
+                        chosen.push(i);
+                    }
+                }
+                chosen
+            }
+            "#,
+            10_000,
+        )
+        .unwrap();
+        let entity = farmer();
+
+        let mut market = Market::new();
+        assert_eq!(
+            Ok(vec![(
+                Ware::new(WareType::Food, 2),
+                OfferType::Sell,
+                Ware::money(7)
+            )]),
+            agent.script_offers(&entity, &market)
+        );
+        market.create_offer(
+            Ware::new(WareType::Food, 1),
+            OfferType::Buy,
+            Ware::money(9),
+            Index::new(0, 0),
+        );
+        assert_eq!(
+            Ok(vec![(
+                Ware::new(WareType::Food, 2),
+                OfferType::Sell,
+                Ware::money(9)
+            )]),
+            agent.script_offers(&entity, &market)
+        );
+        assert_eq!(Ok(vec![1]), agent.script_production(&entity));
+    }
+
+    #[test]
+    fn test_script_sandbox() {
+        assert!(ScriptAgent::new("fn offers(entity, market) { eval(\"[]\") }", 1000).is_err());
+        assert!(ScriptAgent::new("fn production(entity) { [] }", 1000).is_err());
+
+        // A script that does not finish is aborted, and the entity follows the standard strategy.
+        let mut agent = ScriptAgent::new(
+            "fn offers(entity, market) { loop {} }\nfn production(entity) { [5] }",
+            1000,
+        )
+        .unwrap();
+        let entity = farmer();
+        let market = Market::new();
+        assert_eq!(Err(()), agent.script_offers(&entity, &market));
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        assert_eq!(
+            StandardAgent.decide_offers(&entity, &market, &mut Pcg64Mcg::seed_from_u64(0)),
+            agent.decide_offers(&entity, &market, &mut rng)
+        );
+        assert_eq!(Err(()), agent.script_production(&entity));
+        assert_eq!(vec![0, 1], agent.decide_production(&entity));
+    }
+}
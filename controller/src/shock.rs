@@ -0,0 +1,144 @@
+use model::{
+    event::Event,
+    shock::{Shock, Trigger},
+    world::World,
+};
+use rand::{seq::SliceRandom, Rng};
+
+pub trait Shocks {
+    fn fire_shocks<R: Rng>(&mut self, rng: &mut R) -> Vec<Shock>;
+}
+
+impl Shocks for World {
+    /// Ends the lasting shocks that ran out, then fires the scheduled shocks whose trigger says so in this tick,
+    /// and records an event for each. Returns the shocks that fired.
+    /// Lasting shocks take effect for their duration, or forever.
+    /// A cull removes its share of the households, chosen at random and rounded to the nearest household,
+    /// and a windfall gives its ware to every household.
+    fn fire_shocks<R: Rng>(&mut self, rng: &mut R) -> Vec<Shock> {
+        self.expire_shocks();
+
+        let tick = self.tick();
+        let mut fired = Vec::new();
+        for scheduled in self.shocks().to_vec() {
+            let fires = match scheduled.trigger() {
+                Trigger::At(at) => at == tick,
+                Trigger::Chance(chance) => rng.gen_bool(chance.min(1.0)),
+            };
+            if !fires {
+                continue;
+            }
+
+            let shock = scheduled.shock().clone();
+            match &shock {
+                Shock::Output(..) | Shock::Disable(_) => {
+                    let until = scheduled.duration().map(|duration| tick + duration);
+                    self.activate_shock(shock.clone(), until);
+                }
+                Shock::Cull(share) => {
+                    let mut household_ids: Vec<_> = self
+                        .iter_entities()
+                        .filter(|(_, entity)| entity.is_household())
+                        .map(|(entity_id, _)| entity_id)
+                        .collect();
+                    household_ids.shuffle(rng);
+                    let culled = (household_ids.len() as f64 * share.min(1.0)).round() as usize;
+                    for &entity_id in &household_ids[..culled] {
+                        self.remove_entity(entity_id);
+                    }
+                }
+                Shock::Windfall(ware) => {
+                    for entity in self.entities_mut() {
+                        if entity.is_household() {
+                            entity.add_ware(ware.clone());
+                        }
+                    }
+                }
+            }
+            self.log_event(Event::Shock(shock.clone()));
+            fired.push(shock);
+        }
+        fired
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{shock::Shocks, simulation::Simulation};
+    use model::{
+        entity::{recipe::Recipe, utility::Utility},
+        event::Event,
+        shock::{ScheduledShock, Shock},
+        ware::{Ware, WareType},
+        world::World,
+    };
+    use rand::SeedableRng;
+    use rand_pcg::Pcg64Mcg;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_lasting_shocks() {
+        let mut world = World::new();
+        let well = world.create_entity("Well", &[Recipe::from_str("() -> (4x Water)").unwrap()]);
+        let farm = world.create_entity("Farm", &[Recipe::from_str("() -> (1x Food)").unwrap()]);
+        world.add_shock(ScheduledShock::from_str("at 1: output Water 50% for 2 ticks").unwrap());
+        world.add_shock(
+            ScheduledShock::from_str("at 1: disable () -> (1x Food) for 1 tick").unwrap(),
+        );
+
+        let mut simulation = Simulation::new(world, 0);
+        let mut water = Vec::new();
+        let mut food = Vec::new();
+        for _ in 0..4 {
+            simulation.step();
+            let world = simulation.world();
+            water.push(world.get_entity(well).wares().ware_amount(WareType::Water));
+            food.push(world.get_entity(farm).wares().ware_amount(WareType::Food));
+        }
+
+        assert_eq!(vec![4, 6, 8, 12], water);
+        assert_eq!(vec![1, 1, 2, 3], food);
+        assert!(simulation.world().active_shocks().is_empty());
+        assert_eq!(2, simulation.world().events().len());
+    }
+
+    #[test]
+    fn test_cull_and_windfall() {
+        let mut rng = Pcg64Mcg::seed_from_u64(0);
+        let mut world = World::new();
+        let mut households = Vec::new();
+        for _ in 0..10 {
+            let entity_id = world.create_entity("Household", &[]);
+            world
+                .get_entity_mut(entity_id)
+                .set_utility(Utility::CobbDouglas(vec![(WareType::Food, 1.0)]));
+            households.push(entity_id);
+        }
+        let firm = world.create_entity("Firm", &[]);
+        world.add_shock(ScheduledShock::from_str("at 0: cull 30%").unwrap());
+        world.add_shock(ScheduledShock::from_str("at 0: windfall 5x Money").unwrap());
+        world.add_shock(ScheduledShock::from_str("at 1: cull 100%").unwrap());
+
+        let fired = world.fire_shocks(&mut rng);
+        assert_eq!(
+            vec![Shock::Cull(0.3), Shock::Windfall(Ware::money(5))],
+            fired
+        );
+        let remaining: Vec<_> = households
+            .iter()
+            .filter_map(|&entity_id| world.try_get_entity(entity_id))
+            .collect();
+        assert_eq!(7, remaining.len());
+        assert!(remaining
+            .iter()
+            .all(|entity| entity.wares().ware_amount(WareType::Money) == 5));
+        assert_eq!(
+            0,
+            world.get_entity(firm).wares().ware_amount(WareType::Money)
+        );
+        assert_eq!(
+            &(0, Event::Shock(Shock::Cull(0.3))),
+            world.events().first().unwrap()
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    agent::Agents,
+    auction::Auctioneer,
+    banking::Banking,
+    bankruptcy::Insolvency,
+    central_bank::MonetaryPolicy,
+    contract::Contracting,
+    decay::Spoilage,
+    fiscal::FiscalPolicy,
+    futures::Futures,
+    labor::LaborMarket,
+    market_maker::LiquidityProvision,
+    merchant::Arbitrage,
+    population::{Mortality, Population},
+    production::Production,
+    resource::Renewal,
+    shock::Shocks,
+    trading::{Economy, RandomizedMarket},
+    transport::Transport,
+};
+use model::{market::fill::Fill, world::World};
+use rand::SeedableRng;
+use rand_pcg::Pcg64Mcg;
+
+/// Drives a world through ticks.
+/// All random decisions are drawn from a single seeded random number generator,
+/// such that a simulation is reproducible from its world and seed.
+#[derive(Clone, Debug)]
+pub struct Simulation {
+    world: World,
+    rng: Pcg64Mcg,
+}
+
+// Creators
+impl Simulation {
+    pub fn new(world: World, seed: u64) -> Self {
+        Self {
+            world,
+            rng: Pcg64Mcg::seed_from_u64(seed),
+        }
+    }
+}
+
+// Modifiers
+impl Simulation {
+    /// Runs one tick of the world. Returns the fills that were settled in this tick.
+    pub fn step(&mut self) -> Vec<Fill> {
+        // Shocks, travellers and merchant voyages.
+        self.world.fire_shocks(&mut self.rng);
+        self.world.arrive_travellers();
+        self.world.plan_voyages();
+        self.world.supply_labor();
+        // Deliveries of forward and supply contracts due in this tick.
+        let mut fills = self.world.settle_forwards();
+        fills.extend(self.world.execute_contracts());
+        // Trading in the spot markets, then futures and auctions.
+        self.world.prepare_open_market_operations();
+        self.world.update_market_offers(&mut self.rng);
+        let trades = self.world.resolve_trades(&mut self.rng);
+        self.world.record_market_making(&trades);
+        fills.extend(trades);
+        self.world.clear_futures(&mut self.rng);
+        fills.extend(self.world.run_auctions());
+        self.world.notify_agents(&fills);
+        // Bankrupt entities sold their inventory in the trades above.
+        self.world.liquidate_bankrupt_entities();
+        // Production, and regeneration of the resources it extracted.
+        self.world.produce(&mut self.rng);
+        self.world.regenerate_resources();
+        // Unused labor perishes and wares spoil.
+        self.world.expire_labor(&fills);
+        self.world.decay_wares(&mut self.rng);
+        // Taxes, liabilities and transfers.
+        self.world.levy_taxes(&fills);
+        self.world.pay_liabilities();
+        self.world.pay_transfers(&fills);
+        // Monetary policy, then interest and installments due in the next tick.
+        self.world.update_price_index();
+        self.world.update_monetary_policy();
+        self.world.update_banks();
+        // Deaths, exits, births and entries.
+        self.world.remove_dead_entities();
+        self.world.remove_unprofitable_entities();
+        self.world.spawn_entities(&mut self.rng);
+        self.world.advance_tick();
+        fills
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+}
+
+// Getters
+impl Simulation {
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+}
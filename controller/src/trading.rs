@@ -1,11 +1,12 @@
+use crate::{agent::StandardAgent, central_bank::OpenMarketAgent, fiscal::ProcurementAgent};
 use model::{
-    entity::Entity,
-    market::{offer::OfferType, Market},
-    ware::{WareAmount, WareStore, WareType},
+    entity::{agent::Agent, Entity},
+    market::{fill::Fill, offer::OfferType, Market},
+    ware::{Ware, WareAmount, WareStore},
     world::World,
 };
-use rand::seq::SliceRandom;
-use std::cmp::Ordering;
+use rand::{seq::SliceRandom, Rng};
+use std::cmp::Reverse;
 
 pub trait Trader {
     fn tradable_wares_and_unmet_demands(&self) -> (WareStore, WareStore);
@@ -15,119 +16,230 @@ impl Trader for Entity {
     fn tradable_wares_and_unmet_demands(&self) -> (WareStore, WareStore) {
         let mut demands = WareStore::new();
 
+        let mut capital_demands = WareStore::new();
+
         for recipe in self.recipes() {
             for input in recipe.inputs() {
-                demands.push_ware(input.clone());
+                demands.push_ware_unlimited(input.clone());
             }
+
+            // Capital inputs are not consumed, so recipes can share them.
+            for capital_input in recipe.capital_inputs() {
+                let demand = capital_demands.ware_amount(capital_input.ware_type());
+                if capital_input.ware().amount() > demand {
+                    capital_demands.push_ware_unlimited(Ware::new(
+                        capital_input.ware_type(),
+                        capital_input.ware().amount() - demand,
+                    ));
+                }
+            }
+        }
+
+        for capital_demand in capital_demands.iter() {
+            demands.push_ware_unlimited(capital_demand);
         }
 
         let mut tradable_wares = self.wares().clone();
+        // Wares due under supply contracts are kept for delivery.
+        // Contracted supplies arrive only after this tick's recipes ran, so they do not cover its demands.
+        tradable_wares.pop_wares_max(&mut self.committed_deliveries().clone());
         tradable_wares.pop_wares_max(&mut demands);
+        if let Some(utility) = self.utility() {
+            // Households keep the wares they consume.
+            for ware_type in utility.ware_types() {
+                if let Some(ware) = tradable_wares.get_ware(ware_type) {
+                    tradable_wares.pop_ware(ware).unwrap();
+                }
+            }
+        }
+        // Outputs of batches in progress will cover part of the demands once they complete.
+        self.wares_in_progress().pop_wares_max(&mut demands);
         (tradable_wares, demands)
     }
 }
 
 pub trait Economy {
-    fn update_market_offers(&mut self);
+    fn update_market_offers<R: Rng>(&mut self, rng: &mut R);
 }
 
 impl Economy for World {
-    fn update_market_offers(&mut self) {
-        let (entities, market) = self.entities_market_mut();
+    fn update_market_offers<R: Rng>(&mut self, rng: &mut R) {
+        let (entities, regions) = self.entities_regions_mut();
 
-        market.clear_offers();
+        for region in regions.iter_mut() {
+            region.market_mut().clear_offers();
+        }
 
-        for (entity_id, entity) in entities.iter_mut().enumerate() {
-            let (tradable_wares, unmet_demands) = entity.tradable_wares_and_unmet_demands();
+        for (entity_id, entity) in entities {
             entity.clear_offer_ids();
+            if entity.is_travelling() {
+                continue;
+            }
 
-            for tradable_ware in tradable_wares.iter() {
-                if tradable_ware.is_money() {
-                    continue;
-                }
+            // Entities trade in the market of their region.
+            let market = regions[entity.region()].market_mut();
 
-                let price_per_ware = entity.sell_prices().single_price_as_ware(&tradable_ware);
+            // Entities trade by their agent. Entities without one get the agent of their role.
+            let mut agent = entity.take_agent().unwrap_or_else(|| role_agent(entity));
+            agent.observe_market(entity, market);
+            let offers = agent.decide_offers(entity, market, rng);
+            entity.set_agent(Some(agent));
+            for (ware, offer_type, price_per_ware) in offers {
                 entity.add_offer_id(market.create_offer(
-                    tradable_ware,
-                    OfferType::Sell,
+                    ware,
+                    offer_type,
                     price_per_ware,
                     entity_id,
                 ));
             }
+        }
 
-            let mut money = tradable_wares.ware_amount(WareType::Money);
-            let mut unmet_demands: Vec<_> = unmet_demands.iter().collect();
-            unmet_demands.shuffle(&mut rand::thread_rng());
-
-            for unmet_demand in unmet_demands.iter() {
-                if unmet_demand.is_money() {
-                    continue;
-                }
-
-                let price_per_ware = entity.buy_prices().single_price_as_ware(&unmet_demand);
-                let max_buy = money / price_per_ware.amount();
-                let mut unmet_demand = unmet_demand.clone();
-                *unmet_demand.amount_mut() = unmet_demand.amount().min(max_buy);
-                if unmet_demand.amount() > 0 {
-                    money -= unmet_demand.amount() * price_per_ware.amount();
-                    entity.add_offer_id(market.create_offer(
-                        unmet_demand,
-                        OfferType::Buy,
-                        price_per_ware,
-                        entity_id,
-                    ));
-                }
-            }
+        for region in regions.iter_mut() {
+            region.market_mut().sort_offers();
         }
+    }
+}
 
-        market.sort_offers();
+/// The agent of an entity that has none yet: the agent of its role, or the standard agent.
+fn role_agent(entity: &Entity) -> Box<dyn Agent> {
+    if entity.is_central_bank() {
+        Box::new(OpenMarketAgent)
+    } else if entity.is_government() {
+        Box::new(ProcurementAgent)
+    } else {
+        Box::new(StandardAgent)
     }
 }
 
 pub trait RandomizedMarket {
-    fn resolve_trades(&mut self);
+    fn resolve_trades<R: Rng>(&mut self, rng: &mut R) -> Vec<Fill>;
 }
 
-impl RandomizedMarket for Market {
-    fn resolve_trades(&mut self) {
-        println!("{}", self);
-
-        for ware_range in self.iter_ware_ranges_mut() {
-            // Iterate over minimum price of sell offers.
-            let sell_offer_limits = ware_range.sell_offer_limits();
-
-            for sell_offer_limit in sell_offer_limits {
-                let sell_offer_size: WareAmount = sell_offer_limit
-                    .sell_slice(ware_range.sell_offers())
-                    .iter()
-                    .map(|offer| offer.amount())
-                    .sum();
-                let buy_offer_size: WareAmount = sell_offer_limit
-                    .buy_slice(ware_range.buy_offers())
-                    .iter()
-                    .map(|offer| offer.amount())
-                    .sum();
-
-                match sell_offer_size.cmp(&buy_offer_size) {
-                    Ordering::Equal => {
-                        // Fulfill all offers at minimum price.
+pub trait OfferBook {
+    fn match_offers<R, S>(&mut self, rng: &mut R, settle: S) -> Vec<Fill>
+    where
+        R: Rng,
+        S: FnMut(&Fill) -> Result<(), OfferType>;
+}
+
+impl OfferBook for Market {
+    /// Matches the highest buy offers with the lowest sell offers of each trading pair.
+    /// Offers with equal prices are served in random order, and trades happen at the price of the sell offer.
+    /// Each fill is settled right away. If the settlement fails, the offer of the failing side is skipped,
+    /// and no volume of either offer is consumed.
+    /// Filled offers are removed from the market, and partially filled offers are reduced.
+    fn match_offers<R, S>(&mut self, rng: &mut R, mut settle: S) -> Vec<Fill>
+    where
+        R: Rng,
+        S: FnMut(&Fill) -> Result<(), OfferType>,
+    {
+        let ranges: Vec<_> = self
+            .iter_ware_ranges()
+            .map(|ware_range| {
+                (
+                    ware_range.buy_offers().len(),
+                    ware_range.sell_offers().len(),
+                )
+            })
+            .collect();
+        let offers = self.offers();
+        let mut remaining: Vec<WareAmount> = offers.iter().map(|offer| offer.amount()).collect();
+        let mut fills = Vec::new();
+        let mut last_prices = Vec::new();
+        let mut offset = 0;
+
+        for (buy_count, sell_count) in ranges {
+            let mut buys: Vec<_> = (offset..offset + buy_count).collect();
+            offset += buy_count;
+            let mut sells: Vec<_> = (offset..offset + sell_count).collect();
+            offset += sell_count;
+
+            // Shuffle first, such that the stable sort orders offers with equal prices randomly.
+            buys.shuffle(rng);
+            buys.sort_by_key(|&i| Reverse(offers[i].price_per_ware().amount()));
+            sells.shuffle(rng);
+            sells.sort_by_key(|&i| offers[i].price_per_ware().amount());
+
+            let (mut buy_index, mut sell_index) = (0, 0);
+            while buy_index < buys.len() && sell_index < sells.len() {
+                let (buy, sell) = (buys[buy_index], sells[sell_index]);
+                if offers[buy].price_per_ware().amount() < offers[sell].price_per_ware().amount() {
+                    break;
+                }
+
+                let amount = remaining[buy].min(remaining[sell]);
+                let fill = Fill::new(
+                    Ware::new(offers[sell].offer().ware_type(), amount),
+                    offers[sell].price_per_ware().clone(),
+                    offers[buy].entity_id(),
+                    offers[sell].entity_id(),
+                );
+                match settle(&fill) {
+                    Ok(()) => {}
+                    Err(OfferType::Buy) => {
+                        buy_index += 1;
+                        continue;
+                    }
+                    Err(OfferType::Sell) => {
+                        sell_index += 1;
+                        continue;
                     }
-                    _ => unimplemented!(),
                 }
-            }
 
-            // Fulfill the offers fairly at random, using GSL::multinomial() to deal out the minority to the majority.
+                remaining[buy] -= amount;
+                remaining[sell] -= amount;
+                fills.push(fill);
+                last_prices.push((
+                    offers[sell].trading_pair(),
+                    offers[sell].price_per_ware().amount(),
+                ));
 
-            // Offers are fulfilled at minimum price.
+                if remaining[buy] == 0 {
+                    buy_index += 1;
+                }
+                if remaining[sell] == 0 {
+                    sell_index += 1;
+                }
+            }
         }
 
-        unimplemented!()
+        let filled: Vec<_> = offers
+            .iter()
+            .zip(remaining)
+            .map(|(offer, remaining)| offer.amount() - remaining)
+            .collect();
+        for (offer_index, amount) in filled.into_iter().enumerate() {
+            self.fill_offer(offer_index, amount);
+        }
+        for ((ware_type, currency), price) in last_prices {
+            self.set_last_price(ware_type, currency, price);
+        }
+        self.remove_filled_offers();
+
+        fills
     }
 }
 
 impl RandomizedMarket for World {
-    fn resolve_trades(&mut self) {
-        self.market_mut().resolve_trades()
+    /// Matches the offers of the market of each region and settles the fills between the entities.
+    /// Returns the fills that were settled, and records them in the trade statistics of their region.
+    fn resolve_trades<R: Rng>(&mut self, rng: &mut R) -> Vec<Fill> {
+        let mut fills = Vec::new();
+        for region in 0..self.regions().len() {
+            // The market is taken out of the world while it settles fills between the entities.
+            let mut market = std::mem::take(self.market_in_mut(region));
+            market.clear_trade_stats();
+            let region_fills = market.match_offers(rng, |fill| {
+                self.check_settlement(fill)?;
+                self.settle(fill).map_err(|_| OfferType::Buy)
+            });
+            for fill in &region_fills {
+                market.record_trade(fill);
+            }
+            *self.market_in_mut(region) = market;
+            fills.extend(region_fills);
+        }
+        fills
     }
 }
 
@@ -136,9 +248,9 @@ mod test {
     use crate::trading::{Economy, RandomizedMarket, Trader};
     use model::{
         entity::{recipe::Recipe, Entity},
-        market::{offer::OfferType, Market},
+        market::{fill::Fill, offer::OfferType, Market},
         templates::{EATING_RECIPE, FOOD_CREATOR_RECIPE},
-        ware::{Ware, WareStore, WareType},
+        ware::{Storage, Ware, WareStore, WareType},
         world::World,
     };
     use rand::{distributions::Uniform, Rng, SeedableRng};
@@ -154,7 +266,7 @@ mod test {
         entity.add_ware(Ware::new(WareType::Food, 10));
 
         let mut tradable_wares = WareStore::new();
-        tradable_wares.push_ware(Ware::new(WareType::Food, 10));
+        tradable_wares.push_ware_unlimited(Ware::new(WareType::Food, 10));
         let unmet_demands = WareStore::new();
 
         assert_eq!(
@@ -163,37 +275,60 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_capital_inputs_are_not_sold() {
+        let mut entity = Entity::new(
+            "Farmer".to_owned(),
+            vec![
+                Recipe::from_str("(1x Soil; @1x Plough) -> (1x Food)").unwrap(),
+                Recipe::from_str("(1x Water; @1x Plough/10) -> (1x Food)").unwrap(),
+            ],
+        );
+        entity.add_ware(Ware::new(WareType::Plough, 3));
+        entity.add_ware(Ware::new(WareType::Soil, 1));
+        entity.add_ware(Ware::new(WareType::Water, 1));
+
+        let mut tradable_wares = WareStore::new();
+        tradable_wares.push_ware_unlimited(Ware::new(WareType::Plough, 2));
+
+        assert_eq!(
+            (tradable_wares, WareStore::new()),
+            entity.tradable_wares_and_unmet_demands()
+        );
+    }
+
     #[test]
     fn test_update_market_offers() {
+        let mut rng: Pcg64Mcg = SeedableRng::from_seed([0; 16]);
         let mut world = World::new();
-        world.create_entity(
+        let alice = world.create_entity(
             "Alice",
             &[Recipe::new(vec![Ware::new(WareType::Food, 1)], vec![])],
         );
-        world.create_entity(
+        let bob = world.create_entity(
             "Bob",
             &[Recipe::new(vec![], vec![Ware::new(WareType::Food, 1)])],
         );
         world
-            .get_entity_mut(0)
+            .get_entity_mut(alice)
             .add_ware(Ware::new(WareType::Money, 50));
         world
-            .get_entity_mut(1)
+            .get_entity_mut(bob)
             .add_ware(Ware::new(WareType::Food, 10));
-        world.update_market_offers();
+        world.update_market_offers(&mut rng);
 
         let mut market = Market::new();
         market.create_offer(
             Ware::new(WareType::Food, 1),
             OfferType::Buy,
             Ware::new(WareType::Money, 5),
-            0,
+            alice,
         );
         market.create_offer(
             Ware::new(WareType::Food, 10),
             OfferType::Sell,
             Ware::new(WareType::Money, 5),
-            1,
+            bob,
         );
 
         assert_eq!(&market, world.market());
@@ -201,9 +336,10 @@ mod test {
 
     #[test]
     fn test_update_market_offers_no_redundant_offers() {
+        let mut rng: Pcg64Mcg = SeedableRng::from_seed([0; 16]);
         let mut world = World::new();
         let eating_recipe = Recipe::from_str(EATING_RECIPE).unwrap();
-        world.create_entity(
+        let human = world.create_entity(
             "Human",
             &[
                 eating_recipe.clone(),
@@ -211,11 +347,61 @@ mod test {
                 eating_recipe.clone(),
             ],
         );
-        world.get_entity_mut(0).add_ware(Ware::money(100));
-        world.update_market_offers();
+        world.get_entity_mut(human).add_ware(Ware::money(100));
+        world.update_market_offers(&mut rng);
         assert_eq!(world.market().offers().len(), 1);
     }
 
+    #[test]
+    fn test_storage_limits_bids_and_deliveries() {
+        let mut rng: Pcg64Mcg = SeedableRng::from_seed([0; 16]);
+        let mut world = World::new();
+        let eating_recipe = Recipe::from_str(EATING_RECIPE).unwrap();
+        let human = world.create_entity(
+            "Human",
+            &[eating_recipe.clone(), eating_recipe.clone(), eating_recipe],
+        );
+        let seller = world.create_entity("Seller", &[]);
+        let entity = world.get_entity_mut(human);
+        entity.set_storage(Some(Storage::new(Some(2))));
+        entity.add_ware(Ware::money(100));
+        assert_eq!(
+            Ware::new(WareType::Food, 1),
+            entity.add_ware_max(Ware::new(WareType::Food, 3))
+        );
+        entity.add_ware(Ware::new(WareType::Food, 2));
+        assert_eq!(2, entity.lost().ware_amount(WareType::Food));
+        world
+            .get_entity_mut(seller)
+            .add_ware(Ware::new(WareType::Food, 5));
+
+        world
+            .get_entity_mut(human)
+            .remove_ware(Ware::new(WareType::Food, 1))
+            .unwrap();
+        world.update_market_offers(&mut rng);
+        let bids: Vec<_> = world
+            .market()
+            .offers()
+            .iter()
+            .filter(|offer| offer.entity_id() == human)
+            .collect();
+        assert_eq!(1, bids.len());
+        assert_eq!(1, bids[0].amount());
+
+        let fill = |amount| {
+            Fill::new(
+                Ware::new(WareType::Food, amount),
+                Ware::money(5),
+                human,
+                seller,
+            )
+        };
+        assert_eq!(Err(()), world.settle(&fill(2)));
+        assert_eq!(Ok(()), world.settle(&fill(1)));
+        assert_eq!(0, world.get_entity(human).wares().room_for(WareType::Food));
+    }
+
     #[test]
     fn test_resolve_trades() {
         let mut rng: Pcg64Mcg = SeedableRng::from_seed([0; 16]);
@@ -259,9 +445,113 @@ mod test {
                 .set_single_price(WareType::Food, rng.sample(food_price_distribution));
         }
 
-        world.update_market_offers();
-        world.market_mut().resolve_trades();
+        world.update_market_offers(&mut rng);
+        let fills = world.resolve_trades(&mut rng);
+
+        for fill in &fills {
+            assert!(humans.contains(&fill.buyer()));
+            assert!(food_creators.contains(&fill.seller()));
+            assert!(
+                world
+                    .get_entity(fill.buyer())
+                    .wares()
+                    .ware_amount(WareType::Food)
+                    <= 1
+            );
+        }
+        let total = |ware_type| -> u32 {
+            world
+                .entities()
+                .map(|entity| entity.wares().ware_amount(ware_type))
+                .sum()
+        };
+        assert_eq!(100, total(WareType::Food));
+        assert_eq!(500, total(WareType::Money));
+        assert!(world
+            .market()
+            .offers()
+            .iter()
+            .all(|offer| offer.amount() > 0));
+    }
+
+    #[test]
+    fn test_failed_settlement_keeps_offers() {
+        let mut rng: Pcg64Mcg = SeedableRng::from_seed([0; 16]);
+        let mut world = World::new();
+        let seller = world.create_entity("Seller", &[]);
+        let broke = world.create_entity("Broke", &[]);
+        let buyer = world.create_entity("Buyer", &[]);
+        world
+            .get_entity_mut(seller)
+            .add_ware(Ware::new(WareType::Food, 5));
+        world.get_entity_mut(buyer).add_ware(Ware::money(30));
+
+        // The best bid cannot be paid, so the food goes to the next bid.
+        let market = world.market_in_mut(0);
+        let food = Ware::new(WareType::Food, 5);
+        market.create_offer(food.clone(), OfferType::Sell, Ware::money(5), seller);
+        market.create_offer(food.clone(), OfferType::Buy, Ware::money(10), broke);
+        market.create_offer(food, OfferType::Buy, Ware::money(6), buyer);
+        market.sort_offers();
+        let fills = world.resolve_trades(&mut rng);
+
+        assert_eq!(
+            vec![Fill::new(
+                Ware::new(WareType::Food, 5),
+                Ware::money(5),
+                buyer,
+                seller
+            )],
+            fills
+        );
+        assert_eq!(
+            5,
+            world.get_entity(buyer).wares().ware_amount(WareType::Food)
+        );
+        let offers = world.market().offers();
+        assert_eq!(1, offers.len());
+        assert_eq!(broke, offers[0].entity_id());
+    }
 
-        unimplemented!()
+    #[test]
+    fn test_exchange_currencies() {
+        let mut rng: Pcg64Mcg = SeedableRng::from_seed([0; 16]);
+        let mut world = World::new();
+        let alice = world.create_entity("Alice", &[]);
+        let bob = world.create_entity("Bob", &[]);
+        world
+            .get_entity_mut(alice)
+            .add_ware(Ware::new(WareType::Gold, 5));
+        world.get_entity_mut(bob).set_currency(WareType::Gold);
+        world.get_entity_mut(bob).add_ware(Ware::money(100));
+
+        world.update_market_offers(&mut rng);
+        let fills = world.resolve_trades(&mut rng);
+
+        assert_eq!(
+            vec![Fill::new(
+                Ware::new(WareType::Gold, 5),
+                Ware::money(10),
+                bob,
+                alice
+            )],
+            fills
+        );
+        assert_eq!(
+            50,
+            world.get_entity(alice).wares().ware_amount(WareType::Money)
+        );
+        assert_eq!(5, world.get_entity(bob).wares().ware_amount(WareType::Gold));
+        assert_eq!(
+            50,
+            world.get_entity(bob).wares().ware_amount(WareType::Money)
+        );
+        assert_eq!(
+            Some(10.0),
+            world
+                .market()
+                .exchange_rate(WareType::Gold, WareType::Money)
+        );
+        assert_eq!(1, world.market().offers().len());
     }
 }
@@ -0,0 +1,164 @@
+use model::{
+    region::{RegionId, Trip},
+    ware::{Ware, WareAmount},
+    world::{EntityId, World},
+};
+
+pub trait Transport {
+    fn travel(&mut self, entity_id: EntityId, destination: RegionId) -> Result<(), ()>;
+    fn arrive_travellers(&mut self) -> Vec<EntityId>;
+}
+
+impl Transport for World {
+    /// Sends the entity with all its wares along the cheapest route to the given region.
+    /// It pays the cost of the route per unit of its wares that are not currencies, in its own currency,
+    /// withdraws its offers and does not trade until it arrives.
+    /// Fails if the destination cannot be reached, the entity is already travelling, or it cannot pay,
+    /// including when the cost does not fit in a ware amount.
+    fn travel(&mut self, entity_id: EntityId, destination: RegionId) -> Result<(), ()> {
+        let tick = self.tick();
+        let entity = self.try_get_entity(entity_id).ok_or(())?;
+        if entity.is_travelling() || entity.region() == destination {
+            return Err(());
+        }
+        let region = entity.region();
+        let route = self.route(region, destination).ok_or(())?;
+
+        let entity = self.get_entity_mut(entity_id);
+        let cargo = entity
+            .wares()
+            .iter()
+            .filter(|ware| !ware.is_currency())
+            .try_fold(0, |cargo: WareAmount, ware| {
+                cargo.checked_add(ware.amount())
+            })
+            .ok_or(())?;
+        let cost = cargo.checked_mul(route.cost()).ok_or(())?;
+        let currency = entity.currency();
+        entity.remove_ware(Ware::new(currency, cost))?;
+        entity.clear_offer_ids();
+        self.market_in_mut(region).remove_offers_of(entity_id);
+
+        let entity = self.get_entity_mut(entity_id);
+        if route.duration() == 0 {
+            entity.set_region(destination);
+        } else {
+            entity.set_trip(Some(Trip::new(destination, tick + route.duration())));
+        }
+        Ok(())
+    }
+
+    /// Moves the entities whose trips end at the current tick to their destinations.
+    /// Returns the ids of the entities that arrived.
+    fn arrive_travellers(&mut self) -> Vec<EntityId> {
+        let tick = self.tick();
+        let mut arrived = Vec::new();
+        for (entity_id, entity) in self.iter_entities_mut() {
+            if let Some(trip) = entity.trip().copied().filter(|trip| trip.arrival() <= tick) {
+                entity.set_region(trip.destination());
+                entity.set_trip(None);
+                arrived.push(entity_id);
+            }
+        }
+        arrived
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{simulation::Simulation, transport::Transport};
+    use model::{
+        entity::utility::Utility,
+        region::Route,
+        ware::{Ware, WareType},
+        world::World,
+    };
+
+    #[test]
+    fn test_regional_markets() {
+        let mut world = World::new();
+        let village = world.create_region("Village");
+        let city_buyer = world.create_entity("City buyer", &[]);
+        let village_buyer = world.create_entity("Village buyer", &[]);
+        let seller = world.create_entity("Seller", &[]);
+        for &buyer in &[city_buyer, village_buyer] {
+            let entity = world.get_entity_mut(buyer);
+            entity.set_utility(Utility::CobbDouglas(vec![(WareType::Food, 1.0)]));
+            entity.add_ware(Ware::money(50));
+        }
+        world.get_entity_mut(village_buyer).set_region(village);
+        world
+            .get_entity_mut(seller)
+            .add_ware(Ware::new(WareType::Food, 5));
+        world.get_entity_mut(seller).set_region(village);
+
+        let mut simulation = Simulation::new(world, 0);
+        let fills = simulation.step();
+        let world = simulation.world();
+
+        assert!(fills.iter().all(|fill| fill.buyer() == village_buyer));
+        assert!(world
+            .market_in(village)
+            .last_price(WareType::Food, WareType::Money)
+            .is_some());
+        assert!(world
+            .market()
+            .last_price(WareType::Food, WareType::Money)
+            .is_none());
+        assert_eq!(
+            0,
+            world
+                .get_entity(city_buyer)
+                .wares()
+                .ware_amount(WareType::Food)
+        );
+    }
+
+    #[test]
+    fn test_travel() {
+        let mut world = World::new();
+        let port = world.create_region("Port");
+        let island = world.create_region("Island");
+        world.add_route(Route::new(0, port, 1, 1)).unwrap();
+        world.add_route(Route::new(port, island, 1, 2)).unwrap();
+        assert_eq!(Err(()), world.add_route(Route::new(island, 3, 1, 1)));
+        let merchant = world.create_entity("Merchant", &[]);
+        let buyer = world.create_entity("Buyer", &[]);
+        world.get_entity_mut(merchant).add_ware(Ware::money(20));
+        world
+            .get_entity_mut(merchant)
+            .add_ware(Ware::new(WareType::Food, 5));
+        let entity = world.get_entity_mut(buyer);
+        entity.set_utility(Utility::CobbDouglas(vec![(WareType::Food, 1.0)]));
+        entity.add_ware(Ware::money(50));
+        entity.set_region(island);
+
+        assert_eq!(Err(()), world.travel(merchant, 0));
+        assert_eq!(Err(()), world.travel(buyer, 0));
+        // The cost of a cargo this large does not fit in a ware amount.
+        let hauler = world.create_entity("Hauler", &[]);
+        world
+            .get_entity_mut(hauler)
+            .add_ware(Ware::new(WareType::Food, u32::MAX / 2 + 1));
+        assert_eq!(Err(()), world.travel(hauler, island));
+        world.travel(merchant, island).unwrap();
+        assert_eq!(
+            10,
+            world
+                .get_entity(merchant)
+                .wares()
+                .ware_amount(WareType::Money)
+        );
+
+        let mut simulation = Simulation::new(world, 0);
+        for _ in 0..3 {
+            assert!(simulation.step().is_empty());
+        }
+        assert!(simulation.world().get_entity(merchant).is_travelling());
+
+        let fills = simulation.step();
+        assert_eq!(island, simulation.world().get_entity(merchant).region());
+        assert!(!fills.is_empty());
+        assert!(fills.iter().all(|fill| fill.seller() == merchant));
+    }
+}
@@ -0,0 +1,196 @@
+/// An index into an arena.
+/// Each slot of an arena counts how often it was reused, such that indices to removed values can be detected.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Index {
+    index: u32,
+    generation: u32,
+}
+
+impl Index {
+    pub fn new(index: u32, generation: u32) -> Self {
+        Self { index, generation }
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+}
+
+/// A collection that hands out stable indices for its values.
+/// Removing a value keeps the indices of all other values valid,
+/// and an index to a removed value never refers to a value inserted later.
+///
+/// # Example
+///
+/// ```
+/// use model::arena::Arena;
+///
+/// let mut arena = Arena::new();
+/// let a = arena.insert("a");
+/// let b = arena.insert("b");
+/// assert_eq!(Some("a"), arena.remove(a));
+///
+/// let c = arena.insert("c");
+/// assert_eq!(a.index(), c.index());
+/// assert_eq!(None, arena.get(a));
+/// assert_eq!(Some(&"b"), arena.get(b));
+/// assert_eq!(Some(&"c"), arena.get(c));
+/// assert_eq!(vec![(c, &"c"), (b, &"b")], arena.iter().collect::<Vec<_>>());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Arena<T> {
+    slots: Vec<Slot<T>>,
+    free_slots: Vec<u32>,
+    len: usize,
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+            len: 0,
+        }
+    }
+}
+
+// Creators
+impl<T> Arena<T> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+// Modifiers
+impl<T> Arena<T> {
+    pub fn insert(&mut self, value: T) -> Index {
+        self.len += 1;
+
+        if let Some(index) = self.free_slots.pop() {
+            let slot = &mut self.slots[index as usize];
+            slot.value = Some(value);
+            Index::new(index, slot.generation)
+        } else {
+            self.slots.push(Slot {
+                generation: 0,
+                value: Some(value),
+            });
+            Index::new(self.slots.len() as u32 - 1, 0)
+        }
+    }
+
+    /// Removes the value at the given index.
+    /// Returns None if the index does not refer to a value in this arena.
+    pub fn remove(&mut self, index: Index) -> Option<T> {
+        let slot = self.slots.get_mut(index.index() as usize)?;
+        if slot.generation != index.generation() {
+            return None;
+        }
+
+        let value = slot.value.take()?;
+        slot.generation += 1;
+        self.free_slots.push(index.index());
+        self.len -= 1;
+        Some(value)
+    }
+
+    pub fn get_mut(&mut self, index: Index) -> Option<&mut T> {
+        match self.slots.get_mut(index.index() as usize) {
+            Some(slot) if slot.generation == index.generation() => slot.value.as_mut(),
+            _ => None,
+        }
+    }
+
+    pub fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = (Index, &'a mut T)> + 'a {
+        self.slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                let generation = slot.generation;
+                Some((Index::new(index as u32, generation), slot.value.as_mut()?))
+            })
+    }
+}
+
+// Getters
+impl<T> Arena<T> {
+    pub fn get(&self, index: Index) -> Option<&T> {
+        match self.slots.get(index.index() as usize) {
+            Some(slot) if slot.generation == index.generation() => slot.value.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn contains(&self, index: Index) -> bool {
+        self.get(index).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = (Index, &'a T)> + 'a {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            Some((
+                Index::new(index as u32, slot.generation),
+                slot.value.as_ref()?,
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::arena::{Arena, Index};
+
+    #[test]
+    fn test_stale_indices() {
+        let mut arena = Arena::new();
+        let first = arena.insert("First");
+        arena.insert("Second");
+        assert_eq!(Some("First"), arena.remove(first));
+
+        let third = arena.insert("Third");
+        assert_eq!(first.index(), third.index());
+        assert_eq!(first.generation() + 1, third.generation());
+        assert!(!arena.contains(first));
+        assert_eq!(None, arena.remove(first));
+        assert_eq!(None, arena.get_mut(first));
+        assert_eq!(Some(&"Third"), arena.get(third));
+        assert_eq!(None, arena.get(Index::new(5, 0)));
+    }
+
+    #[test]
+    fn test_len_and_iter_mut() {
+        let mut arena = Arena::new();
+        let indices: Vec<_> = (0..3).map(|i| arena.insert(i)).collect();
+        arena.remove(indices[1]);
+        assert_eq!(2, arena.len());
+
+        for (_, value) in arena.iter_mut() {
+            *value *= 10;
+        }
+        assert_eq!(
+            vec![(indices[0], &0), (indices[2], &20)],
+            arena.iter().collect::<Vec<_>>()
+        );
+
+        arena.remove(indices[0]);
+        arena.remove(indices[2]);
+        assert!(arena.is_empty());
+    }
+}
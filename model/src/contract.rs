@@ -0,0 +1,143 @@
+use crate::{
+    ware::Ware,
+    world::{EntityId, Tick},
+};
+
+pub type ContractId = usize;
+
+/// A recurring agreement under which the supplier delivers the ware to the customer every tick
+/// from the start tick until before the end tick, and the customer pays the price per ware on delivery.
+/// The party that fails to deliver or to pay owes the penalty to the other for each breach.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SupplyContract {
+    supplier: EntityId,
+    customer: EntityId,
+    ware: Ware,
+    price_per_ware: Ware,
+    start: Tick,
+    end: Tick,
+    penalty: Ware,
+    deliveries: u32,
+    breaches: u32,
+}
+
+/// The supply contracts of a world. Contracts keep their ids after they end.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContractRegistry {
+    contracts: Vec<SupplyContract>,
+}
+
+impl SupplyContract {
+    pub fn new(
+        supplier: EntityId,
+        customer: EntityId,
+        ware: Ware,
+        price_per_ware: Ware,
+        start: Tick,
+        end: Tick,
+        penalty: Ware,
+    ) -> Self {
+        debug_assert!(price_per_ware.is_currency() && penalty.is_currency());
+
+        Self {
+            supplier,
+            customer,
+            ware,
+            price_per_ware,
+            start,
+            end,
+            penalty,
+            deliveries: 0,
+            breaches: 0,
+        }
+    }
+
+    pub fn record_delivery(&mut self) {
+        self.deliveries += 1;
+    }
+
+    pub fn record_breach(&mut self) {
+        self.breaches += 1;
+    }
+
+    pub fn supplier(&self) -> EntityId {
+        self.supplier
+    }
+
+    pub fn customer(&self) -> EntityId {
+        self.customer
+    }
+
+    /// The ware delivered every tick.
+    pub fn ware(&self) -> &Ware {
+        &self.ware
+    }
+
+    pub fn price_per_ware(&self) -> &Ware {
+        &self.price_per_ware
+    }
+
+    pub fn start(&self) -> Tick {
+        self.start
+    }
+
+    /// The first tick without a delivery.
+    pub fn end(&self) -> Tick {
+        self.end
+    }
+
+    pub fn penalty(&self) -> &Ware {
+        &self.penalty
+    }
+
+    pub fn deliveries(&self) -> u32 {
+        self.deliveries
+    }
+
+    pub fn breaches(&self) -> u32 {
+        self.breaches
+    }
+
+    /// True if a delivery is due at the given tick.
+    pub fn is_active(&self, tick: Tick) -> bool {
+        self.start <= tick && tick < self.end
+    }
+}
+
+// Creators
+impl ContractRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn create_contract(&mut self, contract: SupplyContract) -> ContractId {
+        self.contracts.push(contract);
+        self.contracts.len() - 1
+    }
+}
+
+// Modifiers
+impl ContractRegistry {
+    pub fn get_mut(&mut self, contract_id: ContractId) -> Option<&mut SupplyContract> {
+        self.contracts.get_mut(contract_id)
+    }
+}
+
+// Getters
+impl ContractRegistry {
+    pub fn get(&self, contract_id: ContractId) -> Option<&SupplyContract> {
+        self.contracts.get(contract_id)
+    }
+
+    pub fn contracts(&self) -> &[SupplyContract] {
+        &self.contracts
+    }
+
+    /// Iterates over the contracts with a delivery due at the given tick, together with their ids.
+    pub fn active(&self, tick: Tick) -> impl Iterator<Item = (ContractId, &SupplyContract)> {
+        self.contracts
+            .iter()
+            .enumerate()
+            .filter(move |(_, contract)| contract.is_active(tick))
+    }
+}
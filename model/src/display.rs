@@ -1,9 +1,18 @@
 use crate::{
-    entity::{recipe::Recipe, Entity},
+    arena::Index,
+    entity::{
+        batch::Batch,
+        recipe::{CapitalInput, Outcome, Output, Recipe, Yield},
+        Entity,
+    },
+    event::Event,
     market::{
+        fill::Fill,
         offer::{Offer, OfferType},
         Market,
     },
+    resource::ResourceStock,
+    shock::{ScheduledShock, Shock, Trigger},
     ware::{Ware, WareStore, WareType},
     world::World,
 };
@@ -11,35 +20,156 @@ use std::fmt::{Debug, Display, Error, Formatter};
 
 impl Display for World {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        write!(f, "World")?;
+        write!(f, "World at tick {}", self.tick())?;
         for entity in self.entities() {
             write!(f, "\n{}", entity)?;
         }
-        write!(f, "\n{}", self.market())
+        for resource in self.resources() {
+            write!(f, "\n{}", resource)?;
+        }
+        for region in self.regions() {
+            write!(f, "\nRegion {}:", region.name())?;
+            for resource in region.resources() {
+                write!(f, "\n{}", resource)?;
+            }
+            write!(f, "\n{}", region.market())?;
+        }
+        Ok(())
     }
 }
 
 impl Display for Entity {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        write!(f, "{:.12}: Recipes:", self.name())?;
+        write!(f, "{:.12}:", self.name())?;
+        if self.is_bankrupt() {
+            write!(f, " Bankrupt;")?;
+        }
+        if let Some(health) = self.health() {
+            write!(f, " Health {}/{};", health.health(), health.max_health())?;
+        }
+        write!(f, " Recipes:")?;
         for recipe in self.recipes() {
             write!(f, " {}", recipe)?;
         }
-        write!(f, "; ||| Wares n (b/s):")?;
+        if !self.batches().is_empty() {
+            write!(f, "; Batches:")?;
+            for batch in self.batches() {
+                write!(f, " {}", batch)?;
+            }
+        }
+        write!(f, "; ||| Wares n (b/s in {}):", self.currency())?;
         for ware_type in self.wares().iter_ware_types() {
             write!(
                 f,
                 " {:?} {} ({}/{})",
                 ware_type,
                 self.wares().ware_amount(ware_type),
-                self.buy_prices().single_price(ware_type),
-                self.sell_prices().single_price(ware_type)
+                self.buy_prices()
+                    .single_price_in(ware_type, self.currency()),
+                self.sell_prices()
+                    .single_price_in(ware_type, self.currency())
             )?;
         }
         Ok(())
     }
 }
 
+impl Display for Index {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "#{}", self.index())?;
+        if self.generation() > 0 {
+            write!(f, "v{}", self.generation())?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            Event::Bankruptcy(entity_id) => write!(f, "Entity {} went bankrupt", entity_id),
+            Event::ForwardDefault {
+                entity_id,
+                counterparty,
+                forfeited,
+            } => write!(
+                f,
+                "Entity {} defaulted on a forward contract with entity {} and forfeited {}",
+                entity_id, counterparty, forfeited
+            ),
+            Event::Breach {
+                contract_id,
+                entity_id,
+            } => write!(f, "Entity {} breached contract {}", entity_id, contract_id),
+            Event::Shock(shock) => write!(f, "Shock: {}", shock),
+            Event::Liquidation {
+                entity_id,
+                payments,
+                closed,
+            } => {
+                write!(f, "Entity {} was liquidated, paying", entity_id)?;
+                for (creditor, payment) in payments {
+                    match creditor {
+                        Some(creditor) => write!(f, " {} to {},", payment, creditor)?,
+                        None => write!(f, " {},", payment)?,
+                    }
+                }
+                if *closed {
+                    write!(f, " and closed")
+                } else {
+                    write!(f, " and restructured")
+                }
+            }
+        }
+    }
+}
+
+impl Display for ResourceStock {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(
+            f,
+            "{} stock: {:.1}/{:.1}, {} extracted",
+            self.ware_type(),
+            self.amount(),
+            self.capacity(),
+            self.extracted()
+        )
+    }
+}
+
+impl Display for Shock {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            Shock::Output(ware_type, factor) => {
+                write!(f, "output {} {}%", ware_type, factor * 100.0)
+            }
+            Shock::Disable(recipe) => write!(f, "disable {}", recipe),
+            Shock::Cull(share) => write!(f, "cull {}%", share * 100.0),
+            Shock::Windfall(ware) => write!(f, "windfall {}", ware),
+        }
+    }
+}
+
+impl Display for Trigger {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            Trigger::At(tick) => write!(f, "at {}", tick),
+            Trigger::Chance(chance) => write!(f, "{}%", chance * 100.0),
+        }
+    }
+}
+
+impl Display for ScheduledShock {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{}: {}", self.trigger(), self.shock())?;
+        match self.duration() {
+            None => Ok(()),
+            Some(1) => write!(f, " for 1 tick"),
+            Some(duration) => write!(f, " for {} ticks", duration),
+        }
+    }
+}
+
 impl Display for Market {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         write!(f, "Market")?;
@@ -60,6 +190,19 @@ impl Display for Offer {
     }
 }
 
+impl Display for Fill {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(
+            f,
+            "{} from {} to {} at {}/unit",
+            self.ware(),
+            self.seller(),
+            self.buyer(),
+            self.price_per_ware()
+        )
+    }
+}
+
 impl Display for WareType {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         Debug::fmt(self, f)
@@ -68,6 +211,9 @@ impl Display for WareType {
 
 impl Display for Recipe {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if self.is_required() {
+            write!(f, "!")?;
+        }
         write!(f, "(")?;
         let mut once = false;
         for input in self.inputs() {
@@ -78,7 +224,40 @@ impl Display for Recipe {
             }
             write!(f, "{}", input)?;
         }
-        write!(f, ") -> (")?;
+        for capital_input in self.capital_inputs() {
+            if once {
+                write!(f, "; ")?;
+            } else {
+                once = true;
+            }
+            write!(f, "{}", capital_input)?;
+        }
+        write!(f, ") -> ")?;
+        match self.outcomes() {
+            [outcome] if outcome.weight() == 1 => write!(f, "{}", outcome)?,
+            outcomes => {
+                let mut once = false;
+                for outcome in outcomes {
+                    if once {
+                        write!(f, " | ")?;
+                    } else {
+                        once = true;
+                    }
+                    write!(f, "{}:{}", outcome.weight(), outcome)?;
+                }
+            }
+        }
+        match self.duration() {
+            0 => Ok(()),
+            1 => write!(f, " in 1 tick"),
+            duration => write!(f, " in {} ticks", duration),
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "(")?;
         let mut once = false;
         for output in self.outputs() {
             if once {
@@ -92,6 +271,45 @@ impl Display for Recipe {
     }
 }
 
+impl Display for Output {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self.amount() {
+            Yield::Fixed(amount) => write!(f, "{}x {}", amount, self.ware_type()),
+            Yield::Chance(amount, percent) => {
+                write!(f, "{}x {} {}%", amount, self.ware_type(), percent)
+            }
+            Yield::Uniform(min, max) => write!(f, "{}-{}x {}", min, max, self.ware_type()),
+            Yield::Poisson(mean) => write!(f, "~{}x {}", mean, self.ware_type()),
+        }
+    }
+}
+
+impl Display for CapitalInput {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "@{}", self.ware())?;
+        if let Some(lifetime) = self.lifetime() {
+            write!(f, "/{}", lifetime)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Batch {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "(")?;
+        let mut once = false;
+        for output in self.outputs() {
+            if once {
+                write!(f, "; ")?;
+            } else {
+                once = true;
+            }
+            write!(f, "{}", output)?;
+        }
+        write!(f, ") at tick {}", self.completion())
+    }
+}
+
 impl Display for Ware {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         write!(f, "{}x {}", self.amount(), self.ware_type())
@@ -112,3 +330,30 @@ impl Display for WareStore {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        entity::batch::Batch,
+        ware::{Ware, WareType},
+        world::World,
+    };
+
+    #[test]
+    fn test_display_batches() {
+        let mut world = World::new();
+        let entity_id = world.create_entity("Farmer", &[]);
+        let entity = world.get_entity_mut(entity_id);
+        assert!(!entity.to_string().contains("Batches"));
+
+        entity.start_batch(Batch::new(vec![Ware::new(WareType::Food, 2)], 3));
+        entity.start_batch(Batch::new(
+            vec![Ware::new(WareType::Food, 1), Ware::new(WareType::Water, 1)],
+            4,
+        ));
+        assert!(entity
+            .to_string()
+            .contains("; Batches: (2x Food) at tick 3 (1x Food; 1x Water) at tick 4;"));
+        assert!(world.to_string().contains("Batches: (2x Food) at tick 3"));
+    }
+}
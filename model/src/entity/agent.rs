@@ -0,0 +1,50 @@
+use crate::{
+    entity::Entity,
+    market::{fill::Fill, offer::OfferType, Market},
+    ware::Ware,
+};
+use rand::RngCore;
+use std::{any::Any, fmt::Debug};
+
+/// The strategy by which an entity trades, like a market maker, a merchant or a liquidation.
+/// The simulation takes the agent out of its entity while calling it, so the agent sees the entity without itself.
+pub trait Agent: Any + Debug + Send {
+    /// Called every tick with the market of the region of the entity, before the agent decides its offers.
+    fn observe_market(&mut self, _entity: &Entity, _market: &Market) {}
+
+    /// Returns the offers of the entity for this tick as triples of the ware, the offer type and the price per ware.
+    fn decide_offers(
+        &mut self,
+        entity: &Entity,
+        market: &Market,
+        rng: &mut dyn RngCore,
+    ) -> Vec<(Ware, OfferType, Ware)>;
+
+    /// Returns the indices of the recipes of the entity to run in this tick. Required recipes that are left out starve.
+    fn decide_production(&mut self, entity: &Entity) -> Vec<usize> {
+        (0..entity.recipes().len()).collect()
+    }
+
+    /// Called after a trade of the entity was settled.
+    fn on_fill(&mut self, _entity: &Entity, _fill: &Fill) {}
+
+    /// Clones the agent, such that entities with agents can be cloned.
+    fn box_clone(&self) -> Box<dyn Agent>;
+}
+
+impl dyn Agent {
+    /// The agent as its concrete type, or None if it has another type.
+    pub fn downcast_ref<T: Agent>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref()
+    }
+
+    pub fn downcast_mut<T: Agent>(&mut self) -> Option<&mut T> {
+        (self as &mut dyn Any).downcast_mut()
+    }
+}
+
+impl Clone for Box<dyn Agent> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
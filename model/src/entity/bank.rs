@@ -0,0 +1,325 @@
+use crate::{
+    ware::{Ware, WareAmount},
+    world::{EntityId, Tick},
+};
+use std::collections::HashMap;
+
+/// An entity that takes deposits and grants loans and credit lines in its own currency.
+/// Rates are per tick.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Bank {
+    deposit_rate: f64,
+    loan_rate: f64,
+    loan_to_value: f64,
+    credit_ratio: f64,
+    spread: Option<f64>,
+    deposits: HashMap<EntityId, Account>,
+    loans: Vec<Loan>,
+}
+
+/// A balance that earns or owes interest per tick.
+/// Fractions of interest are carried over until they add up to a whole unit.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Account {
+    balance: WareAmount,
+    accrued: f64,
+}
+
+/// A loan of a bank to a borrower, repaid in equal installments.
+/// A loan secured by collateral is paid before unsecured liabilities in a liquidation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Loan {
+    borrower: EntityId,
+    principal: Ware,
+    rate: f64,
+    schedule: Vec<(Tick, WareAmount)>,
+    collateral: Option<Ware>,
+    outstanding: Account,
+}
+
+/// The right of an entity to borrow from a bank up to a limit whenever it lacks cash to pay for a trade.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CreditLine {
+    bank: EntityId,
+    limit: Ware,
+    rate: f64,
+    drawn: Account,
+}
+
+impl Bank {
+    /// Creates a bank with the given interest rates per tick.
+    /// Secured loans are granted up to the value of the collateral times `loan_to_value`,
+    /// and unsecured loans and credit lines up to the credit score of the borrower times `credit_ratio`.
+    pub fn new(deposit_rate: f64, loan_rate: f64, loan_to_value: f64, credit_ratio: f64) -> Self {
+        Self {
+            deposit_rate,
+            loan_rate,
+            loan_to_value,
+            credit_ratio,
+            spread: None,
+            deposits: HashMap::new(),
+            loans: Vec::new(),
+        }
+    }
+
+    /// Makes the rates of this bank follow the policy rate of the central bank:
+    /// deposits earn the policy rate, and loans cost the given spread above it.
+    pub fn set_spread(&mut self, spread: Option<f64>) {
+        self.spread = spread;
+    }
+
+    /// Updates the rates of this bank to the given policy rate, if it follows the policy rate.
+    /// Loans that were granted before keep their rate.
+    pub fn follow_policy_rate(&mut self, policy_rate: f64) {
+        if let Some(spread) = self.spread {
+            self.deposit_rate = policy_rate;
+            self.loan_rate = policy_rate + spread;
+        }
+    }
+
+    pub fn deposit(&mut self, depositor: EntityId, amount: WareAmount) {
+        self.deposits.entry(depositor).or_default().balance += amount;
+    }
+
+    pub fn withdraw(&mut self, depositor: EntityId, amount: WareAmount) -> Result<(), ()> {
+        let account = self.deposits.get_mut(&depositor).ok_or(())?;
+        account.balance = account.balance.checked_sub(amount).ok_or(())?;
+        Ok(())
+    }
+
+    /// Credits the interest of this tick to all deposits.
+    pub fn pay_deposit_interest(&mut self) {
+        let rate = self.deposit_rate;
+        for account in self.deposits.values_mut() {
+            let interest = account.accrue(rate);
+            account.balance += interest;
+        }
+    }
+
+    /// Closes all deposits and returns the balances owed to the depositors, ordered by depositor.
+    pub fn take_deposits(&mut self) -> Vec<(EntityId, WareAmount)> {
+        let mut deposits: Vec<_> = self
+            .deposits
+            .drain()
+            .map(|(depositor, account)| (depositor, account.balance))
+            .collect();
+        deposits.sort();
+        deposits
+    }
+
+    pub fn add_loan(&mut self, loan: Loan) {
+        self.loans.push(loan);
+    }
+
+    pub fn loans_mut(&mut self) -> &mut Vec<Loan> {
+        &mut self.loans
+    }
+
+    pub fn deposit_rate(&self) -> f64 {
+        self.deposit_rate
+    }
+
+    pub fn loan_rate(&self) -> f64 {
+        self.loan_rate
+    }
+
+    pub fn spread(&self) -> Option<f64> {
+        self.spread
+    }
+
+    pub fn loan_to_value(&self) -> f64 {
+        self.loan_to_value
+    }
+
+    pub fn credit_ratio(&self) -> f64 {
+        self.credit_ratio
+    }
+
+    pub fn deposit_balance(&self, depositor: EntityId) -> WareAmount {
+        self.deposits
+            .get(&depositor)
+            .map_or(0, |account| account.balance)
+    }
+
+    /// The sum of all deposits, which the bank owes to its depositors.
+    pub fn total_deposits(&self) -> WareAmount {
+        self.deposits.values().map(|account| account.balance).sum()
+    }
+
+    pub fn loans(&self) -> &[Loan] {
+        &self.loans
+    }
+}
+
+impl Account {
+    /// Accrues the interest of one tick at the given rate and returns its whole units.
+    pub fn accrue(&mut self, rate: f64) -> WareAmount {
+        self.accrued += f64::from(self.balance) * rate;
+        let interest = self.accrued.floor();
+        self.accrued -= interest;
+        interest as WareAmount
+    }
+
+    pub fn balance(&self) -> WareAmount {
+        self.balance
+    }
+}
+
+impl Loan {
+    /// Creates a loan that is repaid in the given number of equal installments,
+    /// one every `period` ticks after the start.
+    /// Any remainder of the principal is added to the last installment.
+    pub fn new(
+        borrower: EntityId,
+        principal: Ware,
+        rate: f64,
+        start: Tick,
+        period: Tick,
+        installments: u32,
+        collateral: Option<Ware>,
+    ) -> Self {
+        debug_assert!(principal.is_currency());
+        debug_assert!(period > 0 && installments > 0);
+
+        let installment = principal.amount() / installments;
+        let schedule = (1..=installments)
+            .map(|i| {
+                let amount = if i == installments {
+                    principal.amount() - installment * (installments - 1)
+                } else {
+                    installment
+                };
+                (start + period * Tick::from(i), amount)
+            })
+            .collect();
+        Self {
+            borrower,
+            outstanding: Account {
+                balance: principal.amount(),
+                accrued: 0.0,
+            },
+            principal,
+            rate,
+            schedule,
+            collateral,
+        }
+    }
+
+    /// Accrues the interest of one tick and returns its whole units.
+    pub fn accrue_interest(&mut self) -> WareAmount {
+        let rate = self.rate;
+        self.outstanding.accrue(rate)
+    }
+
+    /// Removes the installments due at the given tick from the schedule and returns their sum.
+    pub fn take_due_installments(&mut self, tick: Tick) -> WareAmount {
+        let due: WareAmount = self
+            .schedule
+            .iter()
+            .filter(|(due, _)| *due <= tick)
+            .map(|(_, amount)| amount)
+            .sum();
+        self.schedule.retain(|(due, _)| *due > tick);
+        self.outstanding.balance -= due;
+        due
+    }
+
+    /// Removes all installments from the schedule and returns the outstanding principal.
+    pub fn accelerate(&mut self) -> WareAmount {
+        self.schedule.clear();
+        std::mem::take(&mut self.outstanding.balance)
+    }
+
+    pub fn borrower(&self) -> EntityId {
+        self.borrower
+    }
+
+    pub fn principal(&self) -> &Ware {
+        &self.principal
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// The remaining installments as pairs of the tick they are due and the amount.
+    pub fn schedule(&self) -> &[(Tick, WareAmount)] {
+        &self.schedule
+    }
+
+    pub fn collateral(&self) -> Option<&Ware> {
+        self.collateral.as_ref()
+    }
+
+    pub fn is_secured(&self) -> bool {
+        self.collateral.is_some()
+    }
+
+    /// The principal that was not repaid yet.
+    pub fn outstanding(&self) -> WareAmount {
+        self.outstanding.balance
+    }
+
+    pub fn is_repaid(&self) -> bool {
+        self.outstanding.balance == 0
+    }
+}
+
+impl CreditLine {
+    pub fn new(bank: EntityId, limit: Ware, rate: f64) -> Self {
+        debug_assert!(limit.is_currency());
+
+        Self {
+            bank,
+            limit,
+            rate,
+            drawn: Account::default(),
+        }
+    }
+
+    pub fn draw(&mut self, amount: WareAmount) -> Result<(), ()> {
+        if amount > self.available() {
+            return Err(());
+        }
+
+        self.drawn.balance += amount;
+        Ok(())
+    }
+
+    pub fn set_rate(&mut self, rate: f64) {
+        self.rate = rate;
+    }
+
+    pub fn repay(&mut self, amount: WareAmount) {
+        debug_assert!(amount <= self.drawn.balance);
+
+        self.drawn.balance -= amount;
+    }
+
+    /// Accrues the interest of one tick on the drawn amount and returns its whole units.
+    pub fn accrue_interest(&mut self) -> WareAmount {
+        let rate = self.rate;
+        self.drawn.accrue(rate)
+    }
+
+    pub fn bank(&self) -> EntityId {
+        self.bank
+    }
+
+    pub fn limit(&self) -> &Ware {
+        &self.limit
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn drawn(&self) -> WareAmount {
+        self.drawn.balance
+    }
+
+    /// The amount that can still be drawn.
+    pub fn available(&self) -> WareAmount {
+        self.limit.amount() - self.drawn.balance
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{ware::Ware, world::Tick};
+
+/// A run of a recipe whose inputs were consumed, but whose outputs were not delivered yet.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Batch {
+    outputs: Vec<Ware>,
+    completion: Tick,
+}
+
+impl Batch {
+    pub fn new(outputs: Vec<Ware>, completion: Tick) -> Self {
+        Self {
+            outputs,
+            completion,
+        }
+    }
+
+    pub fn outputs(&self) -> &[Ware] {
+        &self.outputs
+    }
+
+    /// The tick at which the outputs of this batch are delivered.
+    pub fn completion(&self) -> Tick {
+        self.completion
+    }
+
+    pub fn is_complete(&self, tick: Tick) -> bool {
+        self.completion <= tick
+    }
+}
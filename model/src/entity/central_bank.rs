@@ -0,0 +1,163 @@
+use crate::{
+    market::offer::OfferType,
+    ware::{Ware, WareAmount},
+};
+
+/// An entity that creates the money it spends and destroys the money it receives.
+/// It sets the policy rate that the rates of commercial banks follow.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CentralBank {
+    policy_rate: f64,
+    rule: Option<TaylorRule>,
+    operations: Vec<OpenMarketOperation>,
+    created: WareAmount,
+    destroyed: WareAmount,
+}
+
+/// A rule that sets the policy rate from the inflation of the price index and the unemployment rate:
+/// `neutral_rate + inflation + inflation_weight * (inflation - target_inflation)
+/// + unemployment_weight * (natural_unemployment - unemployment)`, but at least `min_rate`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaylorRule {
+    neutral_rate: f64,
+    target_inflation: f64,
+    inflation_weight: f64,
+    natural_unemployment: f64,
+    unemployment_weight: f64,
+    min_rate: f64,
+}
+
+/// An offer of the central bank to buy or sell wares on the market in the next tick.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpenMarketOperation {
+    ware: Ware,
+    offer_type: OfferType,
+    price_per_ware: Ware,
+}
+
+impl CentralBank {
+    pub fn new(policy_rate: f64) -> Self {
+        Self {
+            policy_rate,
+            ..Default::default()
+        }
+    }
+
+    pub fn set_policy_rate(&mut self, policy_rate: f64) {
+        self.policy_rate = policy_rate;
+    }
+
+    pub fn set_rule(&mut self, rule: Option<TaylorRule>) {
+        self.rule = rule;
+    }
+
+    pub fn add_operation(&mut self, operation: OpenMarketOperation) {
+        self.operations.push(operation);
+    }
+
+    pub fn take_operations(&mut self) -> Vec<OpenMarketOperation> {
+        std::mem::take(&mut self.operations)
+    }
+
+    pub fn record_created(&mut self, amount: WareAmount) {
+        self.created += amount;
+    }
+
+    pub fn record_destroyed(&mut self, amount: WareAmount) {
+        self.destroyed += amount;
+    }
+
+    pub fn policy_rate(&self) -> f64 {
+        self.policy_rate
+    }
+
+    pub fn rule(&self) -> Option<&TaylorRule> {
+        self.rule.as_ref()
+    }
+
+    pub fn operations(&self) -> &[OpenMarketOperation] {
+        &self.operations
+    }
+
+    /// The money created by this central bank so far.
+    pub fn created(&self) -> WareAmount {
+        self.created
+    }
+
+    /// The money destroyed by this central bank so far.
+    pub fn destroyed(&self) -> WareAmount {
+        self.destroyed
+    }
+
+    /// The money this central bank added to circulation so far, negative if it withdrew money.
+    pub fn net_issuance(&self) -> i64 {
+        i64::from(self.created) - i64::from(self.destroyed)
+    }
+}
+
+impl TaylorRule {
+    pub fn new(
+        neutral_rate: f64,
+        target_inflation: f64,
+        inflation_weight: f64,
+        natural_unemployment: f64,
+        unemployment_weight: f64,
+    ) -> Self {
+        Self {
+            neutral_rate,
+            target_inflation,
+            inflation_weight,
+            natural_unemployment,
+            unemployment_weight,
+            min_rate: 0.0,
+        }
+    }
+
+    pub fn set_min_rate(&mut self, min_rate: f64) {
+        self.min_rate = min_rate;
+    }
+
+    /// The policy rate for the given inflation and unemployment rate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use model::entity::central_bank::TaylorRule;
+    ///
+    /// let rule = TaylorRule::new(0.01, 0.02, 0.5, 0.05, 0.5);
+    /// assert!((rule.rate(0.02, 0.05) - 0.03).abs() < 1e-9);
+    /// assert!((rule.rate(0.04, 0.05) - 0.06).abs() < 1e-9);
+    /// assert_eq!(0.0, rule.rate(-0.1, 0.05));
+    /// ```
+    pub fn rate(&self, inflation: f64, unemployment: f64) -> f64 {
+        let rate = self.neutral_rate
+            + inflation
+            + self.inflation_weight * (inflation - self.target_inflation)
+            + self.unemployment_weight * (self.natural_unemployment - unemployment);
+        rate.max(self.min_rate)
+    }
+}
+
+impl OpenMarketOperation {
+    pub fn new(ware: Ware, offer_type: OfferType, price_per_ware: Ware) -> Self {
+        debug_assert!(price_per_ware.is_currency());
+
+        Self {
+            ware,
+            offer_type,
+            price_per_ware,
+        }
+    }
+
+    pub fn ware(&self) -> &Ware {
+        &self.ware
+    }
+
+    pub fn offer_type(&self) -> OfferType {
+        self.offer_type
+    }
+
+    pub fn price_per_ware(&self) -> &Ware {
+        &self.price_per_ware
+    }
+}
@@ -0,0 +1,248 @@
+use crate::{
+    ware::{Ware, WareAmount},
+    world::Tick,
+};
+
+/// An entity that levies taxes in its own currency and spends the revenue on purchases and transfers.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Government {
+    taxes: Taxes,
+    scheduled_taxes: Vec<(Tick, Taxes)>,
+    transfers: Vec<Transfer>,
+    purchases: Vec<(Ware, Ware)>,
+    stats: FiscalStats,
+}
+
+/// The tax rates of a government. All taxes are levied per tick.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Taxes {
+    sales_tax: f64,
+    income_tax: Vec<(WareAmount, f64)>,
+    wealth_tax: f64,
+    head_tax: WareAmount,
+}
+
+/// A payment of a government to each eligible entity per tick.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Transfer {
+    /// Paid to every household.
+    BasicIncome(WareAmount),
+    /// Paid to every worker that sold none of its labor.
+    UnemploymentBenefit(WareAmount),
+}
+
+/// The revenue and spending of a government in the last tick.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FiscalStats {
+    sales_tax: WareAmount,
+    income_tax: WareAmount,
+    wealth_tax: WareAmount,
+    head_tax: WareAmount,
+    transfers: WareAmount,
+    purchases: WareAmount,
+}
+
+impl Government {
+    pub fn new(taxes: Taxes) -> Self {
+        Self {
+            taxes,
+            ..Default::default()
+        }
+    }
+
+    /// Changes the tax rates from now on.
+    pub fn set_taxes(&mut self, taxes: Taxes) {
+        self.taxes = taxes;
+    }
+
+    /// Changes the tax rates when the given tick is reached.
+    pub fn schedule_taxes(&mut self, tick: Tick, taxes: Taxes) {
+        self.scheduled_taxes.push((tick, taxes));
+        self.scheduled_taxes.sort_by_key(|(tick, _)| *tick);
+    }
+
+    /// Applies the tax changes that were scheduled up to the given tick.
+    pub fn apply_scheduled_taxes(&mut self, tick: Tick) {
+        while self
+            .scheduled_taxes
+            .first()
+            .is_some_and(|(scheduled, _)| *scheduled <= tick)
+        {
+            self.taxes = self.scheduled_taxes.remove(0).1;
+        }
+    }
+
+    pub fn add_transfer(&mut self, transfer: Transfer) {
+        self.transfers.push(transfer);
+    }
+
+    pub fn clear_transfers(&mut self) {
+        self.transfers.clear();
+    }
+
+    /// Adds a ware the government offers to buy every tick at the given price per ware.
+    pub fn add_purchase(&mut self, ware: Ware, price_per_ware: Ware) {
+        debug_assert!(price_per_ware.is_currency());
+
+        self.purchases.push((ware, price_per_ware));
+    }
+
+    pub fn clear_purchases(&mut self) {
+        self.purchases.clear();
+    }
+
+    pub fn stats_mut(&mut self) -> &mut FiscalStats {
+        &mut self.stats
+    }
+
+    pub fn taxes(&self) -> &Taxes {
+        &self.taxes
+    }
+
+    pub fn scheduled_taxes(&self) -> &[(Tick, Taxes)] {
+        &self.scheduled_taxes
+    }
+
+    pub fn transfers(&self) -> &[Transfer] {
+        &self.transfers
+    }
+
+    /// The wares the government offers to buy every tick, as pairs of the ware and the price per ware.
+    pub fn purchases(&self) -> &[(Ware, Ware)] {
+        &self.purchases
+    }
+
+    pub fn stats(&self) -> &FiscalStats {
+        &self.stats
+    }
+}
+
+impl Taxes {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the share of the total price of each trade that the buyer pays in addition.
+    pub fn set_sales_tax(&mut self, rate: f64) {
+        self.sales_tax = rate;
+    }
+
+    /// Sets the brackets of the tax on the proceeds of sellers per tick,
+    /// as pairs of the income from which on the marginal rate applies, and the rate.
+    pub fn set_income_tax(&mut self, mut brackets: Vec<(WareAmount, f64)>) {
+        brackets.sort_by_key(|(threshold, _)| *threshold);
+        self.income_tax = brackets;
+    }
+
+    /// Sets the share of the currency holdings that entities pay every tick.
+    pub fn set_wealth_tax(&mut self, rate: f64) {
+        self.wealth_tax = rate;
+    }
+
+    /// Sets the amount that every household pays every tick.
+    pub fn set_head_tax(&mut self, amount: WareAmount) {
+        self.head_tax = amount;
+    }
+
+    pub fn sales_tax(&self) -> f64 {
+        self.sales_tax
+    }
+
+    pub fn income_tax_brackets(&self) -> &[(WareAmount, f64)] {
+        &self.income_tax
+    }
+
+    pub fn wealth_tax(&self) -> f64 {
+        self.wealth_tax
+    }
+
+    pub fn head_tax(&self) -> WareAmount {
+        self.head_tax
+    }
+
+    /// The income tax on the given income, rounded down.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use model::entity::government::Taxes;
+    ///
+    /// let mut taxes = Taxes::new();
+    /// taxes.set_income_tax(vec![(100, 0.3), (0, 0.1)]);
+    /// assert_eq!(5, taxes.income_tax(50));
+    /// assert_eq!(25, taxes.income_tax(150));
+    /// ```
+    pub fn income_tax(&self, income: WareAmount) -> WareAmount {
+        let mut tax = 0.0;
+        for (i, (threshold, rate)) in self.income_tax.iter().enumerate() {
+            let upper = self
+                .income_tax
+                .get(i + 1)
+                .map_or(income, |(next, _)| (*next).min(income));
+            if upper > *threshold {
+                tax += f64::from(upper - threshold) * rate;
+            }
+        }
+        tax.floor() as WareAmount
+    }
+}
+
+impl FiscalStats {
+    pub fn record_sales_tax(&mut self, amount: WareAmount) {
+        self.sales_tax += amount;
+    }
+
+    pub fn record_income_tax(&mut self, amount: WareAmount) {
+        self.income_tax += amount;
+    }
+
+    pub fn record_wealth_tax(&mut self, amount: WareAmount) {
+        self.wealth_tax += amount;
+    }
+
+    pub fn record_head_tax(&mut self, amount: WareAmount) {
+        self.head_tax += amount;
+    }
+
+    pub fn record_transfers(&mut self, amount: WareAmount) {
+        self.transfers += amount;
+    }
+
+    pub fn record_purchases(&mut self, amount: WareAmount) {
+        self.purchases += amount;
+    }
+
+    pub fn sales_tax(&self) -> WareAmount {
+        self.sales_tax
+    }
+
+    pub fn income_tax(&self) -> WareAmount {
+        self.income_tax
+    }
+
+    pub fn wealth_tax(&self) -> WareAmount {
+        self.wealth_tax
+    }
+
+    pub fn head_tax(&self) -> WareAmount {
+        self.head_tax
+    }
+
+    /// The taxes levied in the last tick. Taxes that were levied but not paid count as well.
+    pub fn revenue(&self) -> WareAmount {
+        self.sales_tax + self.income_tax + self.wealth_tax + self.head_tax
+    }
+
+    pub fn transfers(&self) -> WareAmount {
+        self.transfers
+    }
+
+    pub fn purchases(&self) -> WareAmount {
+        self.purchases
+    }
+
+    /// The revenue minus the spending in the last tick.
+    pub fn balance(&self) -> i64 {
+        i64::from(self.revenue()) - i64::from(self.transfers) - i64::from(self.purchases)
+    }
+}
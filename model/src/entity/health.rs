@@ -0,0 +1,53 @@
+/// The health of an entity with needs.
+/// Health declines for every required recipe that starves, and recovers when no required recipe starves.
+/// An entity whose health reaches zero dies.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Health {
+    health: u32,
+    max_health: u32,
+}
+
+impl Health {
+    /// Creates a health counter at its maximum.
+    pub fn new(max_health: u32) -> Self {
+        Self {
+            health: max_health,
+            max_health,
+        }
+    }
+
+    /// Declines the health by one for each starved recipe, or recovers it by one if none starved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use model::entity::health::Health;
+    ///
+    /// let mut health = Health::new(3);
+    /// health.update(2);
+    /// assert_eq!(1, health.health());
+    /// health.update(0);
+    /// assert_eq!(2, health.health());
+    /// health.update(5);
+    /// assert!(health.is_dead());
+    /// ```
+    pub fn update(&mut self, starved_recipes: u32) {
+        if starved_recipes > 0 {
+            self.health = self.health.saturating_sub(starved_recipes);
+        } else {
+            self.health = (self.health + 1).min(self.max_health);
+        }
+    }
+
+    pub fn health(&self) -> u32 {
+        self.health
+    }
+
+    pub fn max_health(&self) -> u32 {
+        self.max_health
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health == 0
+    }
+}
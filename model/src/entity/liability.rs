@@ -0,0 +1,49 @@
+use crate::{
+    ware::Ware,
+    world::{EntityId, Tick},
+};
+
+/// An amount of currency that an entity owes to a creditor.
+/// Liabilities without a creditor are paid to no one, like fixed costs.
+/// In a liquidation, liabilities with a lower priority value are paid first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Liability {
+    creditor: Option<EntityId>,
+    amount: Ware,
+    due: Tick,
+    priority: u32,
+}
+
+impl Liability {
+    pub fn new(creditor: Option<EntityId>, amount: Ware, due: Tick, priority: u32) -> Self {
+        debug_assert!(amount.is_currency());
+
+        Self {
+            creditor,
+            amount,
+            due,
+            priority,
+        }
+    }
+
+    pub fn creditor(&self) -> Option<EntityId> {
+        self.creditor
+    }
+
+    pub fn amount(&self) -> &Ware {
+        &self.amount
+    }
+
+    /// The tick at which this liability must be paid.
+    pub fn due(&self) -> Tick {
+        self.due
+    }
+
+    pub fn is_due(&self, tick: Tick) -> bool {
+        self.due <= tick
+    }
+
+    pub fn priority(&self) -> u32 {
+        self.priority
+    }
+}
@@ -0,0 +1,246 @@
+use crate::{
+    entity::{agent::Agent, Entity},
+    market::{offer::OfferType, Market},
+    ware::{Ware, WareAmount, WareType},
+};
+use rand::RngCore;
+
+/// The agent of an entity that provides liquidity by quoting both sides of the market for some ware types.
+/// Prices are in the currency of the entity.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarketMaker {
+    quotes: Vec<Quote>,
+}
+
+/// How a market maker quotes a ware type, and its results in that ware type.
+/// It keeps its inventory within the band around the target, and its spread widens
+/// as its inventory moves away from the target.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quote {
+    ware_type: WareType,
+    reference_price: WareAmount,
+    spread: f64,
+    widening: f64,
+    target: WareAmount,
+    band: WareAmount,
+    size: WareAmount,
+    enabled: bool,
+    stats: MarketMakerStats,
+}
+
+/// The trades of a market maker in a ware type.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MarketMakerStats {
+    trades: u32,
+    bought: WareAmount,
+    sold: WareAmount,
+    cash_flow: i64,
+}
+
+impl MarketMaker {
+    pub fn new(quotes: Vec<Quote>) -> Self {
+        Self { quotes }
+    }
+
+    /// Turns quoting of the given ware type on or off. Fails if the market maker has no quote for it.
+    pub fn set_enabled(&mut self, ware_type: WareType, enabled: bool) -> Result<(), ()> {
+        self.quote_mut(ware_type).ok_or(())?.enabled = enabled;
+        Ok(())
+    }
+
+    pub fn quote_mut(&mut self, ware_type: WareType) -> Option<&mut Quote> {
+        self.quotes
+            .iter_mut()
+            .find(|quote| quote.ware_type == ware_type)
+    }
+
+    pub fn quotes(&self) -> &[Quote] {
+        &self.quotes
+    }
+
+    pub fn quote(&self, ware_type: WareType) -> Option<&Quote> {
+        self.quotes
+            .iter()
+            .find(|quote| quote.ware_type == ware_type)
+    }
+}
+
+impl Agent for MarketMaker {
+    /// Quotes the enabled ware types of this market maker.
+    /// It bids only for as much as its inventory band, its cash and its storage allow,
+    /// and asks only for as much as it holds above the lower end of its band.
+    fn decide_offers(
+        &mut self,
+        entity: &Entity,
+        _market: &Market,
+        _rng: &mut dyn RngCore,
+    ) -> Vec<(Ware, OfferType, Ware)> {
+        let currency = entity.currency();
+        let mut cash = entity.wares().ware_amount(currency);
+        let mut offers = Vec::new();
+        for quote in self.quotes().iter().filter(|quote| quote.is_enabled()) {
+            let inventory = entity.wares().ware_amount(quote.ware_type());
+            let (bid, ask) = quote.prices(inventory);
+            let bid_size = quote
+                .bid_size(inventory)
+                .min(cash / bid)
+                .min(entity.wares().room_for(quote.ware_type()));
+            if bid_size > 0 {
+                cash -= bid_size * bid;
+                offers.push((
+                    Ware::new(quote.ware_type(), bid_size),
+                    OfferType::Buy,
+                    Ware::new(currency, bid),
+                ));
+            }
+            let ask_size = quote.ask_size(inventory);
+            if ask_size > 0 {
+                offers.push((
+                    Ware::new(quote.ware_type(), ask_size),
+                    OfferType::Sell,
+                    Ware::new(currency, ask),
+                ));
+            }
+        }
+        offers
+    }
+
+    fn box_clone(&self) -> Box<dyn Agent> {
+        Box::new(self.clone())
+    }
+}
+
+impl Quote {
+    /// Creates a quote around the reference price with the given spread, as a share of the reference price.
+    /// The spread grows by `widening` times itself for each band width the inventory is away from the target.
+    /// At most `size` wares are offered on each side per tick.
+    pub fn new(
+        ware_type: WareType,
+        reference_price: WareAmount,
+        spread: f64,
+        widening: f64,
+        target: WareAmount,
+        band: WareAmount,
+        size: WareAmount,
+    ) -> Self {
+        Self {
+            ware_type,
+            reference_price,
+            spread,
+            widening,
+            target,
+            band,
+            size,
+            enabled: true,
+            stats: MarketMakerStats::default(),
+        }
+    }
+
+    pub fn set_reference_price(&mut self, reference_price: WareAmount) {
+        self.reference_price = reference_price;
+    }
+
+    pub fn stats_mut(&mut self) -> &mut MarketMakerStats {
+        &mut self.stats
+    }
+
+    /// The bid and ask prices for the given inventory, rounded to whole prices.
+    /// The ask is always above the bid, and the bid is at least 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use model::{entity::market_maker::Quote, ware::WareType};
+    ///
+    /// let quote = Quote::new(WareType::Food, 100, 0.1, 1.0, 20, 10, 5);
+    /// assert_eq!((95, 105), quote.prices(20));
+    /// assert_eq!((90, 110), quote.prices(30));
+    /// assert_eq!((90, 110), quote.prices(10));
+    /// ```
+    pub fn prices(&self, inventory: WareAmount) -> (WareAmount, WareAmount) {
+        let deviation = (f64::from(inventory) - f64::from(self.target)).abs();
+        let half_spread =
+            self.spread / 2.0 * (1.0 + self.widening * deviation / f64::from(self.band.max(1)));
+        let offset = (f64::from(self.reference_price) * half_spread)
+            .round()
+            .max(1.0) as WareAmount;
+        let bid = self.reference_price.saturating_sub(offset).max(1);
+        (bid, self.reference_price + offset)
+    }
+
+    /// The most wares the market maker buys with the given inventory, staying within its band.
+    pub fn bid_size(&self, inventory: WareAmount) -> WareAmount {
+        self.size
+            .min((self.target + self.band).saturating_sub(inventory))
+    }
+
+    /// The most wares the market maker sells with the given inventory, staying within its band.
+    pub fn ask_size(&self, inventory: WareAmount) -> WareAmount {
+        self.size
+            .min(inventory.saturating_sub(self.target.saturating_sub(self.band)))
+    }
+
+    pub fn ware_type(&self) -> WareType {
+        self.ware_type
+    }
+
+    pub fn reference_price(&self) -> WareAmount {
+        self.reference_price
+    }
+
+    pub fn target(&self) -> WareAmount {
+        self.target
+    }
+
+    pub fn band(&self) -> WareAmount {
+        self.band
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn stats(&self) -> &MarketMakerStats {
+        &self.stats
+    }
+}
+
+impl MarketMakerStats {
+    /// Records a trade of the given amount at the given price per ware.
+    pub fn record_trade(&mut self, offer_type: OfferType, amount: WareAmount, price: WareAmount) {
+        let value = i64::from(amount) * i64::from(price);
+        self.trades += 1;
+        match offer_type {
+            OfferType::Buy => {
+                self.bought += amount;
+                self.cash_flow -= value;
+            }
+            OfferType::Sell => {
+                self.sold += amount;
+                self.cash_flow += value;
+            }
+        }
+    }
+
+    pub fn trades(&self) -> u32 {
+        self.trades
+    }
+
+    pub fn bought(&self) -> WareAmount {
+        self.bought
+    }
+
+    pub fn sold(&self) -> WareAmount {
+        self.sold
+    }
+
+    /// The currency received for sales minus the currency paid for purchases.
+    pub fn cash_flow(&self) -> i64 {
+        self.cash_flow
+    }
+
+    /// The profit and loss, valuing the net inventory bought at the given price per ware.
+    pub fn pnl(&self, price: WareAmount) -> i64 {
+        self.cash_flow + (i64::from(self.bought) - i64::from(self.sold)) * i64::from(price)
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{
+    entity::{agent::Agent, Entity},
+    market::{offer::OfferType, Market},
+    region::RegionId,
+    ware::{Ware, WareAmount, WareType},
+};
+use rand::RngCore;
+
+/// The agent of an entity that buys wares in a region where they are cheap
+/// and carries them to a region where they sell for more.
+/// Prices are in the currency of the entity.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Merchant {
+    ware_types: Vec<WareType>,
+    min_margin: f64,
+    inventory_cap: WareAmount,
+    risk_limit: WareAmount,
+    voyage: Option<Voyage>,
+    stats: MerchantStats,
+}
+
+/// A planned trade of a merchant: buying in its current region and selling in the destination.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Voyage {
+    ware_type: WareType,
+    destination: RegionId,
+    amount: WareAmount,
+    buy_price: WareAmount,
+    sell_price: WareAmount,
+    capital: WareAmount,
+}
+
+/// The results of the completed voyages of a merchant.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MerchantStats {
+    voyages: u32,
+    profit: i64,
+    last_profit: Option<i64>,
+}
+
+impl Merchant {
+    /// Creates a merchant that trades the given ware types.
+    /// It only starts a voyage if the expected profit per ware after transport costs is at least
+    /// `min_margin` times the buy price, carries at most `inventory_cap` wares,
+    /// and spends at most `risk_limit` of its currency on a voyage.
+    pub fn new(
+        ware_types: Vec<WareType>,
+        min_margin: f64,
+        inventory_cap: WareAmount,
+        risk_limit: WareAmount,
+    ) -> Self {
+        Self {
+            ware_types,
+            min_margin,
+            inventory_cap,
+            risk_limit,
+            voyage: None,
+            stats: MerchantStats::default(),
+        }
+    }
+
+    pub fn set_voyage(&mut self, voyage: Option<Voyage>) {
+        self.voyage = voyage;
+    }
+
+    pub fn voyage_mut(&mut self) -> Option<&mut Voyage> {
+        self.voyage.as_mut()
+    }
+
+    /// Ends the current voyage with the given amount of currency held, and records its profit.
+    pub fn complete_voyage(&mut self, cash: WareAmount) {
+        if let Some(voyage) = self.voyage.take() {
+            self.stats
+                .record_voyage(i64::from(cash) - i64::from(voyage.capital));
+        }
+    }
+
+    pub fn ware_types(&self) -> &[WareType] {
+        &self.ware_types
+    }
+
+    pub fn min_margin(&self) -> f64 {
+        self.min_margin
+    }
+
+    pub fn inventory_cap(&self) -> WareAmount {
+        self.inventory_cap
+    }
+
+    pub fn risk_limit(&self) -> WareAmount {
+        self.risk_limit
+    }
+
+    pub fn voyage(&self) -> Option<&Voyage> {
+        self.voyage.as_ref()
+    }
+
+    pub fn stats(&self) -> &MerchantStats {
+        &self.stats
+    }
+}
+
+impl Agent for Merchant {
+    /// Offers for the voyage of this merchant: a buy offer for the rest of the planned amount before it leaves,
+    /// and a sell offer for its cargo in the destination.
+    fn decide_offers(
+        &mut self,
+        entity: &Entity,
+        _market: &Market,
+        _rng: &mut dyn RngCore,
+    ) -> Vec<(Ware, OfferType, Ware)> {
+        let voyage = match &self.voyage {
+            Some(voyage) => voyage,
+            None => return Vec::new(),
+        };
+
+        let held = entity.wares().ware_amount(voyage.ware_type());
+        let (amount, offer_type, price) = if entity.region() == voyage.destination() {
+            (held, OfferType::Sell, voyage.sell_price())
+        } else {
+            (
+                voyage.amount().saturating_sub(held),
+                OfferType::Buy,
+                voyage.buy_price(),
+            )
+        };
+        if amount > 0 {
+            vec![(
+                Ware::new(voyage.ware_type(), amount),
+                offer_type,
+                Ware::new(entity.currency(), price),
+            )]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn Agent> {
+        Box::new(self.clone())
+    }
+}
+
+impl Voyage {
+    /// Creates a voyage that buys up to `amount` wares for at most `buy_price` per ware
+    /// and sells them in the destination for `sell_price` per ware.
+    /// The capital is the amount of currency the merchant held when it started.
+    pub fn new(
+        ware_type: WareType,
+        destination: RegionId,
+        amount: WareAmount,
+        buy_price: WareAmount,
+        sell_price: WareAmount,
+        capital: WareAmount,
+    ) -> Self {
+        Self {
+            ware_type,
+            destination,
+            amount,
+            buy_price,
+            sell_price,
+            capital,
+        }
+    }
+
+    /// Changes the destination, for example to sell the wares where they are if the merchant cannot travel.
+    pub fn set_destination(&mut self, destination: RegionId) {
+        self.destination = destination;
+    }
+
+    pub fn ware_type(&self) -> WareType {
+        self.ware_type
+    }
+
+    pub fn destination(&self) -> RegionId {
+        self.destination
+    }
+
+    pub fn amount(&self) -> WareAmount {
+        self.amount
+    }
+
+    pub fn buy_price(&self) -> WareAmount {
+        self.buy_price
+    }
+
+    pub fn sell_price(&self) -> WareAmount {
+        self.sell_price
+    }
+
+    pub fn capital(&self) -> WareAmount {
+        self.capital
+    }
+}
+
+impl MerchantStats {
+    pub fn record_voyage(&mut self, profit: i64) {
+        self.voyages += 1;
+        self.profit += profit;
+        self.last_profit = Some(profit);
+    }
+
+    pub fn voyages(&self) -> u32 {
+        self.voyages
+    }
+
+    /// The summed profit of all completed voyages, after transport costs.
+    pub fn profit(&self) -> i64 {
+        self.profit
+    }
+
+    pub fn last_profit(&self) -> Option<i64> {
+        self.last_profit
+    }
+}
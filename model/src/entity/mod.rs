@@ -1,19 +1,65 @@
 use crate::{
-    entity::recipe::Recipe,
+    entity::{
+        agent::Agent,
+        bank::{Bank, CreditLine},
+        batch::Batch,
+        central_bank::CentralBank,
+        government::Government,
+        health::Health,
+        liability::Liability,
+        recipe::{CapitalInput, Recipe},
+        utility::Utility,
+    },
     market::OfferId,
     prices::PriceTable,
-    ware::{Ware, WareStore},
+    region::{RegionId, Trip},
+    ware::{Lot, Storage, Ware, WareAmount, WareStore, WareType},
+    world::{EntityId, Tick},
 };
+use std::collections::{HashMap, VecDeque};
 
+pub mod agent;
+pub mod bank;
+pub mod batch;
+pub mod central_bank;
+pub mod government;
+pub mod health;
+pub mod liability;
+pub mod market_maker;
+pub mod merchant;
 pub mod recipe;
+pub mod utility;
 
 #[derive(Clone, Debug, Default)]
 pub struct Entity {
     name: String,
     wares: WareStore,
+    currency: WareType,
     buy_prices: PriceTable,
     sell_prices: PriceTable,
     recipes: Vec<Recipe>,
+    batches: Vec<Batch>,
+    wear: HashMap<(WareType, u32), u32>,
+    utility: Option<Utility>,
+    utility_level: f64,
+    health: Option<Health>,
+    heir: Option<EntityId>,
+    labor: WareAmount,
+    liabilities: Vec<Liability>,
+    fixed_costs: Vec<Ware>,
+    bankrupt: bool,
+    bank: Option<Bank>,
+    credit_line: Option<CreditLine>,
+    central_bank: Option<CentralBank>,
+    government: Option<Government>,
+    agent: Option<Box<dyn Agent>>,
+    spoiled: WareStore,
+    lost: WareStore,
+    committed_deliveries: WareStore,
+    contracted_supplies: WareStore,
+    wealth_history: VecDeque<f64>,
+    region: RegionId,
+    trip: Option<Trip>,
     offer_ids: Vec<OfferId>,
 }
 
@@ -23,9 +69,32 @@ impl Entity {
         Entity {
             name,
             wares: Default::default(),
+            currency: Default::default(),
             buy_prices: Default::default(),
             sell_prices: Default::default(),
             recipes,
+            batches: Default::default(),
+            wear: Default::default(),
+            utility: None,
+            utility_level: 0.0,
+            health: None,
+            heir: None,
+            labor: 0,
+            liabilities: Default::default(),
+            fixed_costs: Default::default(),
+            bankrupt: false,
+            bank: None,
+            credit_line: None,
+            central_bank: None,
+            government: None,
+            agent: None,
+            spoiled: Default::default(),
+            lost: Default::default(),
+            committed_deliveries: Default::default(),
+            contracted_supplies: Default::default(),
+            wealth_history: Default::default(),
+            region: 0,
+            trip: None,
             offer_ids: Default::default(),
         }
     }
@@ -36,10 +105,109 @@ impl Entity {
     pub fn remove_ware(&mut self, ware: Ware) -> Result<Ware, ()> {
         self.wares_mut().pop_ware(ware)
     }
+
+    /// Adds the given ware as far as the storage of this entity has room for it,
+    /// and records the part that did not fit as lost.
     pub fn add_ware(&mut self, ware: Ware) {
+        let overflow = self.add_ware_max(ware);
+        self.lose(overflow);
+    }
+
+    /// Adds as much of the given ware as the storage of this entity has room for, and returns the rest.
+    #[must_use = "the part of the ware that did not fit is not stored"]
+    pub fn add_ware_max(&mut self, ware: Ware) -> Ware {
         self.wares_mut().push_ware(ware)
     }
 
+    /// Limits what this entity can hold, and sets the fee it pays for holding wares.
+    pub fn set_storage(&mut self, storage: Option<Storage>) {
+        self.wares_mut().set_storage(storage)
+    }
+
+    /// Removes the given ware like `remove_ware`, and returns the lots it was taken from, oldest first.
+    pub fn remove_ware_lots(&mut self, ware: Ware) -> Result<Vec<Lot>, ()> {
+        self.wares_mut().pop_lots(ware)
+    }
+
+    /// Adds the given ware like `add_ware`, keeping the ages of the given lots of it.
+    pub fn add_ware_lots(&mut self, ware: Ware, lots: Vec<Lot>) {
+        let overflow = self.wares_mut().push_lots(ware, lots);
+        self.lose(overflow);
+    }
+
+    /// Starts tracking the age of the wares of the given type that arrived since the last stamp.
+    pub fn stamp_lots(&mut self, ware_type: WareType, tick: Tick) {
+        self.wares_mut().stamp_lots(ware_type, tick)
+    }
+
+    /// Removes the wares of the given type that were stored before the given tick and records them as spoiled.
+    /// Returns the amount removed.
+    pub fn spoil_lots_before(&mut self, ware_type: WareType, tick: Tick) -> WareAmount {
+        let amount = self.wares_mut().pop_lots_before(ware_type, tick);
+        self.spoiled
+            .push_ware_unlimited(Ware::new(ware_type, amount));
+        amount
+    }
+
+    /// Removes the given ware, taken from the oldest lots, and records it as spoiled.
+    pub fn spoil(&mut self, ware: Ware) -> Result<(), ()> {
+        let ware = self.remove_ware(ware)?;
+        self.spoiled.push_ware_unlimited(ware);
+        Ok(())
+    }
+
+    /// Records the given ware as lost for lack of storage.
+    fn lose(&mut self, ware: Ware) {
+        if ware.amount() > 0 {
+            trace!("{} lost {} for lack of storage", self.name(), ware);
+            self.lost.push_ware_unlimited(ware);
+        }
+    }
+
+    /// Sets the wares this entity must deliver and the wares it will receive under supply contracts in the next tick,
+    /// which it keeps out of the market.
+    pub fn set_commitments(&mut self, deliveries: WareStore, supplies: WareStore) {
+        self.committed_deliveries = deliveries;
+        self.contracted_supplies = supplies;
+    }
+
+    /// Removes all of the given wares, or none of them if not all are available.
+    pub fn remove_wares(&mut self, wares: WareStore) -> Result<WareStore, ()> {
+        self.wares_mut().pop_wares(wares)
+    }
+
+    /// Records one use of the given capital input, and removes the units that wore out.
+    /// Returns the wares that were removed.
+    pub fn wear_capital_input(&mut self, capital_input: &CapitalInput) -> Ware {
+        let lifetime = match capital_input.lifetime() {
+            Some(lifetime) => lifetime,
+            None => return Ware::new(capital_input.ware_type(), 0),
+        };
+
+        let uses = self
+            .wear
+            .entry((capital_input.ware_type(), lifetime))
+            .or_insert(0);
+        *uses += capital_input.ware().amount();
+        let mut worn_out = Ware::new(capital_input.ware_type(), *uses / lifetime);
+        *uses %= lifetime;
+        self.wares_mut().pop_ware_max(&mut worn_out)
+    }
+
+    pub fn start_batch(&mut self, batch: Batch) {
+        self.batches.push(batch);
+    }
+
+    /// Removes and returns all batches that are complete at the given tick.
+    pub fn take_completed_batches(&mut self, tick: Tick) -> Vec<Batch> {
+        let (completed, in_progress) = self
+            .batches
+            .drain(..)
+            .partition(|batch| batch.is_complete(tick));
+        self.batches = in_progress;
+        completed
+    }
+
     pub fn add_offer_id(&mut self, offer_id: OfferId) {
         self.offer_ids_mut().push(offer_id);
     }
@@ -56,6 +224,164 @@ impl Entity {
         &mut self.wares
     }
 
+    /// Sets the currency this entity prices its offers in.
+    /// Holdings of other currencies are offered on the exchange market for this currency.
+    pub fn set_currency(&mut self, currency: WareType) {
+        debug_assert!(currency.is_currency());
+
+        self.currency = currency;
+    }
+
+    /// Makes this entity a household that buys and consumes wares according to the given utility function.
+    pub fn set_utility(&mut self, utility: Utility) {
+        self.utility = Some(utility);
+    }
+
+    /// Consumes all wares that contribute to the utility of this household,
+    /// and records the utility of the consumed wares.
+    pub fn consume(&mut self) {
+        let utility = match &self.utility {
+            Some(utility) => utility.clone(),
+            None => return,
+        };
+
+        let mut consumed = WareStore::new();
+        for ware_type in utility.ware_types() {
+            if let Some(ware) = self.wares().get_ware(ware_type) {
+                consumed.push_ware_unlimited(self.remove_ware(ware).unwrap());
+            }
+        }
+        self.utility_level = utility.value(&consumed);
+    }
+
+    /// Gives this entity needs: it dies if its required recipes starve for too long.
+    pub fn set_health(&mut self, health: Health) {
+        self.health = Some(health);
+    }
+
+    /// Updates the health of this entity by the number of required recipes that starved in this tick.
+    pub fn update_health(&mut self, starved_recipes: u32) {
+        if let Some(health) = &mut self.health {
+            health.update(starved_recipes);
+        }
+    }
+
+    /// Sets the entity that inherits the wares of this entity when it is removed from the world.
+    pub fn set_heir(&mut self, heir: Option<EntityId>) {
+        self.heir = heir;
+    }
+
+    /// Makes this entity a bank that takes deposits and grants loans.
+    pub fn set_bank(&mut self, bank: Bank) {
+        self.bank = Some(bank);
+    }
+
+    pub fn bank_mut(&mut self) -> Option<&mut Bank> {
+        self.bank.as_mut()
+    }
+
+    pub fn set_credit_line(&mut self, credit_line: Option<CreditLine>) {
+        self.credit_line = credit_line;
+    }
+
+    pub fn credit_line_mut(&mut self) -> Option<&mut CreditLine> {
+        self.credit_line.as_mut()
+    }
+
+    /// Makes this entity the central bank, which creates the money it spends.
+    pub fn set_central_bank(&mut self, central_bank: CentralBank) {
+        self.central_bank = Some(central_bank);
+    }
+
+    pub fn central_bank_mut(&mut self) -> Option<&mut CentralBank> {
+        self.central_bank.as_mut()
+    }
+
+    /// Makes this entity the government, which levies taxes and spends the revenue.
+    pub fn set_government(&mut self, government: Government) {
+        self.government = Some(government);
+    }
+
+    pub fn government_mut(&mut self) -> Option<&mut Government> {
+        self.government.as_mut()
+    }
+
+    /// Sets the agent that decides the offers and production of this entity.
+    /// Entities without an agent get the agent of their role when they first trade.
+    pub fn set_agent(&mut self, agent: Option<Box<dyn Agent>>) {
+        self.agent = agent;
+    }
+
+    /// Removes the agent of this entity and returns it, such that it can be called with the entity.
+    pub fn take_agent(&mut self) -> Option<Box<dyn Agent>> {
+        self.agent.take()
+    }
+
+    /// The agent of this entity as its concrete type, or None if it has no agent of that type.
+    pub fn agent_as_mut<T: Agent>(&mut self) -> Option<&mut T> {
+        self.agent.as_deref_mut()?.downcast_mut()
+    }
+
+    /// Makes this entity a worker that gets the given hours of labor every tick.
+    pub fn set_labor(&mut self, hours: WareAmount) {
+        self.labor = hours;
+    }
+
+    pub fn add_liability(&mut self, liability: Liability) {
+        self.liabilities.push(liability);
+    }
+
+    /// Removes the liabilities due at the given tick and returns them.
+    pub fn take_due_liabilities(&mut self, tick: Tick) -> Vec<Liability> {
+        let (due, pending) = std::mem::take(&mut self.liabilities)
+            .into_iter()
+            .partition(|liability| liability.is_due(tick));
+        self.liabilities = pending;
+        due
+    }
+
+    /// Removes all liabilities of this entity and returns them.
+    pub fn take_liabilities(&mut self) -> Vec<Liability> {
+        std::mem::take(&mut self.liabilities)
+    }
+
+    /// Sets the currency this entity has to pay every tick, to no one.
+    pub fn set_fixed_costs(&mut self, fixed_costs: Vec<Ware>) {
+        debug_assert!(fixed_costs.iter().all(Ware::is_currency));
+
+        self.fixed_costs = fixed_costs;
+    }
+
+    /// Marks this entity as bankrupt, or clears the mark after a restructuring.
+    /// A bankrupt entity auctions its inventory and buys nothing.
+    pub fn set_bankrupt(&mut self, bankrupt: bool) {
+        self.bankrupt = bankrupt;
+    }
+
+    /// Records the value of the wares of this entity, keeping at most the given number of the latest records.
+    pub fn record_wealth(&mut self, wealth: f64, records: usize) {
+        self.wealth_history.push_back(wealth);
+        while self.wealth_history.len() > records {
+            self.wealth_history.pop_front();
+        }
+    }
+
+    /// Moves this entity to the given region at once.
+    /// Its offers in the market of the previous region are replaced in the next tick.
+    pub fn set_region(&mut self, region: RegionId) {
+        self.region = region;
+    }
+
+    /// Starts or ends a journey of this entity. A travelling entity does not trade.
+    pub fn set_trip(&mut self, trip: Option<Trip>) {
+        self.trip = trip;
+    }
+
+    /// Removes all wares of this entity.
+    pub fn take_wares(&mut self) -> WareStore {
+        std::mem::take(&mut self.wares)
+    }
+
     pub fn buy_prices_mut(&mut self) -> &mut PriceTable {
         &mut self.buy_prices
     }
@@ -75,6 +401,30 @@ impl Entity {
         &self.wares
     }
 
+    /// The wares this entity must deliver under supply contracts in the next tick.
+    pub fn committed_deliveries(&self) -> &WareStore {
+        &self.committed_deliveries
+    }
+
+    /// The wares this entity will receive under supply contracts in the next tick.
+    pub fn contracted_supplies(&self) -> &WareStore {
+        &self.contracted_supplies
+    }
+
+    /// The wares of this entity that spoiled so far.
+    pub fn spoiled(&self) -> &WareStore {
+        &self.spoiled
+    }
+
+    /// The wares that this entity received so far but could not store.
+    pub fn lost(&self) -> &WareStore {
+        &self.lost
+    }
+
+    pub fn currency(&self) -> WareType {
+        self.currency
+    }
+
     pub fn buy_prices(&self) -> &PriceTable {
         &self.buy_prices
     }
@@ -83,10 +433,152 @@ impl Entity {
         &self.sell_prices
     }
 
+    pub fn utility(&self) -> Option<&Utility> {
+        self.utility.as_ref()
+    }
+
+    pub fn is_household(&self) -> bool {
+        self.utility.is_some()
+    }
+
+    /// The utility of the wares this household consumed most recently.
+    pub fn utility_level(&self) -> f64 {
+        self.utility_level
+    }
+
+    pub fn health(&self) -> Option<&Health> {
+        self.health.as_ref()
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health.as_ref().is_some_and(Health::is_dead)
+    }
+
+    pub fn heir(&self) -> Option<EntityId> {
+        self.heir
+    }
+
+    pub fn liabilities(&self) -> &[Liability] {
+        &self.liabilities
+    }
+
+    pub fn fixed_costs(&self) -> &[Ware] {
+        &self.fixed_costs
+    }
+
+    pub fn is_bankrupt(&self) -> bool {
+        self.bankrupt
+    }
+
+    pub fn bank(&self) -> Option<&Bank> {
+        self.bank.as_ref()
+    }
+
+    pub fn is_bank(&self) -> bool {
+        self.bank.is_some()
+    }
+
+    pub fn credit_line(&self) -> Option<&CreditLine> {
+        self.credit_line.as_ref()
+    }
+
+    pub fn central_bank(&self) -> Option<&CentralBank> {
+        self.central_bank.as_ref()
+    }
+
+    pub fn is_central_bank(&self) -> bool {
+        self.central_bank.is_some()
+    }
+
+    pub fn government(&self) -> Option<&Government> {
+        self.government.as_ref()
+    }
+
+    pub fn is_government(&self) -> bool {
+        self.government.is_some()
+    }
+
+    pub fn agent(&self) -> Option<&dyn Agent> {
+        self.agent.as_deref()
+    }
+
+    /// The agent of this entity as its concrete type, or None if it has no agent of that type.
+    pub fn agent_as<T: Agent>(&self) -> Option<&T> {
+        self.agent()?.downcast_ref()
+    }
+
+    /// The amount of the given currency this entity can still borrow from its credit line.
+    pub fn available_credit(&self, currency: WareType) -> WareAmount {
+        match &self.credit_line {
+            Some(credit_line) if credit_line.limit().ware_type() == currency => {
+                credit_line.available()
+            }
+            _ => 0,
+        }
+    }
+
+    /// The hours of labor this entity gets every tick.
+    pub fn labor(&self) -> WareAmount {
+        self.labor
+    }
+
+    pub fn is_worker(&self) -> bool {
+        self.labor > 0
+    }
+
+    /// The value of the wares of this entity when it was last recorded.
+    pub fn wealth(&self) -> f64 {
+        self.wealth_history.back().copied().unwrap_or(0.0)
+    }
+
+    /// The average change of the wealth of this entity per tick over the given number of latest ticks,
+    /// or None if its wealth was not recorded for that long.
+    pub fn wealth_trend(&self, ticks: usize) -> Option<f64> {
+        let len = self.wealth_history.len();
+        if ticks == 0 || len <= ticks {
+            return None;
+        }
+        Some((self.wealth() - self.wealth_history[len - 1 - ticks]) / ticks as f64)
+    }
+
+    /// The region this entity is located in, or left from if it is travelling.
+    pub fn region(&self) -> RegionId {
+        self.region
+    }
+
+    pub fn trip(&self) -> Option<&Trip> {
+        self.trip.as_ref()
+    }
+
+    pub fn is_travelling(&self) -> bool {
+        self.trip.is_some()
+    }
+
+    /// True if this entity is not a household and has a recipe that it does not require to live.
+    pub fn is_producer(&self) -> bool {
+        !self.is_household() && self.recipes.iter().any(|recipe| !recipe.is_required())
+    }
+
     pub fn recipes(&self) -> &[Recipe] {
         &self.recipes
     }
 
+    /// The batches that are in progress, in the order they were started.
+    pub fn batches(&self) -> &[Batch] {
+        &self.batches
+    }
+
+    /// The outputs of all batches in progress.
+    pub fn wares_in_progress(&self) -> WareStore {
+        let mut wares = WareStore::new();
+        for batch in self.batches() {
+            for output in batch.outputs() {
+                wares.push_ware_unlimited(output.clone());
+            }
+        }
+        wares
+    }
+
     pub fn offer_ids(&self) -> &[OfferId] {
         &self.offer_ids
     }
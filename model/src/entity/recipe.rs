@@ -1,21 +1,220 @@
-use crate::ware::Ware;
+use crate::{
+    ware::{Ware, WareAmount, WareType},
+    world::Tick,
+};
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Recipe {
     inputs: Vec<Ware>,
-    outputs: Vec<Ware>,
+    capital_inputs: Vec<CapitalInput>,
+    outcomes: Vec<Outcome>,
+    duration: Tick,
+    required: bool,
+}
+
+/// A ware that a recipe requires, but does not consume, such as a tool or a building.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CapitalInput {
+    ware: Ware,
+    lifetime: Option<u32>,
+}
+
+/// A set of outputs that a run of a recipe yields, chosen with a probability proportional to its weight.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Outcome {
+    weight: u32,
+    outputs: Vec<Output>,
+}
+
+/// An output ware of a recipe whose amount may be random.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Output {
+    ware_type: WareType,
+    amount: Yield,
+}
+
+/// The distribution of the amount of an output ware.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Yield {
+    /// Always the given amount.
+    Fixed(WareAmount),
+    /// The given amount with the given probability in percent, and nothing otherwise.
+    Chance(WareAmount, f64),
+    /// Uniformly distributed between the given amounts, both inclusive.
+    Uniform(WareAmount, WareAmount),
+    /// Poisson distributed with the given mean.
+    Poisson(f64),
 }
 
 impl Recipe {
+    /// Creates a recipe that always yields the given outputs.
     pub fn new(inputs: Vec<Ware>, outputs: Vec<Ware>) -> Self {
-        Recipe { inputs, outputs }
+        Self::with_outcomes(
+            inputs,
+            vec![Outcome::new(
+                1,
+                outputs.into_iter().map(Output::from).collect(),
+            )],
+        )
+    }
+
+    /// Creates a recipe that yields one of the given outcomes, chosen by their weights.
+    pub fn with_outcomes(inputs: Vec<Ware>, outcomes: Vec<Outcome>) -> Self {
+        debug_assert!(!outcomes.is_empty());
+
+        Recipe {
+            inputs,
+            capital_inputs: Vec::new(),
+            outcomes,
+            duration: 0,
+            required: false,
+        }
+    }
+
+    pub fn set_capital_inputs(&mut self, capital_inputs: Vec<CapitalInput>) {
+        self.capital_inputs = capital_inputs;
+    }
+
+    /// Sets the number of ticks between consuming the inputs and delivering the outputs.
+    /// A duration of zero delivers the outputs in the same tick.
+    pub fn set_duration(&mut self, duration: Tick) {
+        self.duration = duration;
     }
 
+    /// Marks this recipe as a need of the entities running it.
+    /// Entities with health lose health whenever a required recipe cannot run.
+    pub fn set_required(&mut self, required: bool) {
+        self.required = required;
+    }
+
+    /// The wares consumed by this recipe.
     pub fn inputs(&self) -> &[Ware] {
         &self.inputs
     }
 
-    pub fn outputs(&self) -> &[Ware] {
+    /// The wares required by this recipe without being consumed.
+    pub fn capital_inputs(&self) -> &[CapitalInput] {
+        &self.capital_inputs
+    }
+
+    pub fn outcomes(&self) -> &[Outcome] {
+        &self.outcomes
+    }
+
+    /// The outputs of this recipe if they are not random.
+    pub fn fixed_outputs(&self) -> Option<Vec<Ware>> {
+        match self.outcomes() {
+            [outcome] => outcome.fixed_outputs(),
+            _ => None,
+        }
+    }
+
+    /// The expected amount of the given ware type that a run of this recipe yields.
+    pub fn expected_output(&self, ware_type: WareType) -> f64 {
+        let total_weight: u32 = self.outcomes().iter().map(Outcome::weight).sum();
+        if total_weight == 0 {
+            return 0.0;
+        }
+
+        self.outcomes()
+            .iter()
+            .map(|outcome| {
+                let amount: f64 = outcome
+                    .outputs()
+                    .iter()
+                    .filter(|output| output.ware_type() == ware_type)
+                    .map(|output| output.amount().mean())
+                    .sum();
+                amount * f64::from(outcome.weight())
+            })
+            .sum::<f64>()
+            / f64::from(total_weight)
+    }
+
+    pub fn duration(&self) -> Tick {
+        self.duration
+    }
+
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+}
+
+impl CapitalInput {
+    /// Creates a capital input that wears out one unit of the ware every `lifetime` uses,
+    /// or never wears out if `lifetime` is None.
+    pub fn new(ware: Ware, lifetime: Option<u32>) -> Self {
+        debug_assert_ne!(lifetime, Some(0));
+
+        Self { ware, lifetime }
+    }
+
+    pub fn ware(&self) -> &Ware {
+        &self.ware
+    }
+
+    pub fn ware_type(&self) -> WareType {
+        self.ware.ware_type()
+    }
+
+    /// The number of uses after which one unit of the ware is worn out.
+    pub fn lifetime(&self) -> Option<u32> {
+        self.lifetime
+    }
+}
+
+impl Outcome {
+    pub fn new(weight: u32, outputs: Vec<Output>) -> Self {
+        Self { weight, outputs }
+    }
+
+    pub fn weight(&self) -> u32 {
+        self.weight
+    }
+
+    pub fn outputs(&self) -> &[Output] {
         &self.outputs
     }
+
+    /// The outputs of this outcome if they are not random.
+    pub fn fixed_outputs(&self) -> Option<Vec<Ware>> {
+        self.outputs()
+            .iter()
+            .map(|output| match output.amount() {
+                Yield::Fixed(amount) => Some(Ware::new(output.ware_type(), *amount)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Output {
+    pub fn new(ware_type: WareType, amount: Yield) -> Self {
+        Self { ware_type, amount }
+    }
+
+    pub fn ware_type(&self) -> WareType {
+        self.ware_type
+    }
+
+    pub fn amount(&self) -> &Yield {
+        &self.amount
+    }
+}
+
+impl Yield {
+    pub fn mean(&self) -> f64 {
+        match *self {
+            Yield::Fixed(amount) => f64::from(amount),
+            Yield::Chance(amount, percent) => f64::from(amount) * percent / 100.0,
+            Yield::Uniform(min, max) => (f64::from(min) + f64::from(max)) / 2.0,
+            Yield::Poisson(mean) => mean,
+        }
+    }
+}
+
+impl From<Ware> for Output {
+    fn from(ware: Ware) -> Self {
+        Output::new(ware.ware_type(), Yield::Fixed(ware.amount()))
+    }
 }
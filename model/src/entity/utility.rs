@@ -0,0 +1,183 @@
+use crate::ware::{WareAmount, WareStore, WareType};
+
+/// The utility a household derives from consuming wares.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Utility {
+    /// Cobb-Douglas utility with the given exponent per ware type.
+    CobbDouglas(Vec<(WareType, f64)>),
+    /// Constant elasticity of substitution utility with the given weight per ware type,
+    /// and the substitution parameter `rho`, which must be smaller than one and not zero.
+    /// Use `Utility::ces` to construct it with a validated `rho`.
+    Ces(Vec<(WareType, f64)>, f64),
+    /// Separable utility whose marginal utility per ware type, in units of money,
+    /// is linear between the given points of (amount, marginal utility), sorted by amount.
+    /// Beyond the last point, the marginal utility stays constant.
+    PiecewiseLinear(Vec<(WareType, Vec<(WareAmount, f64)>)>),
+}
+
+// Creators
+impl Utility {
+    /// Creates a CES utility. Fails if `rho` is zero, not smaller than one, or not finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use model::{entity::utility::Utility, ware::WareType};
+    ///
+    /// assert!(Utility::ces(vec![(WareType::Food, 1.0)], 0.5).is_ok());
+    /// assert!(Utility::ces(vec![(WareType::Food, 1.0)], 0.0).is_err());
+    /// assert!(Utility::ces(vec![(WareType::Food, 1.0)], 1.0).is_err());
+    /// ```
+    pub fn ces(weights: Vec<(WareType, f64)>, rho: f64) -> Result<Self, ()> {
+        if !rho.is_finite() || rho == 0.0 || rho >= 1.0 {
+            return Err(());
+        }
+        Ok(Utility::Ces(weights, rho))
+    }
+}
+
+// Getters
+impl Utility {
+    pub fn ware_types(&self) -> Vec<WareType> {
+        match self {
+            Utility::CobbDouglas(weights) | Utility::Ces(weights, _) => {
+                weights.iter().map(|(ware_type, _)| *ware_type).collect()
+            }
+            Utility::PiecewiseLinear(marginal_utilities) => marginal_utilities
+                .iter()
+                .map(|(ware_type, _)| *ware_type)
+                .collect(),
+        }
+    }
+
+    /// The utility of consuming the given wares.
+    pub fn value(&self, wares: &WareStore) -> f64 {
+        let amount = |ware_type| f64::from(wares.ware_amount(ware_type));
+        match self {
+            Utility::CobbDouglas(weights) => weights
+                .iter()
+                .map(|(ware_type, weight)| amount(*ware_type).powf(*weight))
+                .product(),
+            Utility::Ces(weights, rho) => weights
+                .iter()
+                .map(|(ware_type, weight)| weight * amount(*ware_type).powf(*rho))
+                .sum::<f64>()
+                .powf(1.0 / rho),
+            Utility::PiecewiseLinear(marginal_utilities) => marginal_utilities
+                .iter()
+                .map(|(ware_type, points)| {
+                    (0..wares.ware_amount(*ware_type))
+                        .map(|unit| marginal_utility(points, unit))
+                        .sum::<f64>()
+                })
+                .sum(),
+        }
+    }
+
+    /// The amount of the given ware type that maximizes utility at the given price and budget.
+    /// The prices of the other ware types are given by `reference_price`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use model::{entity::utility::Utility, ware::WareType};
+    ///
+    /// let utility = Utility::CobbDouglas(vec![(WareType::Food, 3.0), (WareType::Water, 1.0)]);
+    /// assert_eq!(15, utility.demand(WareType::Food, 5.0, 100.0, |_| 1.0));
+    /// assert_eq!(25, utility.demand(WareType::Water, 1.0, 100.0, |_| 5.0));
+    ///
+    /// let utility = Utility::PiecewiseLinear(vec![(WareType::Food, vec![(0, 10.0), (4, 2.0)])]);
+    /// assert_eq!(3, utility.demand(WareType::Food, 5.0, 100.0, |_| 1.0));
+    /// assert_eq!(2, utility.demand(WareType::Food, 5.0, 10.0, |_| 1.0));
+    /// assert_eq!(3, utility.demand(WareType::Food, 5.0, 1e12, |_| 1.0));
+    ///
+    /// let utility = Utility::PiecewiseLinear(vec![(WareType::Food, vec![(0, 10.0)])]);
+    /// assert_eq!(20, utility.demand(WareType::Food, 5.0, 100.0, |_| 1.0));
+    /// ```
+    pub fn demand<F: Fn(WareType) -> f64>(
+        &self,
+        ware_type: WareType,
+        price: f64,
+        budget: f64,
+        reference_price: F,
+    ) -> WareAmount {
+        debug_assert!(price > 0.0);
+
+        let demand = match self {
+            Utility::CobbDouglas(weights) => {
+                let total_weight: f64 = weights.iter().map(|(_, weight)| weight).sum();
+                weight(weights, ware_type) / total_weight * budget / price
+            }
+            Utility::Ces(weights, rho) => {
+                let sigma = 1.0 / (1.0 - rho);
+                let price_of = |other| {
+                    if other == ware_type {
+                        price
+                    } else {
+                        reference_price(other)
+                    }
+                };
+                let price_index: f64 = weights
+                    .iter()
+                    .map(|(other, weight)| weight.powf(sigma) * price_of(*other).powf(1.0 - sigma))
+                    .sum();
+                weight(weights, ware_type).powf(sigma) * price.powf(-sigma) * budget / price_index
+            }
+            Utility::PiecewiseLinear(marginal_utilities) => {
+                let points = match marginal_utilities
+                    .iter()
+                    .find(|(other, _)| *other == ware_type)
+                {
+                    Some((_, points)) => points,
+                    None => return 0,
+                };
+                let affordable = (budget / price).floor();
+                units_worth(points, price)
+                    .map_or(affordable, |units| affordable.min(f64::from(units)))
+            }
+        };
+        demand.max(0.0).floor() as WareAmount
+    }
+}
+
+fn weight(weights: &[(WareType, f64)], ware_type: WareType) -> f64 {
+    weights
+        .iter()
+        .find(|(other, _)| *other == ware_type)
+        .map(|(_, weight)| *weight)
+        .unwrap_or(0.0)
+}
+
+/// The number of units, counted from the first, before the marginal utility first falls below the price,
+/// or None if it never does. The crossing is solved on the segment where it falls below the price.
+fn units_worth(points: &[(WareAmount, f64)], price: f64) -> Option<WareAmount> {
+    match points.first() {
+        Some((_, utility)) if *utility >= price => {}
+        _ => return Some(0),
+    }
+    for segment in points.windows(2) {
+        let ((a0, u0), (a1, u1)) = (segment[0], segment[1]);
+        if u1 < price {
+            if a1 == a0 {
+                return Some(a0);
+            }
+            // The marginal utility is at least the price up to the crossing, including it.
+            let crossing = f64::from(a0) + (u0 - price) * f64::from(a1 - a0) / (u0 - u1);
+            return Some(crossing.floor() as WareAmount + 1);
+        }
+    }
+    None
+}
+
+/// The marginal utility of the unit with the given index, interpolated linearly between the points.
+fn marginal_utility(points: &[(WareAmount, f64)], unit: WareAmount) -> f64 {
+    match points.iter().position(|(amount, _)| *amount > unit) {
+        Some(0) => points[0].1,
+        Some(next) => {
+            let (a0, u0) = points[next - 1];
+            let (a1, u1) = points[next];
+            u0 + (u1 - u0) * f64::from(unit - a0) / f64::from(a1 - a0)
+        }
+        None => points.last().map(|(_, utility)| *utility).unwrap_or(0.0),
+    }
+}
@@ -0,0 +1,28 @@
+use crate::{contract::ContractId, shock::Shock, ware::Ware, world::EntityId};
+
+/// Something notable that happened in a world.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    /// The entity could not pay its due liabilities.
+    Bankruptcy(EntityId),
+    /// The inventory of the bankrupt entity was auctioned and the proceeds were paid to its creditors.
+    /// The entity was closed, or else restructured and its remaining liabilities written off.
+    Liquidation {
+        entity_id: EntityId,
+        payments: Vec<(Option<EntityId>, Ware)>,
+        closed: bool,
+    },
+    /// The entity failed to deliver or pay for a forward contract, and its margin went to the counterparty.
+    ForwardDefault {
+        entity_id: EntityId,
+        counterparty: EntityId,
+        forfeited: Ware,
+    },
+    /// The entity failed to deliver or pay for a delivery of the supply contract, and owes the penalty.
+    Breach {
+        contract_id: ContractId,
+        entity_id: EntityId,
+    },
+    /// A scheduled shock fired.
+    Shock(Shock),
+}
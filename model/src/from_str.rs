@@ -1,6 +1,8 @@
 use crate::{
-    entity::recipe::Recipe,
+    entity::recipe::{CapitalInput, Outcome, Output, Recipe, Yield},
+    shock::{ScheduledShock, Shock, Trigger},
     ware::{Ware, WareAmount, WareType},
+    world::Tick,
 };
 use std::str::FromStr;
 
@@ -50,55 +52,351 @@ impl FromStr for Ware {
 impl FromStr for Recipe {
     type Err = String;
 
+    /// Parses a recipe from the format '({inputs}) -> ({outputs})', optionally followed by 'in {duration} ticks'.
+    /// Inputs and outputs are lists of wares separated by ';'.
+    /// Inputs starting with '@' are capital inputs, which are required but not consumed.
+    ///
+    /// Output amounts may be random: '2x Food 80%' yields 2 Food with a probability of 80%,
+    /// '1-4x Food' yields between 1 and 4 Food uniformly, and '~2.5x Food' yields Poisson distributed Food.
+    /// Alternatively, the outputs may be several weighted outcomes in the format '{weight}:({outputs}) | ...'.
+    /// Required recipes, whose starvation harms the entity, are prefixed with '!'.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use model::{entity::recipe::Recipe, ware::*};
+    /// use std::str::FromStr;
+    ///
+    /// let recipe = Recipe::from_str("(1x Soil; 2x Water) -> (3x Food) in 4 ticks").unwrap();
+    /// assert_eq!(recipe.inputs(), &[Ware::new(WareType::Soil, 1), Ware::new(WareType::Water, 2)]);
+    /// assert_eq!(recipe.fixed_outputs(), Some(vec![Ware::new(WareType::Food, 3)]));
+    /// assert_eq!(recipe.duration(), 4);
+    /// assert_eq!(Ok(recipe.clone()), Recipe::from_str(&recipe.to_string()));
+    ///
+    /// let recipe = Recipe::from_str("(1x Soil; @1x Plough/20; @1x Well) -> (2x Food)").unwrap();
+    /// assert_eq!(recipe.inputs(), &[Ware::new(WareType::Soil, 1)]);
+    /// assert_eq!(recipe.capital_inputs()[0].lifetime(), Some(20));
+    /// assert_eq!(recipe.capital_inputs()[1].lifetime(), None);
+    /// assert_eq!(Ok(recipe.clone()), Recipe::from_str(&recipe.to_string()));
+    ///
+    /// let recipe = Recipe::from_str("(1x Soil) -> 3:(2x Food 80%; 1-4x Water) | 1:(~0.5x Soil)").unwrap();
+    /// assert_eq!(recipe.outcomes().len(), 2);
+    /// assert_eq!(recipe.outcomes()[0].weight(), 3);
+    /// assert_eq!(recipe.fixed_outputs(), None);
+    /// assert_eq!(Ok(recipe.clone()), Recipe::from_str(&recipe.to_string()));
+    ///
+    /// let recipe = Recipe::from_str("!(1x Food) -> ()").unwrap();
+    /// assert!(recipe.is_required());
+    /// assert_eq!(Ok(recipe.clone()), Recipe::from_str(&recipe.to_string()));
+    ///
+    /// assert_eq!(Recipe::from_str("(1x Food) -> ()").unwrap().duration(), 0);
+    /// assert!(Recipe::from_str("(1x Food) -> (1x Food 120%)").is_err());
+    /// assert!(Recipe::from_str("(1x Food) -> (1-2x Food 50%)").is_err());
+    /// assert!(Recipe::from_str("(1x Food) -> () in soon").is_err());
+    /// ```
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let s = s.trim();
+        let (required, s) = match s.strip_prefix('!') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
         let arrow = match s.find("->") {
             Some(arrow) => arrow,
-            None => return Err(format!("Missing arrow (->) in recipe declaration")),
-        };
-
-        let inputs = s[..arrow].trim();
-        let inputs = &inputs[1..inputs.len() - 1];
-        let input_results: Vec<_> = inputs
-            .split(";")
-            .filter_map(|s| {
-                let s = s.trim();
-                if s.is_empty() {
-                    None
-                } else {
-                    Some(Ware::from_str(s.trim()))
-                }
-            })
-            .collect();
+            None => return Err("Missing arrow (->) in recipe declaration".to_owned()),
+        };
+
         let mut inputs = Vec::new();
-        for result in input_results {
-            match result {
-                Ok(w) => inputs.push(w),
-                Err(e) => return Err(format!("Could not parse ware declarations: {}", e)),
+        let mut capital_inputs = Vec::new();
+        for input in parse_list(&s[..arrow])? {
+            if input.starts_with('@') {
+                capital_inputs.push(CapitalInput::from_str(input)?);
+            } else {
+                inputs.push(parse_ware_declaration(input)?);
             }
         }
 
-        let outputs = s[arrow + 2..].trim();
-        let outputs = &outputs[1..outputs.len() - 1];
-        let output_results: Vec<_> = outputs
-            .split(";")
-            .filter_map(|s| {
-                let s = s.trim();
-                if s.is_empty() {
-                    None
-                } else {
-                    Some(Ware::from_str(s.trim()))
+        let rest = s[arrow + 2..].trim();
+        let end_of_outputs = match rest.rfind(')') {
+            Some(end_of_outputs) => end_of_outputs,
+            None => return Err(format!("Missing ')' after recipe outputs: '{}'", rest)),
+        };
+        let outcomes = rest[..=end_of_outputs]
+            .split('|')
+            .map(Outcome::from_str)
+            .collect::<Result<_, _>>()?;
+
+        let mut recipe = Recipe::with_outcomes(inputs, outcomes);
+        recipe.set_capital_inputs(capital_inputs);
+        recipe.set_required(required);
+        let duration = rest[end_of_outputs + 1..].trim();
+        if !duration.is_empty() {
+            recipe.set_duration(parse_duration(duration)?);
+        }
+        Ok(recipe)
+    }
+}
+
+impl FromStr for CapitalInput {
+    type Err = String;
+
+    /// Parses a capital input from the format '@{ware}', optionally followed by '/{lifetime}'.
+    /// The lifetime is the number of uses after which one unit of the ware is worn out.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = match s.strip_prefix('@') {
+            Some(s) => s,
+            None => return Err(format!("Capital input does not start with '@': '{}'", s)),
+        };
+        match s.find('/') {
+            Some(slash) => {
+                let lifetime = match s[slash + 1..].trim().parse() {
+                    Ok(0) | Err(_) => {
+                        return Err(format!(
+                            "Could not parse lifetime: '{}'",
+                            s[slash + 1..].trim()
+                        ))
+                    }
+                    Ok(lifetime) => lifetime,
+                };
+                Ok(CapitalInput::new(
+                    parse_ware_declaration(&s[..slash])?,
+                    Some(lifetime),
+                ))
+            }
+            None => Ok(CapitalInput::new(parse_ware_declaration(s)?, None)),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    /// Parses an outcome from the format '{weight}:({outputs})', or '({outputs})' with a weight of one.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (weight, outputs) = match s.find(':') {
+            Some(colon) => match s[..colon].trim().parse() {
+                Ok(weight) if weight > 0 => (weight, &s[colon + 1..]),
+                _ => {
+                    return Err(format!(
+                        "Could not parse outcome weight: '{}'",
+                        s[..colon].trim()
+                    ))
                 }
-            })
-            .collect();
-        let mut outputs = Vec::new();
-        for result in output_results {
-            match result {
-                Ok(w) => outputs.push(w),
-                Err(e) => return Err(format!("Could not parse ware declarations: {}", e)),
+            },
+            None => (1, s),
+        };
+
+        let outputs = parse_list(outputs)?
+            .into_iter()
+            .map(Output::from_str)
+            .collect::<Result<_, _>>()?;
+        Ok(Outcome::new(weight, outputs))
+    }
+}
+
+impl FromStr for Output {
+    type Err = String;
+
+    /// Parses an output from the format '{amount}x {ware_type}', optionally followed by '{probability}%'.
+    /// The amount is either a fixed amount, a range '{min}-{max}' or a Poisson mean '~{mean}'.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let x = match s.find('x') {
+            Some(x) => x,
+            None => return Err(format!("End of amount delimiter 'x' not found: '{}'", s)),
+        };
+
+        let mut words = s[x + 1..].split_whitespace();
+        let ware_type: WareType = match words.next().map(str::parse) {
+            Some(Ok(ware_type)) => ware_type,
+            _ => {
+                return Err(format!(
+                    "Could not parse ware_type: '{}'",
+                    s[x + 1..].trim()
+                ))
             }
+        };
+        let chance = words.next();
+        if words.next().is_some() {
+            return Err(format!("Unexpected trailing words in output: '{}'", s));
+        }
+
+        let amount = s[..x].trim();
+        let amount = if let Some(mean) = amount.strip_prefix('~') {
+            match mean.parse::<f64>() {
+                Ok(mean) if mean >= 0.0 && mean.is_finite() => Yield::Poisson(mean),
+                _ => return Err(format!("Could not parse Poisson mean: '{}'", amount)),
+            }
+        } else if let Some(dash) = amount.find('-') {
+            match (amount[..dash].parse(), amount[dash + 1..].parse()) {
+                (Ok(min), Ok(max)) if min <= max => Yield::Uniform(min, max),
+                _ => return Err(format!("Could not parse amount range: '{}'", amount)),
+            }
+        } else {
+            match amount.parse() {
+                Ok(amount) => Yield::Fixed(amount),
+                Err(_) => return Err(format!("Could not parse amount: '{}'", amount)),
+            }
+        };
+
+        let amount = match (amount, chance) {
+            (amount, None) => amount,
+            (Yield::Fixed(amount), Some(chance)) if chance.ends_with('%') => {
+                match chance[..chance.len() - 1].parse::<f64>() {
+                    Ok(percent) if (0.0..=100.0).contains(&percent) => {
+                        Yield::Chance(amount, percent)
+                    }
+                    _ => return Err(format!("Could not parse probability: '{}'", chance)),
+                }
+            }
+            _ => {
+                return Err(format!(
+                    "Only fixed amounts can have a probability in percent: '{}'",
+                    s
+                ))
+            }
+        };
+        Ok(Output::new(ware_type, amount))
+    }
+}
+
+impl FromStr for ScheduledShock {
+    type Err = String;
+
+    /// Parses a scheduled shock from the format '{trigger}: {shock}', optionally followed by 'for {ticks} ticks'
+    /// for shocks that last.
+    /// The trigger is either 'at {tick}' or a chance per tick '{probability}%'.
+    /// The shock is one of 'output {ware_type} {percent}%', 'disable {recipe}', 'cull {percent}%'
+    /// or 'windfall {ware}', which scale the output of a ware type, make a recipe unavailable,
+    /// remove a share of the households, or give a ware to every household.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use model::{shock::*, ware::*};
+    /// use std::str::FromStr;
+    ///
+    /// let drought = ScheduledShock::from_str("at 10: output Water 50% for 5 ticks").unwrap();
+    /// assert_eq!(Trigger::At(10), drought.trigger());
+    /// assert_eq!(&Shock::Output(WareType::Water, 0.5), drought.shock());
+    /// assert_eq!(Some(5), drought.duration());
+    ///
+    /// let plague = ScheduledShock::from_str("1%: cull 20%").unwrap();
+    /// assert_eq!(Trigger::Chance(0.01), plague.trigger());
+    /// assert_eq!(&Shock::Cull(0.2), plague.shock());
+    ///
+    /// for shock in &["at 3: windfall 100x Money", "at 20: disable (2x Labor) -> (1x Food) in 2 ticks for 1 tick"] {
+    ///     assert_eq!(shock.to_string(), ScheduledShock::from_str(shock).unwrap().to_string());
+    /// }
+    /// assert!(ScheduledShock::from_str("at noon: cull 20%").is_err());
+    /// assert!(ScheduledShock::from_str("at 1: flood").is_err());
+    /// assert!(ScheduledShock::from_str("at 1: cull 20% for 2 ticks").is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let colon = match s.find(':') {
+            Some(colon) => colon,
+            None => return Err(format!("Missing ':' after shock trigger: '{}'", s)),
+        };
+
+        let trigger = s[..colon].trim();
+        let trigger = if let Some(tick) = trigger.strip_prefix("at ") {
+            match tick.trim().parse() {
+                Ok(tick) => Trigger::At(tick),
+                Err(_) => return Err(format!("Could not parse tick: '{}'", tick.trim())),
+            }
+        } else {
+            Trigger::Chance(parse_percent(trigger)?)
+        };
+
+        let mut shock = s[colon + 1..].trim();
+        let mut duration = None;
+        if let Some(for_index) = shock.rfind(" for ") {
+            let mut words = shock[for_index + 5..].split_whitespace();
+            duration = match (words.next(), words.next(), words.next()) {
+                (Some(ticks), Some("tick"), None) | (Some(ticks), Some("ticks"), None) => {
+                    match ticks.parse() {
+                        Ok(0) | Err(_) => {
+                            return Err(format!("Could not parse duration: '{}'", ticks))
+                        }
+                        Ok(ticks) => Some(ticks),
+                    }
+                }
+                _ => {
+                    return Err(format!(
+                        "Expected duration in the format 'for {{ticks}} ticks': '{}'",
+                        &shock[for_index + 1..]
+                    ))
+                }
+            };
+            shock = shock[..for_index].trim();
         }
 
-        Ok(Recipe::new(inputs, outputs))
+        let (kind, rest) = match shock.find(' ') {
+            Some(space) => (&shock[..space], shock[space + 1..].trim()),
+            None => (shock, ""),
+        };
+        let shock = match kind {
+            "output" => {
+                let mut words = rest.split_whitespace();
+                match (words.next().map(str::parse), words.next(), words.next()) {
+                    (Some(Ok(ware_type)), Some(factor), None) => {
+                        Shock::Output(ware_type, parse_percent(factor)?)
+                    }
+                    _ => return Err(format!("Could not parse output shock: '{}'", rest)),
+                }
+            }
+            "disable" => Shock::Disable(Recipe::from_str(rest)?),
+            "cull" => Shock::Cull(parse_percent(rest)?),
+            "windfall" => Shock::Windfall(parse_ware_declaration(rest)?),
+            _ => return Err(format!("Unknown shock: '{}'", shock)),
+        };
+        if duration.is_some() && !shock.is_lasting() {
+            return Err(format!("Only output and disable shocks can last: '{}'", s));
+        }
+        Ok(ScheduledShock::new(trigger, shock, duration))
+    }
+}
+
+/// Parses a fraction from the format '{percent}%'.
+fn parse_percent(s: &str) -> Result<f64, String> {
+    let s = s.trim();
+    match s.strip_suffix('%').map(str::parse::<f64>) {
+        Some(Ok(percent)) if percent >= 0.0 && percent.is_finite() => Ok(percent / 100.0),
+        _ => Err(format!("Could not parse percentage: '{}'", s)),
+    }
+}
+
+/// Splits a list from the format '({item}; {item}; ...)' into its non-empty items.
+fn parse_list(s: &str) -> Result<Vec<&str>, String> {
+    let s = s.trim();
+    if !s.starts_with('(') || !s.ends_with(')') || s.len() < 2 {
+        return Err(format!("List is not enclosed in parentheses: '{}'", s));
+    }
+
+    Ok(s[1..s.len() - 1]
+        .split(';')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect())
+}
+
+fn parse_ware_declaration(s: &str) -> Result<Ware, String> {
+    Ware::from_str(s).map_err(|e| format!("Could not parse ware declarations: {}", e))
+}
+
+/// Parses a duration from the format 'in {ticks} ticks'.
+fn parse_duration(s: &str) -> Result<Tick, String> {
+    let mut words = s.split_whitespace();
+    match (words.next(), words.next(), words.next(), words.next()) {
+        (Some("in"), Some(ticks), Some("tick"), None)
+        | (Some("in"), Some(ticks), Some("ticks"), None) => ticks
+            .parse()
+            .map_err(|_| format!("Could not parse duration: '{}'", ticks)),
+        _ => Err(format!(
+            "Expected duration in the format 'in {{ticks}} ticks': '{}'",
+            s
+        )),
     }
 }
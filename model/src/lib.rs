@@ -6,12 +6,21 @@ extern crate log;
 extern crate custom_derive;
 #[macro_use]
 extern crate enum_derive;
+extern crate rand;
 
+pub mod arena;
+pub mod contract;
 pub mod display;
 pub mod entity;
+pub mod event;
 pub mod from_str;
 pub mod market;
+pub mod population;
+pub mod price_index;
 pub mod prices;
+pub mod region;
+pub mod resource;
+pub mod shock;
 pub mod templates;
 pub mod ware;
 pub mod world;
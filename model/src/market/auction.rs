@@ -0,0 +1,311 @@
+use crate::{
+    arena::Index,
+    market::fill::Fill,
+    ware::{Lot, Ware, WareAmount, WareType},
+    world::EntityId,
+};
+
+pub type AuctionId = Index;
+
+/// How the winner and the price of an auction are determined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuctionFormat {
+    /// Bids must raise the highest bid by at least the increment.
+    /// The auction closes after a round without bids, and the highest bidder pays its bid.
+    English(WareAmount),
+    /// Starts at the given price, which falls by the decrement every round until it reaches the reserve price.
+    /// The first bidder that accepts the current price wins and pays it.
+    Dutch(WareAmount, WareAmount),
+    /// Bidders place one sealed bid in the first round, and the highest bidder pays its bid.
+    FirstPrice,
+    /// Bidders place one sealed bid in the first round,
+    /// and the highest bidder pays the second highest bid, or the reserve price if there is none.
+    Vickrey,
+}
+
+/// A bid of an entity in an auction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bid {
+    bidder: EntityId,
+    price: WareAmount,
+}
+
+/// An auction of a single indivisible lot.
+/// Bids are placed in rounds, and the lot is only sold for at least the reserve price.
+/// All prices are per ware of the lot, like the prices of offers in a market.
+/// While the auction is open in a world, it holds the lot of the seller and the standing bids of the bidders.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Auction {
+    seller: EntityId,
+    lot: Ware,
+    lot_lots: Vec<Lot>,
+    deposits: Vec<Bid>,
+    reserve: Ware,
+    format: AuctionFormat,
+    bids: Vec<Bid>,
+    round: u32,
+    round_bids: u32,
+    closed: bool,
+    winner: Option<Bid>,
+}
+
+impl Bid {
+    pub fn new(bidder: EntityId, price: WareAmount) -> Self {
+        Self { bidder, price }
+    }
+
+    pub fn bidder(&self) -> EntityId {
+        self.bidder
+    }
+
+    pub fn price(&self) -> WareAmount {
+        self.price
+    }
+}
+
+// Creators
+impl Auction {
+    /// Creates an auction in which the seller sells the lot for at least the reserve price.
+    pub fn new(seller: EntityId, lot: Ware, reserve: Ware, format: AuctionFormat) -> Self {
+        debug_assert!(reserve.is_currency());
+
+        Self {
+            seller,
+            lot,
+            lot_lots: Vec::new(),
+            deposits: Vec::new(),
+            reserve,
+            format,
+            bids: Vec::new(),
+            round: 0,
+            round_bids: 0,
+            closed: false,
+            winner: None,
+        }
+    }
+}
+
+// Modifiers
+impl Auction {
+    /// Places a bid for the whole lot at the given price.
+    /// In an English auction the price must be at least the current price, and in a Dutch auction
+    /// the first bid of at least the current price accepts it, and the auction closes at the end of the round.
+    /// Sealed bids must be placed in the first round, and replace earlier bids of the bidder.
+    /// Fails if the auction is closed, the bidder is the seller, or the bid is too low.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use model::{market::auction::*, ware::{Ware, WareType}, world::World};
+    ///
+    /// let mut world = World::new();
+    /// let ids: Vec<_> = (0..4).map(|_| world.create_entity("Bidder", &[])).collect();
+    /// let lot = Ware::new(WareType::Well, 1);
+    /// let mut auction = Auction::new(ids[0], lot, Ware::money(10), AuctionFormat::Vickrey);
+    /// auction.bid(ids[1], 30).unwrap();
+    /// auction.bid(ids[2], 20).unwrap();
+    /// auction.bid(ids[3], 5).unwrap();
+    /// assert_eq!(Err(()), auction.bid(ids[0], 50));
+    ///
+    /// auction.end_round();
+    /// let fill = auction.fill().unwrap();
+    /// assert_eq!(ids[1], fill.buyer());
+    /// assert_eq!(&Ware::money(20), fill.price_per_ware());
+    /// assert_eq!(Err(()), auction.bid(ids[2], 40));
+    /// ```
+    pub fn bid(&mut self, bidder: EntityId, price: WareAmount) -> Result<(), ()> {
+        if self.closed || bidder == self.seller {
+            return Err(());
+        }
+
+        match self.format {
+            AuctionFormat::English(_) => {
+                if price < self.current_price() {
+                    return Err(());
+                }
+                self.bids.push(Bid::new(bidder, price));
+            }
+            AuctionFormat::Dutch(..) => {
+                if self.winner.is_some() || price < self.current_price() {
+                    return Err(());
+                }
+                self.winner = Some(Bid::new(bidder, self.current_price()));
+            }
+            AuctionFormat::FirstPrice | AuctionFormat::Vickrey => {
+                if self.round > 0 {
+                    return Err(());
+                }
+                self.bids.retain(|bid| bid.bidder != bidder);
+                self.bids.push(Bid::new(bidder, price));
+            }
+        }
+        self.round_bids += 1;
+        Ok(())
+    }
+
+    /// Ends the current round of bidding. Closes the auction if its format says so, and determines the winner.
+    pub fn end_round(&mut self) {
+        if self.closed {
+            return;
+        }
+
+        let reserve = self.reserve.amount();
+        match self.format {
+            AuctionFormat::English(_) => {
+                if self.round_bids == 0 {
+                    self.winner = self.highest_bid();
+                    self.closed = true;
+                }
+            }
+            AuctionFormat::Dutch(..) => {
+                if self.winner.is_some() || self.current_price() <= reserve {
+                    self.closed = true;
+                }
+            }
+            AuctionFormat::FirstPrice => {
+                self.winner = self.highest_bid().filter(|bid| bid.price >= reserve);
+                self.closed = true;
+            }
+            AuctionFormat::Vickrey => {
+                self.winner = self
+                    .highest_bid()
+                    .filter(|bid| bid.price >= reserve)
+                    .map(|bid| {
+                        let second = self
+                            .bids
+                            .iter()
+                            .filter(|other| other.bidder != bid.bidder)
+                            .map(|other| other.price)
+                            .max()
+                            .unwrap_or(0);
+                        Bid::new(bid.bidder, second.max(reserve))
+                    });
+                self.closed = true;
+            }
+        }
+        self.round += 1;
+        self.round_bids = 0;
+    }
+
+    /// Cancels the auction without a sale, for example if the winner cannot pay.
+    pub fn cancel(&mut self) {
+        self.closed = true;
+        self.winner = None;
+    }
+
+    /// Keeps the ages of the lot while it is held by the auction.
+    pub fn hold_lot(&mut self, lots: Vec<Lot>) {
+        self.lot_lots = lots;
+    }
+
+    /// Takes the ages of the held lot.
+    pub fn take_lot_lots(&mut self) -> Vec<Lot> {
+        std::mem::take(&mut self.lot_lots)
+    }
+
+    /// Replaces the bids whose price is held by the auction, and returns the previous ones.
+    pub fn replace_deposits(&mut self, deposits: Vec<Bid>) -> Vec<Bid> {
+        std::mem::replace(&mut self.deposits, deposits)
+    }
+}
+
+// Getters
+impl Auction {
+    pub fn seller(&self) -> EntityId {
+        self.seller
+    }
+
+    pub fn lot(&self) -> &Ware {
+        &self.lot
+    }
+
+    pub fn currency(&self) -> WareType {
+        self.reserve.ware_type()
+    }
+
+    pub fn reserve(&self) -> &Ware {
+        &self.reserve
+    }
+
+    pub fn format(&self) -> AuctionFormat {
+        self.format
+    }
+
+    /// The bids placed so far, in order. Dutch auctions only record their winner.
+    pub fn bids(&self) -> &[Bid] {
+        &self.bids
+    }
+
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// The winner and the price it pays, once it is known.
+    pub fn winner(&self) -> Option<Bid> {
+        self.winner
+    }
+
+    /// The bids whose price is held by the auction.
+    pub fn deposits(&self) -> &[Bid] {
+        &self.deposits
+    }
+
+    /// The price per ware held by the auction for the bidder.
+    pub fn deposit_of(&self, bidder: EntityId) -> WareAmount {
+        self.deposits
+            .iter()
+            .find(|deposit| deposit.bidder == bidder)
+            .map_or(0, |deposit| deposit.price)
+    }
+
+    /// The bids that can still win, and whose price must therefore be held.
+    /// These are the highest bid of an open English auction, every sealed bid of an open sealed-bid auction,
+    /// and the winner otherwise.
+    pub fn standing_bids(&self) -> Vec<Bid> {
+        match self.format {
+            AuctionFormat::English(_) if !self.closed => self.highest_bid().into_iter().collect(),
+            AuctionFormat::FirstPrice | AuctionFormat::Vickrey if !self.closed => self.bids.clone(),
+            _ => self.winner.into_iter().collect(),
+        }
+    }
+
+    /// The lowest price a bid must have to be accepted now.
+    /// For sealed bids this is the reserve price, although lower bids are accepted and lose.
+    pub fn current_price(&self) -> WareAmount {
+        match self.format {
+            AuctionFormat::English(increment) => self
+                .highest_bid()
+                .map_or(self.reserve.amount(), |bid| bid.price + increment.max(1)),
+            AuctionFormat::Dutch(start, decrement) => start
+                .saturating_sub(decrement.saturating_mul(self.round))
+                .max(self.reserve.amount()),
+            AuctionFormat::FirstPrice | AuctionFormat::Vickrey => self.reserve.amount(),
+        }
+    }
+
+    /// The highest bid, where the earliest of equal bids wins.
+    pub fn highest_bid(&self) -> Option<Bid> {
+        self.bids
+            .iter()
+            .fold(None, |highest: Option<Bid>, &bid| match highest {
+                Some(highest) if highest.price >= bid.price => Some(highest),
+                _ => Some(bid),
+            })
+    }
+
+    /// The sale of the lot to the winner, once the auction is closed with a sale.
+    pub fn fill(&self) -> Option<Fill> {
+        self.winner.filter(|_| self.closed).map(|winner| {
+            Fill::new(
+                self.lot.clone(),
+                Ware::new(self.currency(), winner.price),
+                winner.bidder,
+                self.seller,
+            )
+        })
+    }
+}
@@ -0,0 +1,23 @@
+use crate::ware::WareType;
+
+/// Returns the trading pair on which the two given currencies are exchanged, as (base, quote).
+/// The more valuable currency is the base and is priced in the less valuable one,
+/// so that exchange prices are at least one unit of the quote currency.
+///
+/// # Example
+///
+/// ```
+/// use model::{market::exchange::exchange_pair, ware::WareType};
+///
+/// assert_eq!((WareType::Gold, WareType::Money), exchange_pair(WareType::Money, WareType::Gold));
+/// assert_eq!((WareType::Gold, WareType::Silver), exchange_pair(WareType::Gold, WareType::Silver));
+/// ```
+pub fn exchange_pair(a: WareType, b: WareType) -> (WareType, WareType) {
+    debug_assert!(a.is_currency() && b.is_currency());
+
+    if (a.default_price(), a) > (b.default_price(), b) {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{ware::Ware, world::EntityId};
+
+/// A trade between two entities that resulted from matching a buy offer with a sell offer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fill {
+    ware: Ware,
+    price_per_ware: Ware,
+    buyer: EntityId,
+    seller: EntityId,
+}
+
+impl Fill {
+    pub fn new(ware: Ware, price_per_ware: Ware, buyer: EntityId, seller: EntityId) -> Self {
+        Self {
+            ware,
+            price_per_ware,
+            buyer,
+            seller,
+        }
+    }
+
+    pub fn ware(&self) -> &Ware {
+        &self.ware
+    }
+
+    pub fn price_per_ware(&self) -> &Ware {
+        &self.price_per_ware
+    }
+
+    pub fn total_price(&self) -> Ware {
+        self.price_per_ware.clone() * self.ware.amount()
+    }
+
+    pub fn buyer(&self) -> EntityId {
+        self.buyer
+    }
+
+    pub fn seller(&self) -> EntityId {
+        self.seller
+    }
+}
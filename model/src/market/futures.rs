@@ -0,0 +1,252 @@
+use crate::{
+    market::offer::OfferType,
+    ware::{Ware, WareAmount, WareType},
+    world::{EntityId, Tick},
+};
+use std::collections::HashMap;
+
+/// An order to buy or sell wares for delivery at a future tick.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FuturesOrder {
+    ware: Ware,
+    offer_type: OfferType,
+    price_per_ware: Ware,
+    delivery: Tick,
+    entity_id: EntityId,
+}
+
+/// A contract under which the seller delivers the ware to the buyer at the delivery tick,
+/// and the buyer pays the price fixed when it was made.
+/// Both parties hold margin in the currency of the price, which they get back on settlement
+/// and which goes to the counterparty if they default.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Forward {
+    ware: Ware,
+    price_per_ware: Ware,
+    delivery: Tick,
+    buyer: EntityId,
+    seller: EntityId,
+    buyer_margin: WareAmount,
+    seller_margin: WareAmount,
+}
+
+/// The futures order book and the open forward contracts of a world.
+/// Orders are matched per ware type, currency and delivery tick,
+/// and both parties of a match post the margin rate times the total price as margin.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FuturesMarket {
+    orders: Vec<FuturesOrder>,
+    contracts: Vec<Forward>,
+    margin: f64,
+    last_prices: HashMap<(WareType, WareType, Tick), WareAmount>,
+}
+
+impl FuturesOrder {
+    pub fn new(
+        ware: Ware,
+        offer_type: OfferType,
+        price_per_ware: Ware,
+        delivery: Tick,
+        entity_id: EntityId,
+    ) -> Self {
+        Self {
+            ware,
+            offer_type,
+            price_per_ware,
+            delivery,
+            entity_id,
+        }
+    }
+
+    /// Reduces the amount of this order by the given amount.
+    pub fn fill(&mut self, amount: WareAmount) {
+        debug_assert!(amount <= self.amount());
+
+        *self.ware.amount_mut() -= amount;
+    }
+
+    pub fn ware(&self) -> &Ware {
+        &self.ware
+    }
+
+    pub fn amount(&self) -> WareAmount {
+        self.ware.amount()
+    }
+
+    pub fn offer_type(&self) -> OfferType {
+        self.offer_type
+    }
+
+    pub fn price_per_ware(&self) -> &Ware {
+        &self.price_per_ware
+    }
+
+    pub fn delivery(&self) -> Tick {
+        self.delivery
+    }
+
+    pub fn entity_id(&self) -> EntityId {
+        self.entity_id
+    }
+
+    /// The ware type, the currency and the delivery tick.
+    /// Orders can only be matched with orders of the same contract.
+    pub fn contract(&self) -> (WareType, WareType, Tick) {
+        (
+            self.ware.ware_type(),
+            self.price_per_ware.ware_type(),
+            self.delivery,
+        )
+    }
+
+    pub fn is_filled(&self) -> bool {
+        self.amount() == 0
+    }
+}
+
+impl Forward {
+    pub fn new(
+        ware: Ware,
+        price_per_ware: Ware,
+        delivery: Tick,
+        buyer: EntityId,
+        seller: EntityId,
+    ) -> Self {
+        Self {
+            ware,
+            price_per_ware,
+            delivery,
+            buyer,
+            seller,
+            buyer_margin: 0,
+            seller_margin: 0,
+        }
+    }
+
+    /// Sets the margin held from the buyer and the seller, in the currency of the price.
+    pub fn set_margins(&mut self, buyer_margin: WareAmount, seller_margin: WareAmount) {
+        self.buyer_margin = buyer_margin;
+        self.seller_margin = seller_margin;
+    }
+
+    pub fn ware(&self) -> &Ware {
+        &self.ware
+    }
+
+    pub fn price_per_ware(&self) -> &Ware {
+        &self.price_per_ware
+    }
+
+    pub fn total_price(&self) -> Ware {
+        self.price_per_ware.clone() * self.ware.amount()
+    }
+
+    pub fn delivery(&self) -> Tick {
+        self.delivery
+    }
+
+    pub fn buyer(&self) -> EntityId {
+        self.buyer
+    }
+
+    pub fn seller(&self) -> EntityId {
+        self.seller
+    }
+
+    pub fn buyer_margin(&self) -> Ware {
+        Ware::new(self.price_per_ware.ware_type(), self.buyer_margin)
+    }
+
+    pub fn seller_margin(&self) -> Ware {
+        Ware::new(self.price_per_ware.ware_type(), self.seller_margin)
+    }
+}
+
+// Creators
+impl FuturesMarket {
+    /// Creates a futures market whose contracts require the given share of their total price as margin from each party.
+    pub fn new(margin: f64) -> Self {
+        Self {
+            margin,
+            ..Default::default()
+        }
+    }
+
+    pub fn create_order(&mut self, order: FuturesOrder) {
+        debug_assert_ne!(order.amount(), 0);
+
+        self.orders.push(order);
+    }
+}
+
+// Modifiers
+impl FuturesMarket {
+    pub fn orders_mut(&mut self) -> &mut Vec<FuturesOrder> {
+        &mut self.orders
+    }
+
+    pub fn remove_orders_of(&mut self, entity_id: EntityId) {
+        self.orders.retain(|order| order.entity_id() != entity_id);
+    }
+
+    /// Removes the orders for delivery at the given tick or before, and the orders that were filled completely.
+    pub fn remove_stale_orders(&mut self, tick: Tick) {
+        self.orders
+            .retain(|order| order.delivery() > tick && !order.is_filled());
+    }
+
+    pub fn add_contract(&mut self, contract: Forward) {
+        let (ware_type, currency) = (
+            contract.ware().ware_type(),
+            contract.price_per_ware().ware_type(),
+        );
+        self.last_prices.insert(
+            (ware_type, currency, contract.delivery()),
+            contract.price_per_ware().amount(),
+        );
+        self.contracts.push(contract);
+    }
+
+    /// Removes the contracts due for delivery at the given tick or before and returns them.
+    pub fn take_due_contracts(&mut self, tick: Tick) -> Vec<Forward> {
+        let (due, open) = std::mem::take(&mut self.contracts)
+            .into_iter()
+            .partition(|contract| contract.delivery() <= tick);
+        self.contracts = open;
+        due
+    }
+}
+
+// Getters
+impl FuturesMarket {
+    pub fn orders(&self) -> &[FuturesOrder] {
+        &self.orders
+    }
+
+    /// The open contracts that were not delivered yet.
+    pub fn contracts(&self) -> &[Forward] {
+        &self.contracts
+    }
+
+    /// The share of the total price of a contract that each party holds as margin.
+    pub fn margin(&self) -> f64 {
+        self.margin
+    }
+
+    /// The margin required for a contract with the given total price, rounded up.
+    pub fn margin_for(&self, total_price: WareAmount) -> WareAmount {
+        (f64::from(total_price) * self.margin).ceil() as WareAmount
+    }
+
+    /// The price per ware of the last contract for delivery of the ware type at the given tick.
+    pub fn last_price(
+        &self,
+        ware_type: WareType,
+        currency: WareType,
+        delivery: Tick,
+    ) -> Option<WareAmount> {
+        self.last_prices
+            .get(&(ware_type, currency, delivery))
+            .copied()
+    }
+}
@@ -1,14 +1,25 @@
 use crate::{
     market::{
+        exchange::exchange_pair,
+        fill::Fill,
         offer::{Offer, OfferType},
+        stats::TradeStats,
         ware_range_iter::{WareOfferRange, WareOfferRangeMut, WareRangeIter, WareRangeIterMut},
     },
-    ware::Ware,
+    ware::{Ware, WareAmount, WareStore, WareType},
     world::EntityId,
 };
-use std::cmp::Ordering;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+};
 
+pub mod auction;
+pub mod exchange;
+pub mod fill;
+pub mod futures;
 pub mod offer;
+pub mod stats;
 pub mod ware_range_iter;
 
 pub type OfferId = usize;
@@ -16,6 +27,8 @@ pub type OfferId = usize;
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct Market {
     offers: Vec<Offer>,
+    last_prices: HashMap<(WareType, WareType), WareAmount>,
+    trade_stats: HashMap<(WareType, WareType), TradeStats>,
 }
 
 // Creators
@@ -46,9 +59,39 @@ impl Market {
         self.offers_mut().clear();
     }
 
+    /// Reduces the amount of the offer at the given index by the given amount.
+    pub fn fill_offer(&mut self, offer_index: usize, amount: WareAmount) {
+        self.offers_mut()[offer_index].fill(amount);
+    }
+
+    pub fn remove_offers_of(&mut self, entity_id: EntityId) {
+        self.offers_mut()
+            .retain(|offer| offer.entity_id() != entity_id);
+    }
+
+    /// Removes all offers whose amount was filled completely.
+    pub fn remove_filled_offers(&mut self) {
+        self.offers_mut().retain(|offer| !offer.is_filled());
+    }
+
+    pub fn set_last_price(&mut self, ware_type: WareType, currency: WareType, price: WareAmount) {
+        self.last_prices.insert((ware_type, currency), price);
+    }
+
+    /// Records a settled trade in the statistics of its trading pair.
+    pub fn record_trade(&mut self, fill: &Fill) {
+        let pair = (fill.ware().ware_type(), fill.price_per_ware().ware_type());
+        self.trade_stats.entry(pair).or_default().record(fill);
+    }
+
+    /// Clears the trade statistics, such that they cover the trades of the next tick.
+    pub fn clear_trade_stats(&mut self) {
+        self.trade_stats.clear();
+    }
+
     pub fn sort_offers(&mut self) {
-        self.offers_mut().sort_by(
-            |a, b| match a.offer().ware_type().cmp(&b.offer().ware_type()) {
+        self.offers_mut()
+            .sort_by(|a, b| match a.trading_pair().cmp(&b.trading_pair()) {
                 Ordering::Equal => {
                     if a.offer_type() != b.offer_type() {
                         if a.offer_type() == OfferType::Buy {
@@ -63,8 +106,7 @@ impl Market {
                     }
                 }
                 o => o,
-            },
-        );
+            });
     }
 
     fn offers_mut(&mut self) -> &mut Vec<Offer> {
@@ -87,6 +129,89 @@ impl Market {
     pub fn iter_ware_ranges<'a>(&'a self) -> impl Iterator<Item = WareOfferRange<'a>> + 'a {
         WareRangeIter::from(self)
     }
+
+    /// The price per ware of the last trade of the given ware type in the given currency.
+    pub fn last_price(&self, ware_type: WareType, currency: WareType) -> Option<WareAmount> {
+        self.last_prices.get(&(ware_type, currency)).cloned()
+    }
+
+    /// The trades of the given ware type for the given currency in the last tick.
+    pub fn trade_stats(&self, ware_type: WareType, currency: WareType) -> TradeStats {
+        self.trade_stats
+            .get(&(ware_type, currency))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// The open offers of the given type for the given ware type in the given currency,
+    /// as pairs of the price per ware and the total amount offered at that price.
+    /// Buy offers are ordered from the highest price, sell offers from the lowest, such that the best price comes first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use model::{market::{offer::OfferType, Market}, ware::{Ware, WareType}, arena::Index};
+    ///
+    /// let mut market = Market::new();
+    /// let entity_id = Index::new(0, 0);
+    /// for &(amount, price) in &[(2, 5), (3, 4), (1, 5)] {
+    ///     market.create_offer(Ware::new(WareType::Food, amount), OfferType::Buy, Ware::money(price), entity_id);
+    /// }
+    /// assert_eq!(vec![(5, 3), (4, 3)], market.depth(WareType::Food, WareType::Money, OfferType::Buy));
+    /// ```
+    pub fn depth(
+        &self,
+        ware_type: WareType,
+        currency: WareType,
+        offer_type: OfferType,
+    ) -> Vec<(WareAmount, WareAmount)> {
+        let mut levels = BTreeMap::new();
+        for offer in self.offers().iter().filter(|offer| {
+            offer.trading_pair() == (ware_type, currency) && offer.offer_type() == offer_type
+        }) {
+            *levels.entry(offer.price_per_ware().amount()).or_insert(0) += offer.amount();
+        }
+        match offer_type {
+            OfferType::Buy => levels.into_iter().rev().collect(),
+            OfferType::Sell => levels.into_iter().collect(),
+        }
+    }
+
+    /// The amount of currency `to` that one unit of currency `from` was last exchanged for.
+    /// Returns None if the two currencies were never exchanged.
+    pub fn exchange_rate(&self, from: WareType, to: WareType) -> Option<f64> {
+        if from == to {
+            return Some(1.0);
+        }
+
+        let (base, quote) = exchange_pair(from, to);
+        let price = f64::from(self.last_price(base, quote)?);
+        if from == base {
+            Some(price)
+        } else {
+            Some(1.0 / price)
+        }
+    }
+
+    /// The value of one unit of the given ware type in the given currency.
+    /// Uses the last trade on this market, or the default prices if the ware was never traded for the currency.
+    pub fn value_in(&self, ware_type: WareType, currency: WareType) -> f64 {
+        if ware_type.is_currency() {
+            self.exchange_rate(ware_type, currency)
+                .unwrap_or_else(|| ware_type.default_value_in(currency))
+        } else {
+            self.last_price(ware_type, currency)
+                .map_or_else(|| ware_type.default_value_in(currency), f64::from)
+        }
+    }
+
+    /// The value of the given wares in the given currency.
+    pub fn value_of(&self, wares: &WareStore, currency: WareType) -> f64 {
+        wares
+            .iter()
+            .map(|ware| f64::from(ware.amount()) * self.value_in(ware.ware_type(), currency))
+            .sum()
+    }
 }
 
 #[cfg(test)]
@@ -94,6 +219,7 @@ mod test {
     use crate::{
         market::{offer::OfferType, *},
         ware::{Ware, WareType},
+        world::EntityId,
     };
     use rand::{distributions::Uniform, seq::SliceRandom, Rng};
     use std::cmp::Ordering;
@@ -103,6 +229,7 @@ mod test {
         let mut market = Market::new();
 
         let possible_ware_types = [WareType::Food, WareType::Water, WareType::Soil];
+        let possible_currencies = [WareType::Money, WareType::Gold];
         let possible_offer_types = [OfferType::Buy, OfferType::Sell];
         let possible_ware_amounts = Uniform::new(1, 16);
         let possible_entity_ids = Uniform::new(0, 22);
@@ -115,14 +242,17 @@ mod test {
                     rng.sample(possible_ware_amounts),
                 ),
                 possible_offer_types.choose(&mut rng).cloned().unwrap(),
-                Ware::new(WareType::Money, rng.sample(possible_ware_amounts)),
-                rng.sample(possible_entity_ids),
+                Ware::new(
+                    possible_currencies.choose(&mut rng).cloned().unwrap(),
+                    rng.sample(possible_ware_amounts),
+                ),
+                EntityId::new(rng.sample(possible_entity_ids), 0),
             );
         }
 
         market.sort_offers();
         assert!(market.offers().is_sorted_by(|a, b| Some(
-            match a.offer().ware_type().cmp(&b.offer().ware_type()) {
+            match a.trading_pair().cmp(&b.trading_pair()) {
                 Ordering::Equal => {
                     if a.offer_type() != b.offer_type() {
                         if a.offer_type() == OfferType::Buy {
@@ -140,4 +270,25 @@ mod test {
             }
         )));
     }
+
+    #[test]
+    fn test_exchange_rate() {
+        let mut market = Market::new();
+        assert_eq!(None, market.exchange_rate(WareType::Money, WareType::Gold));
+        assert_eq!(
+            Some(1.0),
+            market.exchange_rate(WareType::Gold, WareType::Gold)
+        );
+
+        market.set_last_price(WareType::Gold, WareType::Money, 8);
+        assert_eq!(
+            Some(8.0),
+            market.exchange_rate(WareType::Gold, WareType::Money)
+        );
+        assert_eq!(
+            Some(0.125),
+            market.exchange_rate(WareType::Money, WareType::Gold)
+        );
+        assert_eq!(None, market.exchange_rate(WareType::Silver, WareType::Gold));
+    }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    ware::{Ware, WareAmount},
+    ware::{Ware, WareAmount, WareType},
     world::EntityId,
 };
 
@@ -44,6 +44,17 @@ impl Offer {
         self.offer.amount()
     }
 
+    /// The currency the offer is priced in.
+    pub fn currency(&self) -> WareType {
+        self.price_per_ware.ware_type()
+    }
+
+    /// The pair of the offered ware type and the currency it is priced in.
+    /// Offers can only be matched with offers of the same trading pair.
+    pub fn trading_pair(&self) -> (WareType, WareType) {
+        (self.offer.ware_type(), self.currency())
+    }
+
     pub fn price_per_ware(&self) -> &Ware {
         &self.price_per_ware
     }
@@ -57,4 +68,15 @@ impl Offer {
     pub fn entity_id(&self) -> EntityId {
         self.entity_id
     }
+
+    /// Reduces the amount of this offer by the given amount.
+    pub fn fill(&mut self, amount: WareAmount) {
+        debug_assert!(amount <= self.amount());
+
+        *self.offer.amount_mut() -= amount;
+    }
+
+    pub fn is_filled(&self) -> bool {
+        self.amount() == 0
+    }
 }
@@ -0,0 +1,106 @@
+use crate::{market::fill::Fill, ware::WareAmount};
+
+/// The trades of one trading pair in the last tick.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TradeStats {
+    volume: WareAmount,
+    turnover: WareAmount,
+    trades: u32,
+}
+
+/// The labor market in the last tick.
+/// Workers are entities that supply labor every tick, and are employed if they sold any of it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LaborStats {
+    workers: u32,
+    employed_workers: u32,
+    hours_supplied: WareAmount,
+    hours_employed: WareAmount,
+    wage: Option<f64>,
+}
+
+impl TradeStats {
+    pub fn record(&mut self, fill: &Fill) {
+        self.volume += fill.ware().amount();
+        self.turnover += fill.total_price().amount();
+        self.trades += 1;
+    }
+
+    /// The amount of wares traded.
+    pub fn volume(&self) -> WareAmount {
+        self.volume
+    }
+
+    /// The amount of currency paid for the traded wares.
+    pub fn turnover(&self) -> WareAmount {
+        self.turnover
+    }
+
+    pub fn trades(&self) -> u32 {
+        self.trades
+    }
+
+    /// The average price per ware, or None if nothing was traded.
+    pub fn average_price(&self) -> Option<f64> {
+        if self.volume == 0 {
+            None
+        } else {
+            Some(f64::from(self.turnover) / f64::from(self.volume))
+        }
+    }
+}
+
+impl LaborStats {
+    pub fn new(
+        workers: u32,
+        employed_workers: u32,
+        hours_supplied: WareAmount,
+        hours_employed: WareAmount,
+        wage: Option<f64>,
+    ) -> Self {
+        debug_assert!(employed_workers <= workers);
+        debug_assert!(hours_employed <= hours_supplied);
+
+        Self {
+            workers,
+            employed_workers,
+            hours_supplied,
+            hours_employed,
+            wage,
+        }
+    }
+
+    pub fn workers(&self) -> u32 {
+        self.workers
+    }
+
+    pub fn employed_workers(&self) -> u32 {
+        self.employed_workers
+    }
+
+    pub fn unemployed_workers(&self) -> u32 {
+        self.workers - self.employed_workers
+    }
+
+    /// The share of workers that sold none of their labor, or zero if there are no workers.
+    pub fn unemployment_rate(&self) -> f64 {
+        if self.workers == 0 {
+            0.0
+        } else {
+            f64::from(self.unemployed_workers()) / f64::from(self.workers)
+        }
+    }
+
+    pub fn hours_supplied(&self) -> WareAmount {
+        self.hours_supplied
+    }
+
+    pub fn hours_employed(&self) -> WareAmount {
+        self.hours_employed
+    }
+
+    /// The average wage per hour of labor in money, or None if no labor was sold.
+    pub fn wage(&self) -> Option<f64> {
+        self.wage
+    }
+}
@@ -36,7 +36,7 @@ impl<'a> Iterator for WareRangeIter<'a> {
                 .iter()
                 .skip(self.index)
                 .take_while(|offer| {
-                    offer.offer().ware_type() == first_offer.offer().ware_type()
+                    offer.trading_pair() == first_offer.trading_pair()
                         && offer.offer_type() == OfferType::Buy
                 })
                 .count();
@@ -47,7 +47,7 @@ impl<'a> Iterator for WareRangeIter<'a> {
                 .iter()
                 .skip(self.index)
                 .take_while(|offer| {
-                    offer.offer().ware_type() == first_offer.offer().ware_type()
+                    offer.trading_pair() == first_offer.trading_pair()
                         && offer.offer_type() == OfferType::Sell
                 })
                 .count();
@@ -107,13 +107,11 @@ impl<'a> Iterator for WareRangeIterMut<'a> {
             let count = self
                 .offers
                 .iter()
-                .skip(1)
                 .take_while(|offer| {
-                    offer.offer().ware_type() == first_offer.offer().ware_type()
+                    offer.trading_pair() == first_offer.trading_pair()
                         && offer.offer_type() == OfferType::Buy
                 })
-                .count()
-                + 1;
+                .count();
             let tmp = mem::replace(&mut self.offers, &mut []);
             let (buy_offers, new_offers) = tmp.split_at_mut(count);
             self.offers = new_offers;
@@ -122,11 +120,10 @@ impl<'a> Iterator for WareRangeIterMut<'a> {
                 .offers
                 .iter()
                 .take_while(|offer| {
-                    offer.offer().ware_type() == first_offer.offer().ware_type()
+                    offer.trading_pair() == first_offer.trading_pair()
                         && offer.offer_type() == OfferType::Sell
                 })
-                .count()
-                + 1;
+                .count();
             let tmp = mem::replace(&mut self.offers, &mut []);
             let (sell_offers, new_offers) = tmp.split_at_mut(count);
             self.offers = new_offers;
@@ -0,0 +1,164 @@
+use crate::{
+    entity::recipe::Recipe,
+    region::RegionId,
+    ware::{Ware, WareType},
+    world::Tick,
+};
+
+/// A rule under which entities enter or leave the world.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PopulationRule {
+    Birth(Birth),
+    Entry(Entry),
+    Exit(Exit),
+}
+
+/// Households that are well fed reproduce.
+/// A household is well fed if it is at full health and its utility level reached the given minimum.
+/// The child has the same recipes, utility function and currency as its parent,
+/// and receives the given fraction of the currency of its parent.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Birth {
+    chance: f64,
+    min_utility_level: f64,
+    endowment: f64,
+}
+
+/// New producers enter the market of a region when the price of a ware there stays above the cost of producing it.
+/// The producer is created in that region from the given name and recipes and starts with the given wares.
+/// The cost of the ware is the market price of the inputs of the recipes that yield it,
+/// per expected unit of output.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    ware_type: WareType,
+    region: RegionId,
+    name: String,
+    recipes: Vec<Recipe>,
+    endowment: Vec<Ware>,
+    patience: Tick,
+    chance: f64,
+    ticks_above_cost: Tick,
+}
+
+/// Producers exit the market when their wealth fell on average over the given number of ticks.
+/// Producers that break even stay, and noise in their wealth is averaged out over the window.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Exit {
+    window: Tick,
+}
+
+impl Birth {
+    /// Creates a birth rule where well-fed households have a child with the given probability per tick.
+    pub fn new(chance: f64, min_utility_level: f64, endowment: f64) -> Self {
+        debug_assert!((0.0..=1.0).contains(&chance));
+        debug_assert!((0.0..=1.0).contains(&endowment));
+
+        Self {
+            chance,
+            min_utility_level,
+            endowment,
+        }
+    }
+
+    pub fn chance(&self) -> f64 {
+        self.chance
+    }
+
+    pub fn min_utility_level(&self) -> f64 {
+        self.min_utility_level
+    }
+
+    pub fn endowment(&self) -> f64 {
+        self.endowment
+    }
+}
+
+impl Entry {
+    /// Creates an entry rule where a producer enters the given region with the given probability per tick,
+    /// once the price of the ware in its market was above its cost for the given number of ticks.
+    pub fn new(
+        ware_type: WareType,
+        region: RegionId,
+        name: &str,
+        recipes: Vec<Recipe>,
+        endowment: Vec<Ware>,
+        patience: Tick,
+        chance: f64,
+    ) -> Self {
+        debug_assert!((0.0..=1.0).contains(&chance));
+
+        Self {
+            ware_type,
+            region,
+            name: name.to_owned(),
+            recipes,
+            endowment,
+            patience,
+            chance,
+            ticks_above_cost: 0,
+        }
+    }
+
+    /// Records whether the price of the ware was above its cost in this tick.
+    pub fn record_margin(&mut self, above_cost: bool) {
+        if above_cost {
+            self.ticks_above_cost += 1;
+        } else {
+            self.ticks_above_cost = 0;
+        }
+    }
+
+    /// Restarts the count of ticks the price was above the cost, after a producer entered.
+    pub fn reset(&mut self) {
+        self.ticks_above_cost = 0;
+    }
+
+    pub fn ware_type(&self) -> WareType {
+        self.ware_type
+    }
+
+    pub fn region(&self) -> RegionId {
+        self.region
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn recipes(&self) -> &[Recipe] {
+        &self.recipes
+    }
+
+    pub fn endowment(&self) -> &[Ware] {
+        &self.endowment
+    }
+
+    pub fn patience(&self) -> Tick {
+        self.patience
+    }
+
+    pub fn chance(&self) -> f64 {
+        self.chance
+    }
+
+    pub fn ticks_above_cost(&self) -> Tick {
+        self.ticks_above_cost
+    }
+
+    /// True if the price was above the cost for long enough for a producer to enter.
+    pub fn is_open(&self) -> bool {
+        self.ticks_above_cost >= self.patience
+    }
+}
+
+impl Exit {
+    pub fn new(window: Tick) -> Self {
+        debug_assert!(window > 0);
+
+        Self { window }
+    }
+
+    pub fn window(&self) -> Tick {
+        self.window
+    }
+}
@@ -0,0 +1,71 @@
+use crate::ware::{Ware, WareType};
+
+/// The cost of a basket of wares at market prices, relative to its cost when it was first recorded.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PriceIndex {
+    basket: Vec<Ware>,
+    currency: WareType,
+    base_cost: Option<f64>,
+    history: Vec<f64>,
+}
+
+impl PriceIndex {
+    pub fn new(basket: Vec<Ware>, currency: WareType) -> Self {
+        debug_assert!(currency.is_currency());
+
+        Self {
+            basket,
+            currency,
+            base_cost: None,
+            history: Vec::new(),
+        }
+    }
+
+    /// Records the cost of the basket in this tick.
+    /// The first cost that is not zero becomes the base of the index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use model::{price_index::PriceIndex, ware::{Ware, WareType}};
+    ///
+    /// let mut index = PriceIndex::new(vec![Ware::new(WareType::Food, 2)], WareType::Money);
+    /// index.record(10.0);
+    /// index.record(11.0);
+    /// assert_eq!(Some(1.1), index.current());
+    /// assert!((index.inflation().unwrap() - 0.1).abs() < 1e-9);
+    /// ```
+    pub fn record(&mut self, cost: f64) {
+        if self.base_cost.is_none() && cost > 0.0 {
+            self.base_cost = Some(cost);
+        }
+        if let Some(base_cost) = self.base_cost {
+            self.history.push(cost / base_cost);
+        }
+    }
+
+    pub fn basket(&self) -> &[Ware] {
+        &self.basket
+    }
+
+    pub fn currency(&self) -> WareType {
+        self.currency
+    }
+
+    /// The recorded values of the index, one per tick.
+    pub fn history(&self) -> &[f64] {
+        &self.history
+    }
+
+    pub fn current(&self) -> Option<f64> {
+        self.history.last().cloned()
+    }
+
+    /// The relative change of the index in the last tick.
+    pub fn inflation(&self) -> Option<f64> {
+        match self.history.as_slice() {
+            [.., previous, current] => Some(current / previous - 1.0),
+            _ => None,
+        }
+    }
+}
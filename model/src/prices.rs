@@ -1,9 +1,11 @@
 use crate::ware::{Ware, WareAmount, WareType};
 use std::collections::HashMap;
 
+/// Prices of wares, quoted per currency.
+/// Prices that were not set explicitly default to the default price of the ware in the requested currency.
 #[derive(Debug, Clone, Default)]
 pub struct PriceTable {
-    prices: HashMap<WareType, WareAmount>,
+    prices: HashMap<(WareType, WareType), WareAmount>,
 }
 
 // Creators
@@ -16,36 +18,72 @@ impl PriceTable {
 // Modifiers
 impl PriceTable {
     pub fn set_single_price(&mut self, ware_type: WareType, price: WareAmount) {
-        self.prices_mut().insert(ware_type, price);
+        self.set_single_price_in(ware_type, WareType::Money, price);
+    }
+
+    pub fn set_single_price_in(
+        &mut self,
+        ware_type: WareType,
+        currency: WareType,
+        price: WareAmount,
+    ) {
+        debug_assert!(currency.is_currency());
+
+        self.prices_mut().insert((ware_type, currency), price);
     }
 }
 
 // Getters
 impl PriceTable {
-    fn prices(&self) -> &HashMap<WareType, WareAmount> {
+    fn prices(&self) -> &HashMap<(WareType, WareType), WareAmount> {
         &self.prices
     }
 
-    fn prices_mut(&mut self) -> &mut HashMap<WareType, WareAmount> {
+    fn prices_mut(&mut self) -> &mut HashMap<(WareType, WareType), WareAmount> {
         &mut self.prices
     }
 
     pub fn single_price(&self, ware_type: WareType) -> WareAmount {
-        if let Some(price) = self.prices().get(&ware_type) {
+        self.single_price_in(ware_type, WareType::Money)
+    }
+
+    /// The price of a single unit of the given ware type, quoted in the given currency.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use model::{prices::PriceTable, ware::WareType};
+    ///
+    /// let mut prices = PriceTable::new();
+    /// prices.set_single_price_in(WareType::Food, WareType::Gold, 2);
+    /// assert_eq!(2, prices.single_price_in(WareType::Food, WareType::Gold));
+    /// assert_eq!(5, prices.single_price_in(WareType::Food, WareType::Money));
+    /// assert_eq!(3, prices.single_price_in(WareType::Food, WareType::Silver));
+    /// ```
+    pub fn single_price_in(&self, ware_type: WareType, currency: WareType) -> WareAmount {
+        if let Some(price) = self.prices().get(&(ware_type, currency)) {
             *price
         } else {
-            ware_type.default_price()
+            ware_type.default_price_in(currency)
         }
     }
 
     pub fn single_price_as_ware(&self, ware: &Ware) -> Ware {
-        Ware::new(WareType::Money, self.single_price(ware.ware_type()))
+        self.single_price_as_ware_in(ware, WareType::Money)
+    }
+
+    pub fn single_price_as_ware_in(&self, ware: &Ware, currency: WareType) -> Ware {
+        Ware::new(currency, self.single_price_in(ware.ware_type(), currency))
     }
 
     pub fn price(&self, ware: &Ware) -> Ware {
+        self.price_in(ware, WareType::Money)
+    }
+
+    pub fn price_in(&self, ware: &Ware, currency: WareType) -> Ware {
         Ware::new(
-            WareType::Money,
-            ware.amount() * self.single_price(ware.ware_type()),
+            currency,
+            ware.amount() * self.single_price_in(ware.ware_type(), currency),
         )
     }
 }
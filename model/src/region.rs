@@ -0,0 +1,154 @@
+use crate::{market::Market, resource::ResourceStock, ware::WareAmount, world::Tick};
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+pub type RegionId = usize;
+
+/// A part of the world with its own market and natural resources.
+/// Entities trade only in the market of the region they are located in, and extract only its resources.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Region {
+    name: String,
+    market: Market,
+    resources: Vec<ResourceStock>,
+}
+
+/// A one-way connection between two regions.
+/// Travelling along it costs the given amount of currency per unit of cargo and takes the given number of ticks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Route {
+    from: RegionId,
+    to: RegionId,
+    cost: WareAmount,
+    duration: Tick,
+}
+
+/// A journey of an entity to another region.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Trip {
+    destination: RegionId,
+    arrival: Tick,
+}
+
+impl Region {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            market: Market::default(),
+            resources: Vec::new(),
+        }
+    }
+
+    /// Adds a natural resource stock to this region, replacing any previous stock of its ware type.
+    pub fn add_resource(&mut self, stock: ResourceStock) {
+        self.resources
+            .retain(|resource| resource.ware_type() != stock.ware_type());
+        self.resources.push(stock);
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn market(&self) -> &Market {
+        &self.market
+    }
+
+    pub fn market_mut(&mut self) -> &mut Market {
+        &mut self.market
+    }
+
+    pub fn resources(&self) -> &[ResourceStock] {
+        &self.resources
+    }
+
+    pub fn resources_mut(&mut self) -> &mut [ResourceStock] {
+        &mut self.resources
+    }
+}
+
+impl Route {
+    pub fn new(from: RegionId, to: RegionId, cost: WareAmount, duration: Tick) -> Self {
+        Self {
+            from,
+            to,
+            cost,
+            duration,
+        }
+    }
+
+    pub fn from(&self) -> RegionId {
+        self.from
+    }
+
+    pub fn to(&self) -> RegionId {
+        self.to
+    }
+
+    /// The cost per unit of cargo.
+    pub fn cost(&self) -> WareAmount {
+        self.cost
+    }
+
+    pub fn duration(&self) -> Tick {
+        self.duration
+    }
+}
+
+impl Trip {
+    pub fn new(destination: RegionId, arrival: Tick) -> Self {
+        Self {
+            destination,
+            arrival,
+        }
+    }
+
+    pub fn destination(&self) -> RegionId {
+        self.destination
+    }
+
+    pub fn arrival(&self) -> Tick {
+        self.arrival
+    }
+}
+
+/// Returns the cheapest way from one region to another over the given routes as a single route,
+/// with the summed cost and duration of its legs. Among equally cheap ways the fastest is chosen.
+/// Returns None if the destination cannot be reached.
+///
+/// # Example
+///
+/// ```
+/// use model::region::{cheapest_route, Route};
+///
+/// let routes = [Route::new(0, 1, 5, 1), Route::new(0, 2, 1, 2), Route::new(2, 1, 1, 2)];
+/// assert_eq!(Some(Route::new(0, 1, 2, 4)), cheapest_route(&routes, 0, 1));
+/// assert_eq!(None, cheapest_route(&routes, 1, 0));
+/// ```
+pub fn cheapest_route(routes: &[Route], from: RegionId, to: RegionId) -> Option<Route> {
+    let mut best = vec![(from, 0, 0)];
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((0, 0, from)));
+
+    while let Some(Reverse((cost, duration, region))) = queue.pop() {
+        if region == to {
+            return Some(Route::new(from, to, cost, duration));
+        }
+        if best
+            .iter()
+            .any(|&(visited, c, d)| visited == region && (c, d) < (cost, duration))
+        {
+            continue;
+        }
+
+        for route in routes.iter().filter(|route| route.from == region) {
+            let next = (cost + route.cost, duration + route.duration);
+            match best.iter_mut().find(|(visited, _, _)| *visited == route.to) {
+                Some(entry) if (entry.1, entry.2) <= next => continue,
+                Some(entry) => *entry = (route.to, next.0, next.1),
+                None => best.push((route.to, next.0, next.1)),
+            }
+            queue.push(Reverse((next.0, next.1, route.to)));
+        }
+    }
+    None
+}
@@ -0,0 +1,94 @@
+use crate::ware::{WareAmount, WareType};
+
+/// How a natural resource stock regenerates every tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Growth {
+    /// Grows by the rate times the stock times the share of the capacity that is not used yet.
+    /// A depleted stock does not recover.
+    Logistic(f64),
+    /// Grows by a fixed amount, up to the capacity.
+    Inflow(f64),
+}
+
+/// A stock of a natural resource, owned by the world or a region, which entities extract by producing its ware type.
+/// The stock is fractional, but only whole wares can be extracted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResourceStock {
+    ware_type: WareType,
+    amount: f64,
+    capacity: f64,
+    growth: Growth,
+    extracted: u64,
+}
+
+impl ResourceStock {
+    /// Creates a stock of the given ware type that holds at most `capacity` wares.
+    pub fn new(ware_type: WareType, amount: f64, capacity: f64, growth: Growth) -> Self {
+        debug_assert!(amount >= 0.0 && amount <= capacity);
+
+        Self {
+            ware_type,
+            amount,
+            capacity,
+            growth,
+            extracted: 0,
+        }
+    }
+
+    /// Removes up to the given amount of whole wares from the stock, and returns the amount removed.
+    pub fn extract(&mut self, amount: WareAmount) -> WareAmount {
+        let extracted = amount.min(self.amount.floor() as WareAmount);
+        self.amount -= f64::from(extracted);
+        self.extracted += u64::from(extracted);
+        extracted
+    }
+
+    /// Grows the stock by one tick of its growth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use model::{resource::*, ware::WareType};
+    ///
+    /// let mut fish = ResourceStock::new(WareType::Food, 50.0, 100.0, Growth::Logistic(0.2));
+    /// fish.regenerate();
+    /// assert_eq!(55.0, fish.amount());
+    ///
+    /// let mut spring = ResourceStock::new(WareType::Water, 8.0, 10.0, Growth::Inflow(3.0));
+    /// spring.regenerate();
+    /// assert_eq!(10.0, spring.amount());
+    /// ```
+    pub fn regenerate(&mut self) {
+        let growth = match self.growth {
+            Growth::Logistic(rate) => rate * self.amount * (1.0 - self.amount / self.capacity),
+            Growth::Inflow(inflow) => inflow,
+        };
+        self.amount = (self.amount + growth).max(0.0).min(self.capacity);
+    }
+
+    pub fn ware_type(&self) -> WareType {
+        self.ware_type
+    }
+
+    pub fn amount(&self) -> f64 {
+        self.amount
+    }
+
+    pub fn capacity(&self) -> f64 {
+        self.capacity
+    }
+
+    pub fn growth(&self) -> Growth {
+        self.growth
+    }
+
+    /// The total amount of wares extracted from this stock.
+    pub fn extracted(&self) -> u64 {
+        self.extracted
+    }
+
+    /// True if no whole ware can be extracted.
+    pub fn is_depleted(&self) -> bool {
+        self.amount < 1.0
+    }
+}
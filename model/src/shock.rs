@@ -0,0 +1,71 @@
+use crate::{
+    entity::recipe::Recipe,
+    ware::{Ware, WareType},
+    world::Tick,
+};
+
+/// An exogenous change to a world.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shock {
+    /// Scales the outputs of the given ware type of all recipes by the factor while it lasts.
+    Output(WareType, f64),
+    /// Makes the recipe unavailable to all entities while it lasts.
+    Disable(Recipe),
+    /// Removes the given share of the households, chosen at random.
+    Cull(f64),
+    /// Gives the ware to every household.
+    Windfall(Ware),
+}
+
+/// When a scheduled shock fires.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Trigger {
+    /// Once at the given tick.
+    At(Tick),
+    /// Every tick with the given probability.
+    Chance(f64),
+}
+
+/// A shock that fires when its trigger says so.
+/// Shocks that change production last for the given number of ticks, or forever.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScheduledShock {
+    trigger: Trigger,
+    shock: Shock,
+    duration: Option<Tick>,
+}
+
+impl Shock {
+    /// True if the shock lasts for a while, instead of happening at once.
+    pub fn is_lasting(&self) -> bool {
+        match self {
+            Shock::Output(..) | Shock::Disable(_) => true,
+            Shock::Cull(_) | Shock::Windfall(_) => false,
+        }
+    }
+}
+
+impl ScheduledShock {
+    pub fn new(trigger: Trigger, shock: Shock, duration: Option<Tick>) -> Self {
+        debug_assert!(duration.is_none_or(|duration| duration > 0));
+        debug_assert!(duration.is_none() || shock.is_lasting());
+
+        Self {
+            trigger,
+            shock,
+            duration,
+        }
+    }
+
+    pub fn trigger(&self) -> Trigger {
+        self.trigger
+    }
+
+    pub fn shock(&self) -> &Shock {
+        &self.shock
+    }
+
+    pub fn duration(&self) -> Option<Tick> {
+        self.duration
+    }
+}
@@ -1,2 +1,4 @@
-pub const EATING_RECIPE: &str = "(1x Food) -> ()";
-pub const FOOD_CREATOR_RECIPE: &str = "(5x Money) -> (1x Food)";
+pub const EATING_RECIPE: &str = "!(1x Food) -> ()";
+pub const FOOD_CREATOR_RECIPE: &str = "(2x Labor) -> (1x Food)";
+pub const HUMAN_MAX_HEALTH: u32 = 10;
+pub const HUMAN_LABOR: u32 = 8;
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, ops::Mul};
+use crate::world::Tick;
+use std::{
+    collections::{HashMap, VecDeque},
+    ops::Mul,
+};
 
 custom_derive! {
     #[derive(Clone, Debug, Copy, Hash, PartialEq, Eq, Ord, PartialOrd, IterVariants(WareTypeVariants), EnumFromStr)]
@@ -6,7 +10,12 @@ custom_derive! {
         Food,
         Water,
         Soil,
+        Plough,
+        Well,
+        Labor,
         Money,
+        Gold,
+        Silver,
     }
 }
 
@@ -23,12 +32,65 @@ impl WareType {
             Food => 5,
             Water => 1,
             Soil => 1,
+            Plough => 20,
+            Well => 50,
+            Labor => 2,
             Money => 1,
+            Gold => 10,
+            Silver => 2,
         }
     }
 
-    pub fn is_money(&self) -> bool {
-        *self == WareType::Money
+    /// The default price of this ware type quoted in the given currency, as a whole price for offers.
+    /// The price is the default value rounded, and is at least one.
+    pub fn default_price_in(&self, currency: WareType) -> WareAmount {
+        (self.default_value_in(currency).round() as WareAmount).max(1)
+    }
+
+    /// The default value of one unit of this ware type in the given currency,
+    /// derived from the default prices in money without rounding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use model::ware::WareType;
+    ///
+    /// assert_eq!(0.2, WareType::Silver.default_value_in(WareType::Gold));
+    /// assert_eq!(0.1, WareType::Water.default_value_in(WareType::Gold));
+    /// assert_eq!(1, WareType::Water.default_price_in(WareType::Gold));
+    /// ```
+    pub fn default_value_in(&self, currency: WareType) -> f64 {
+        debug_assert!(currency.is_currency());
+
+        f64::from(self.default_price()) / f64::from(currency.default_price())
+    }
+
+    pub fn is_currency(&self) -> bool {
+        use WareType::*;
+        match self {
+            Money | Gold | Silver => true,
+            Food | Water | Soil | Plough | Well | Labor => false,
+        }
+    }
+
+    /// The space one unit of this ware type takes in a storage. Currencies and labor take no space.
+    pub fn volume(&self) -> WareAmount {
+        use WareType::*;
+        match self {
+            Food | Water | Soil => 1,
+            Plough => 2,
+            Well => 10,
+            Labor | Money | Gold | Silver => 0,
+        }
+    }
+
+    /// True if wares of this type perish at the end of the tick in which they were created.
+    pub fn is_perishable(&self) -> bool {
+        *self == WareType::Labor
+    }
+
+    pub fn currencies() -> impl Iterator<Item = WareType> {
+        WareType::iter_variants().filter(WareType::is_currency)
     }
 }
 
@@ -61,8 +123,8 @@ impl Ware {
         &mut self.amount
     }
 
-    pub fn is_money(&self) -> bool {
-        self.ware_type().is_money()
+    pub fn is_currency(&self) -> bool {
+        self.ware_type().is_currency()
     }
 }
 
@@ -81,9 +143,105 @@ impl Mul<WareAmount> for Ware {
     }
 }
 
+/// How wares of a type spoil: a fraction of the amount held is lost every tick,
+/// and wares older than the shelf life are lost at once.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Decay {
+    rate: f64,
+    shelf_life: Option<Tick>,
+}
+
+impl Decay {
+    pub fn new(rate: f64, shelf_life: Option<Tick>) -> Self {
+        debug_assert!((0.0..=1.0).contains(&rate));
+        debug_assert!(shelf_life.is_none_or(|shelf_life| shelf_life > 0));
+
+        Self { rate, shelf_life }
+    }
+
+    /// The fraction of the amount held that is lost every tick.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// The number of ticks after which wares are lost.
+    pub fn shelf_life(&self) -> Option<Tick> {
+        self.shelf_life
+    }
+}
+
+/// The capacity of a ware store: a limit on the total volume of its wares, and limits on the amounts of single ware types.
+/// Holding wares may cost a fee per unit of volume per tick.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Storage {
+    capacity: Option<WareAmount>,
+    limits: HashMap<WareType, WareAmount>,
+    fee: Option<Ware>,
+}
+
+impl Storage {
+    /// Creates a storage for the given total volume, or of unlimited volume.
+    pub fn new(capacity: Option<WareAmount>) -> Self {
+        Self {
+            capacity,
+            ..Default::default()
+        }
+    }
+
+    /// Limits the amount of the given ware type that can be stored.
+    pub fn set_limit(&mut self, ware_type: WareType, limit: WareAmount) {
+        self.limits.insert(ware_type, limit);
+    }
+
+    /// Sets the fee that is charged per unit of volume held every tick.
+    pub fn set_fee(&mut self, fee: Option<Ware>) {
+        debug_assert!(fee.as_ref().is_none_or(Ware::is_currency));
+
+        self.fee = fee;
+    }
+
+    pub fn capacity(&self) -> Option<WareAmount> {
+        self.capacity
+    }
+
+    pub fn limit(&self, ware_type: WareType) -> Option<WareAmount> {
+        self.limits.get(&ware_type).cloned()
+    }
+
+    pub fn fee(&self) -> Option<&Ware> {
+        self.fee.as_ref()
+    }
+}
+
+/// An amount of wares of one type that were stored at the same tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lot {
+    created: Tick,
+    amount: WareAmount,
+}
+
+impl Lot {
+    pub fn new(created: Tick, amount: WareAmount) -> Self {
+        Self { created, amount }
+    }
+
+    pub fn created(&self) -> Tick {
+        self.created
+    }
+
+    pub fn amount(&self) -> WareAmount {
+        self.amount
+    }
+}
+
+/// Amounts of wares by type.
+/// For ware types whose lots are stamped, the store additionally tracks the age of its wares in lots.
+/// Wares that were pushed since the last stamp are the newest, and wares are always popped oldest first.
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct WareStore {
     wares: HashMap<WareType, WareAmount>,
+    lots: HashMap<WareType, VecDeque<Lot>>,
+    storage: Option<Storage>,
 }
 
 // Creators
@@ -95,19 +253,56 @@ impl WareStore {
 
 // Modifiers
 impl WareStore {
-    /// Pushes the given ware to this store.
-    pub fn push_ware(&mut self, ware: Ware) {
+    /// Pushes the given ware to this store, as far as its storage has room for it.
+    ///
+    /// Returns the part of the ware that did not fit, which is not stored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use model::ware::*;
+    ///
+    /// let mut store = WareStore::new();
+    /// let mut storage = Storage::new(Some(10));
+    /// storage.set_limit(WareType::Water, 3);
+    /// store.set_storage(Some(storage));
+    /// assert_eq!(Ware::new(WareType::Water, 2), store.push_ware(Ware::new(WareType::Water, 5)));
+    /// assert_eq!(3, store.room_for(WareType::Plough));
+    /// assert_eq!(0, store.push_ware(Ware::new(WareType::Plough, 3)).amount());
+    /// assert_eq!(1, store.room_for(WareType::Food));
+    /// assert_eq!(9, store.volume());
+    /// ```
+    #[must_use = "the part of the ware that did not fit is not stored"]
+    pub fn push_ware(&mut self, mut ware: Ware) -> Ware {
         trace!("Pushing {}", ware);
 
-        if ware.amount() == 0 {
-            return;
+        let amount = ware.amount().min(self.room_for(ware.ware_type()));
+        *ware.amount_mut() -= amount;
+        if amount == 0 {
+            return ware;
         }
 
-        if let Some(amount) = self.wares.get_mut(&ware.ware_type()) {
-            *amount += ware.amount();
+        if let Some(held) = self.wares.get_mut(&ware.ware_type()) {
+            *held += amount;
         } else {
-            self.wares.insert(ware.ware_type(), ware.amount());
+            self.wares.insert(ware.ware_type(), amount);
         }
+        ware
+    }
+
+    /// Pushes the given ware regardless of the storage of this store.
+    /// For stores that tally wares instead of holding them, like demands.
+    pub fn push_ware_unlimited(&mut self, ware: Ware) {
+        if ware.amount() == 0 {
+            return;
+        }
+
+        *self.wares.entry(ware.ware_type()).or_insert(0) += ware.amount();
+    }
+
+    /// Sets the storage that limits what this store can hold. Wares it already holds are kept.
+    pub fn set_storage(&mut self, storage: Option<Storage>) {
+        self.storage = storage;
     }
 
     /// Pops the given ware from this store.
@@ -121,9 +316,9 @@ impl WareStore {
     /// use model::ware::*;
     ///
     /// let mut store = WareStore::new();
-    /// store.push_ware(Ware::new(WareType::Money, 5));
+    /// store.push_ware_unlimited(Ware::new(WareType::Money, 5));
     /// assert_eq!(5, store.ware_amount(WareType::Money));
-    /// store.push_ware(Ware::new(WareType::Money, 3));
+    /// store.push_ware_unlimited(Ware::new(WareType::Money, 3));
     /// assert_eq!(8, store.ware_amount(WareType::Money));
     /// store.pop_ware(Ware::new(WareType::Money, 4));
     /// assert_eq!(4, store.ware_amount(WareType::Money));
@@ -142,6 +337,7 @@ impl WareStore {
                 } else {
                     self.wares.remove(&ware.ware_type()).unwrap();
                 }
+                self.take_from_lots(ware.ware_type(), ware.amount());
                 Ok(ware)
             } else {
                 Err(())
@@ -162,7 +358,7 @@ impl WareStore {
     /// use model::ware::*;
     ///
     /// let mut store = WareStore::new();
-    /// store.push_ware(Ware::new(WareType::Money, 5));
+    /// store.push_ware_unlimited(Ware::new(WareType::Money, 5));
     /// assert_eq!(5, store.ware_amount(WareType::Money));
     ///
     /// let mut pop = Ware::new(WareType::Money, 4);
@@ -181,17 +377,19 @@ impl WareStore {
         trace!("Popping max {}", ware);
 
         if let Some(amount) = self.wares.get_mut(&ware.ware_type()) {
-            if *amount > ware.amount() {
+            let pop_amount = if *amount > ware.amount() {
                 let pop_amount = ware.amount();
                 *amount -= pop_amount;
                 *ware.amount_mut() = 0;
-                Ware::new(ware.ware_type(), pop_amount)
+                pop_amount
             } else {
                 let pop_amount = *amount;
                 self.wares.remove(&ware.ware_type());
                 *ware.amount_mut() -= pop_amount;
-                Ware::new(ware.ware_type(), pop_amount)
-            }
+                pop_amount
+            };
+            self.take_from_lots(ware.ware_type(), pop_amount);
+            Ware::new(ware.ware_type(), pop_amount)
         } else {
             Ware::new(ware.ware_type(), 0)
         }
@@ -209,16 +407,16 @@ impl WareStore {
     /// use model::ware::*;
     ///
     /// let mut store = WareStore::new();
-    /// store.push_ware(Ware::new(WareType::Money, 13));
-    /// store.push_ware(Ware::new(WareType::Water, 3));
-    /// store.push_ware(Ware::new(WareType::Food, 5));
+    /// store.push_ware_unlimited(Ware::new(WareType::Money, 13));
+    /// store.push_ware_unlimited(Ware::new(WareType::Water, 3));
+    /// store.push_ware_unlimited(Ware::new(WareType::Food, 5));
     /// let mut pop = WareStore::new();
-    /// pop.push_ware(Ware::new(WareType::Money, 11));
-    /// pop.push_ware(Ware::new(WareType::Water, 3));
-    /// pop.push_ware(Ware::new(WareType::Food, 3));
+    /// pop.push_ware_unlimited(Ware::new(WareType::Money, 11));
+    /// pop.push_ware_unlimited(Ware::new(WareType::Water, 3));
+    /// pop.push_ware_unlimited(Ware::new(WareType::Food, 3));
     /// let mut popped = WareStore::new();
-    /// popped.push_ware(Ware::new(WareType::Money, 2));
-    /// popped.push_ware(Ware::new(WareType::Food, 2));
+    /// popped.push_ware_unlimited(Ware::new(WareType::Money, 2));
+    /// popped.push_ware_unlimited(Ware::new(WareType::Food, 2));
     /// assert_eq!(Ok(pop.clone()), store.pop_wares(pop.clone()));
     /// assert_eq!(popped, store);
     ///
@@ -249,22 +447,22 @@ impl WareStore {
     /// use model::ware::*;
     ///
     /// let mut store = WareStore::new();
-    /// store.push_ware(Ware::new(WareType::Money, 13));
-    /// store.push_ware(Ware::new(WareType::Water, 3));
-    /// store.push_ware(Ware::new(WareType::Food, 5));
+    /// store.push_ware_unlimited(Ware::new(WareType::Money, 13));
+    /// store.push_ware_unlimited(Ware::new(WareType::Water, 3));
+    /// store.push_ware_unlimited(Ware::new(WareType::Food, 5));
     /// let mut pop = WareStore::new();
-    /// pop.push_ware(Ware::new(WareType::Money, 11));
-    /// pop.push_ware(Ware::new(WareType::Water, 4));
-    /// pop.push_ware(Ware::new(WareType::Food, 3));
+    /// pop.push_ware_unlimited(Ware::new(WareType::Money, 11));
+    /// pop.push_ware_unlimited(Ware::new(WareType::Water, 4));
+    /// pop.push_ware_unlimited(Ware::new(WareType::Food, 3));
     /// let mut pop_result = WareStore::new();
-    /// pop_result.push_ware(Ware::new(WareType::Money, 11));
-    /// pop_result.push_ware(Ware::new(WareType::Water, 3));
-    /// pop_result.push_ware(Ware::new(WareType::Food, 3));
+    /// pop_result.push_ware_unlimited(Ware::new(WareType::Money, 11));
+    /// pop_result.push_ware_unlimited(Ware::new(WareType::Water, 3));
+    /// pop_result.push_ware_unlimited(Ware::new(WareType::Food, 3));
     /// let mut pop_leftover = WareStore::new();
-    /// pop_leftover.push_ware(Ware::new(WareType::Water, 1));
+    /// pop_leftover.push_ware_unlimited(Ware::new(WareType::Water, 1));
     /// let mut popped = WareStore::new();
-    /// popped.push_ware(Ware::new(WareType::Money, 2));
-    /// popped.push_ware(Ware::new(WareType::Food, 2));
+    /// popped.push_ware_unlimited(Ware::new(WareType::Money, 2));
+    /// popped.push_ware_unlimited(Ware::new(WareType::Food, 2));
     /// assert_eq!(pop_result.clone(), store.pop_wares_max(&mut pop));
     /// assert_eq!(popped, store);
     /// assert_eq!(pop_leftover, pop);
@@ -275,7 +473,7 @@ impl WareStore {
         let mut popped = WareStore::new();
         for (ware_type, amount) in wares.iter_mut() {
             let mut pop = Ware::from((*ware_type, *amount));
-            popped.push_ware(self.pop_ware_max(&mut pop));
+            popped.push_ware_unlimited(self.pop_ware_max(&mut pop));
             *amount = pop.amount();
         }
         wares.clean();
@@ -286,8 +484,131 @@ impl WareStore {
         self.wares.iter_mut()
     }
 
+    /// Records the wares of the given type that are not in a lot yet as a lot stored at the given tick.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use model::ware::*;
+    ///
+    /// let mut store = WareStore::new();
+    /// store.push_ware_unlimited(Ware::new(WareType::Food, 3));
+    /// store.stamp_lots(WareType::Food, 1);
+    /// store.push_ware_unlimited(Ware::new(WareType::Food, 4));
+    /// store.stamp_lots(WareType::Food, 2);
+    /// store.pop_ware(Ware::new(WareType::Food, 2)).unwrap();
+    /// assert_eq!(vec![Lot::new(1, 1), Lot::new(2, 4)], store.lots(WareType::Food));
+    /// assert_eq!(1, store.pop_lots_before(WareType::Food, 2));
+    /// assert_eq!(4, store.ware_amount(WareType::Food));
+    /// ```
+    pub fn stamp_lots(&mut self, ware_type: WareType, tick: Tick) {
+        let unstamped = self.ware_amount(ware_type) - self.lot_amount(ware_type);
+        if unstamped > 0 {
+            self.lots
+                .entry(ware_type)
+                .or_default()
+                .push_back(Lot::new(tick, unstamped));
+        }
+    }
+
+    /// Pops the given ware from this store like `pop_ware`, and returns the lots it was taken from, oldest first.
+    /// Popped wares that were not in a lot are not returned as lots.
+    pub fn pop_lots(&mut self, ware: Ware) -> Result<Vec<Lot>, ()> {
+        let lots = self.lots(ware.ware_type());
+        let before = self.lot_amount(ware.ware_type());
+        self.pop_ware(ware.clone())?;
+
+        let mut taken = before - self.lot_amount(ware.ware_type());
+        let mut popped = Vec::new();
+        for lot in lots {
+            if taken == 0 {
+                break;
+            }
+            let amount = lot.amount().min(taken);
+            popped.push(Lot::new(lot.created(), amount));
+            taken -= amount;
+        }
+        Ok(popped)
+    }
+
+    /// Pushes the given ware to this store like `push_ware`, keeping the ages of the given lots of it.
+    /// Returns the part of the ware that did not fit, which is not stored.
+    /// That part is taken from the wares that are not in the given lots first, and then from the newest given lots,
+    /// so the lots already in this store are kept.
+    #[must_use = "the part of the ware that did not fit is not stored"]
+    pub fn push_lots(&mut self, ware: Ware, mut lots: Vec<Lot>) -> Ware {
+        let in_lots: WareAmount = lots.iter().map(Lot::amount).sum();
+        debug_assert!(in_lots <= ware.amount());
+
+        let ware_type = ware.ware_type();
+        let unstamped = ware.amount() - in_lots;
+        let overflow = self.push_ware(ware);
+        lots.sort_by_key(Lot::created);
+        let mut excess = overflow.amount().saturating_sub(unstamped);
+        while excess > 0 {
+            let newest = lots.last_mut().unwrap();
+            let taken = newest.amount.min(excess);
+            newest.amount -= taken;
+            excess -= taken;
+            if newest.amount == 0 {
+                lots.pop();
+            }
+        }
+        if lots.is_empty() {
+            return overflow;
+        }
+
+        let merged = self.lots.entry(ware_type).or_default();
+        merged.extend(lots);
+        merged.make_contiguous().sort_by_key(Lot::created);
+        overflow
+    }
+
+    /// Pops the wares of the given type in lots stored before the given tick, and returns their amount.
+    pub fn pop_lots_before(&mut self, ware_type: WareType, tick: Tick) -> WareAmount {
+        let expired: WareAmount = self
+            .lots(ware_type)
+            .iter()
+            .take_while(|lot| lot.created() < tick)
+            .map(Lot::amount)
+            .sum();
+        self.pop_ware(Ware::new(ware_type, expired)).unwrap();
+        expired
+    }
+
     fn clean(&mut self) {
         self.wares.retain(|_, v| *v > 0);
+        let ware_types: Vec<_> = self.lots.keys().cloned().collect();
+        for ware_type in ware_types {
+            self.trim_lots(ware_type);
+        }
+    }
+
+    /// Removes the oldest lots of the given type that exceed the amount held.
+    fn trim_lots(&mut self, ware_type: WareType) {
+        let excess = self
+            .lot_amount(ware_type)
+            .saturating_sub(self.ware_amount(ware_type));
+        self.take_from_lots(ware_type, excess);
+    }
+
+    /// Takes the given amount of popped wares from the lots of the given type, oldest first.
+    /// Wares beyond the lots are taken from the wares that were not stamped yet.
+    fn take_from_lots(&mut self, ware_type: WareType, amount: WareAmount) {
+        let mut remaining = amount.min(self.lot_amount(ware_type));
+        if let Some(lots) = self.lots.get_mut(&ware_type) {
+            while let Some(oldest) = lots.front_mut().filter(|_| remaining > 0) {
+                let taken = oldest.amount.min(remaining);
+                oldest.amount -= taken;
+                remaining -= taken;
+                if oldest.amount == 0 {
+                    lots.pop_front();
+                }
+            }
+            if lots.is_empty() {
+                self.lots.remove(&ware_type);
+            }
+        }
     }
 }
 
@@ -310,4 +631,96 @@ impl WareStore {
     pub fn get_ware(&self, ware_type: WareType) -> Option<Ware> {
         Some(Ware::new(ware_type, self.wares.get(&ware_type)?.clone()))
     }
+
+    pub fn storage(&self) -> Option<&Storage> {
+        self.storage.as_ref()
+    }
+
+    /// The total volume of the wares in this store.
+    pub fn volume(&self) -> WareAmount {
+        self.wares
+            .iter()
+            .map(|(ware_type, amount)| ware_type.volume() * amount)
+            .sum()
+    }
+
+    /// The amount of the given ware type that can still be pushed to this store.
+    pub fn room_for(&self, ware_type: WareType) -> WareAmount {
+        let storage = match &self.storage {
+            Some(storage) => storage,
+            None => return WareAmount::MAX,
+        };
+
+        let by_volume = match storage.capacity() {
+            Some(capacity) if ware_type.volume() > 0 => {
+                capacity.saturating_sub(self.volume()) / ware_type.volume()
+            }
+            _ => WareAmount::MAX,
+        };
+        let by_limit = storage.limit(ware_type).map_or(WareAmount::MAX, |limit| {
+            limit.saturating_sub(self.ware_amount(ware_type))
+        });
+        by_volume.min(by_limit)
+    }
+
+    /// The lots of the given ware type, oldest first.
+    pub fn lots(&self, ware_type: WareType) -> Vec<Lot> {
+        self.lots
+            .get(&ware_type)
+            .map_or_else(Vec::new, |lots| lots.iter().copied().collect())
+    }
+
+    fn lot_amount(&self, ware_type: WareType) -> WareAmount {
+        self.lots
+            .get(&ware_type)
+            .map_or(0, |lots| lots.iter().map(Lot::amount).sum())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ware::{Lot, Storage, Ware, WareStore, WareType};
+
+    #[test]
+    fn test_lots_are_popped_oldest_first() {
+        let mut store = WareStore::new();
+        store.push_ware_unlimited(Ware::new(WareType::Food, 2));
+        store.stamp_lots(WareType::Food, 0);
+        store.push_ware_unlimited(Ware::new(WareType::Food, 2));
+
+        // Wares pushed in this tick are not stamped yet, but the old lot goes first.
+        assert_eq!(
+            Ok(vec![Lot::new(0, 2)]),
+            store.pop_lots(Ware::new(WareType::Food, 2))
+        );
+        assert!(store.lots(WareType::Food).is_empty());
+        assert_eq!(2, store.ware_amount(WareType::Food));
+
+        store.stamp_lots(WareType::Food, 1);
+        store.push_ware_unlimited(Ware::new(WareType::Food, 3));
+        let mut pop = Ware::new(WareType::Food, 3);
+        store.pop_ware_max(&mut pop);
+        assert!(store.lots(WareType::Food).is_empty());
+        assert_eq!(2, store.ware_amount(WareType::Food));
+    }
+
+    #[test]
+    fn test_push_lots_returns_the_newest_overflow() {
+        let mut store = WareStore::new();
+        store.set_storage(Some(Storage::new(Some(4))));
+        store.push_ware_unlimited(Ware::new(WareType::Food, 2));
+        store.stamp_lots(WareType::Food, 0);
+
+        // Of the incoming wares, the unstamped one and then the newest lot do not fit.
+        let overflow = store.push_lots(
+            Ware::new(WareType::Food, 4),
+            vec![Lot::new(2, 2), Lot::new(1, 1)],
+        );
+        assert_eq!(Ware::new(WareType::Food, 2), overflow);
+        assert_eq!(
+            vec![Lot::new(0, 2), Lot::new(1, 1), Lot::new(2, 1)],
+            store.lots(WareType::Food)
+        );
+        assert_eq!(4, store.ware_amount(WareType::Food));
+    }
 }
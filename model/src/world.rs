@@ -1,15 +1,96 @@
 use crate::{
-    entity::{recipe::Recipe, Entity},
-    market::{offer::OfferType, Market, OfferId},
-    ware::Ware,
+    arena::{Arena, Index},
+    contract::{ContractId, ContractRegistry, SupplyContract},
+    entity::{batch::Batch, recipe::Recipe, Entity},
+    event::Event,
+    market::{
+        auction::{Auction, AuctionId, Bid},
+        fill::Fill,
+        futures::{FuturesMarket, FuturesOrder},
+        offer::OfferType,
+        stats::LaborStats,
+        Market, OfferId,
+    },
+    population::PopulationRule,
+    price_index::PriceIndex,
+    region::{cheapest_route, Region, RegionId, Route},
+    resource::ResourceStock,
+    shock::{ScheduledShock, Shock},
+    ware::{Decay, Lot, Ware, WareAmount, WareType},
 };
+use std::str::FromStr;
 
-pub type EntityId = usize;
+pub type EntityId = Index;
+pub type Tick = u64;
 
-#[derive(Clone, Debug, Default)]
+/// What happens to the wares of entities that are removed from the world.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Estate {
+    /// The wares go to the heir of the entity, or are destroyed if it has none.
+    #[default]
+    Inherit,
+    /// The wares go to the given entity.
+    Confiscate(EntityId),
+    /// The wares are destroyed.
+    Destroy,
+}
+
+/// What happens to bankrupt entities after their inventory was auctioned and their creditors were paid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Resolution {
+    /// The entity is removed from the world.
+    #[default]
+    Close,
+    /// The remaining liabilities of the entity are written off and it continues.
+    Restructure,
+}
+
+/// The entities and the regional markets of a simulation.
+/// Removing an entity keeps the ids of the other entities valid, and ids of removed entities are detected as stale.
+/// A world starts with a single region, in which all entities are located unless they are moved.
+#[derive(Clone, Debug)]
 pub struct World {
-    entities: Vec<Entity>,
-    market: Market,
+    tick: Tick,
+    entities: Arena<Entity>,
+    regions: Vec<Region>,
+    routes: Vec<Route>,
+    futures: FuturesMarket,
+    auctions: Arena<Auction>,
+    contracts: ContractRegistry,
+    decay: Vec<(WareType, Decay)>,
+    resources: Vec<ResourceStock>,
+    shocks: Vec<ScheduledShock>,
+    active_shocks: Vec<(Shock, Option<Tick>)>,
+    estate: Estate,
+    population_rules: Vec<PopulationRule>,
+    labor_stats: LaborStats,
+    resolution: Resolution,
+    events: Vec<(Tick, Event)>,
+    price_index: PriceIndex,
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self {
+            tick: Default::default(),
+            entities: Default::default(),
+            regions: vec![Region::new("World".to_owned())],
+            routes: Default::default(),
+            futures: Default::default(),
+            auctions: Default::default(),
+            contracts: Default::default(),
+            decay: Default::default(),
+            resources: Default::default(),
+            shocks: Default::default(),
+            active_shocks: Default::default(),
+            estate: Default::default(),
+            population_rules: Default::default(),
+            labor_stats: Default::default(),
+            resolution: Default::default(),
+            events: Default::default(),
+            price_index: Default::default(),
+        }
+    }
 }
 
 // Creators
@@ -22,11 +103,9 @@ impl World {
 
     pub fn create_entity(&mut self, name: &str, recipes: &[Recipe]) -> EntityId {
         let entity = Entity::new(name.to_owned(), recipes.to_owned());
-        let entity_id = self.entities.len() as EntityId;
-        trace!("Creating entity {} with id {}", entity, entity_id);
+        trace!("Creating entity {}", entity);
 
-        self.entities.push(entity);
-        entity_id
+        self.entities.insert(entity)
     }
 
     pub fn create_offer(
@@ -36,39 +115,645 @@ impl World {
         offer_type: OfferType,
         price_per_ware: Ware,
     ) -> Result<OfferId, ()> {
+        let region = self.try_get_entity(entity_id).ok_or(())?.region();
         Ok(self
-            .market_mut()
+            .market_in_mut(region)
             .create_offer(offer, offer_type, price_per_ware, entity_id))
     }
+
+    /// Places an order in the futures market for delivery of the ware at the given tick.
+    /// Fails if the entity does not exist or the delivery tick is not in the future.
+    pub fn create_futures_order(
+        &mut self,
+        entity_id: EntityId,
+        ware: Ware,
+        offer_type: OfferType,
+        price_per_ware: Ware,
+        delivery: Tick,
+    ) -> Result<(), ()> {
+        self.try_get_entity(entity_id).ok_or(())?;
+        if delivery <= self.tick {
+            return Err(());
+        }
+        self.futures.create_order(FuturesOrder::new(
+            ware,
+            offer_type,
+            price_per_ware,
+            delivery,
+            entity_id,
+        ));
+        Ok(())
+    }
+
+    /// Opens an auction, which holds the lot until it closes.
+    /// Fails if the seller does not exist or does not hold the lot.
+    pub fn create_auction(&mut self, mut auction: Auction) -> Result<AuctionId, ()> {
+        let lots = self
+            .try_get_entity_mut(auction.seller())
+            .ok_or(())?
+            .remove_ware_lots(auction.lot().clone())?;
+        auction.hold_lot(lots);
+        Ok(self.auctions.insert(auction))
+    }
+
+    /// Registers a supply contract between two entities.
+    /// Fails if either party does not exist, or the supplier is the customer.
+    pub fn create_contract(&mut self, contract: SupplyContract) -> Result<ContractId, ()> {
+        self.try_get_entity(contract.supplier()).ok_or(())?;
+        self.try_get_entity(contract.customer()).ok_or(())?;
+        if contract.supplier() == contract.customer() {
+            return Err(());
+        }
+        Ok(self.contracts.create_contract(contract))
+    }
+
+    /// Adds a region with an empty market.
+    pub fn create_region(&mut self, name: &str) -> RegionId {
+        self.regions.push(Region::new(name.to_owned()));
+        self.regions.len() - 1
+    }
+}
+
+// Modifiers
+impl World {
+    pub fn set_estate(&mut self, estate: Estate) {
+        self.estate = estate;
+    }
+
+    /// Adds a rule under which entities enter or leave this world.
+    pub fn add_population_rule(&mut self, rule: PopulationRule) {
+        self.population_rules.push(rule);
+    }
+
+    /// Adds a one-way route between two regions. Fails if either region does not exist.
+    pub fn add_route(&mut self, route: Route) -> Result<(), ()> {
+        if route.from() >= self.regions.len() || route.to() >= self.regions.len() {
+            return Err(());
+        }
+
+        self.routes.push(route);
+        Ok(())
+    }
+
+    /// Makes wares of the given type spoil according to the given decay, replacing any previous decay of the type.
+    pub fn set_decay(&mut self, ware_type: WareType, decay: Decay) {
+        self.decay.retain(|(decaying, _)| *decaying != ware_type);
+        self.decay.push((ware_type, decay));
+    }
+
+    /// Places a bid of the entity in the auction.
+    /// The auction holds the price of the bids that can still win, and gives back the price of outbid bids.
+    /// Fails if the auction or the entity does not exist, the entity cannot pay the bid, or the auction rejects it.
+    pub fn bid(
+        &mut self,
+        auction_id: AuctionId,
+        bidder: EntityId,
+        price: WareAmount,
+    ) -> Result<(), ()> {
+        let entity = self.try_get_entity(bidder).ok_or(())?;
+        let auction = self.auctions.get(auction_id).ok_or(())?;
+        let amount = auction.lot().amount();
+        let held = auction.deposit_of(bidder).saturating_mul(amount);
+        if entity
+            .wares()
+            .ware_amount(auction.currency())
+            .saturating_add(held)
+            < price.saturating_mul(amount)
+        {
+            return Err(());
+        }
+
+        let auction = self.auctions.get_mut(auction_id).unwrap();
+        auction.bid(bidder, price)?;
+        let standing = auction.standing_bids();
+        self.hold_deposits(auction_id, standing);
+        Ok(())
+    }
+
+    /// Removes the auction, and gives the held lot back to the seller and the held bids back to the bidders.
+    /// Returns the removed auction, whose fill is still to be settled if it closed with a sale.
+    pub fn remove_auction(&mut self, auction_id: AuctionId) -> Option<Auction> {
+        let (auction, lots) = self.release_auction(auction_id)?;
+        if let Some(seller) = self.entities.get_mut(auction.seller()) {
+            seller.add_ware_lots(auction.lot().clone(), lots);
+        }
+        Some(auction)
+    }
+
+    pub fn auction_mut(&mut self, auction_id: AuctionId) -> Option<&mut Auction> {
+        self.auctions.get_mut(auction_id)
+    }
+
+    pub fn contracts_mut(&mut self) -> &mut ContractRegistry {
+        &mut self.contracts
+    }
+
+    pub fn futures_mut(&mut self) -> &mut FuturesMarket {
+        &mut self.futures
+    }
+
+    /// Adds a natural resource stock that is shared by all regions without their own stock of its ware type,
+    /// replacing any previous shared stock of the type.
+    pub fn add_resource(&mut self, stock: ResourceStock) {
+        self.resources
+            .retain(|resource| resource.ware_type() != stock.ware_type());
+        self.resources.push(stock);
+    }
+
+    pub fn resources_mut(&mut self) -> &mut [ResourceStock] {
+        &mut self.resources
+    }
+
+    /// Schedules a shock that fires when its trigger says so.
+    pub fn add_shock(&mut self, shock: ScheduledShock) {
+        self.shocks.push(shock);
+    }
+
+    /// Schedules the shocks of a scenario, given one per line in the format of `ScheduledShock::from_str`.
+    /// Blank lines and lines starting with '#' are skipped.
+    /// Fails without scheduling any shock if a line cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use model::world::World;
+    ///
+    /// let mut world = World::new();
+    /// let scenario = "
+    ///     ## A drought, and a plague that may strike any time.
+    ///     at 10: output Water 50% for 5 ticks
+    ///     1%: cull 20%
+    /// ";
+    /// world.add_shocks(scenario).unwrap();
+    /// assert_eq!(2, world.shocks().len());
+    /// assert!(world.add_shocks("at 3: cull 20% for 2 ticks").is_err());
+    /// assert_eq!(2, world.shocks().len());
+    /// ```
+    pub fn add_shocks(&mut self, schedule: &str) -> Result<(), String> {
+        let shocks = schedule
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(ScheduledShock::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        self.shocks.extend(shocks);
+        Ok(())
+    }
+
+    /// Makes a lasting shock take effect until the given tick, or forever.
+    pub fn activate_shock(&mut self, shock: Shock, until: Option<Tick>) {
+        debug_assert!(shock.is_lasting());
+
+        self.active_shocks.push((shock, until));
+    }
+
+    /// Ends the active shocks that last until the current tick or before.
+    pub fn expire_shocks(&mut self) {
+        let tick = self.tick;
+        self.active_shocks
+            .retain(|(_, until)| until.is_none_or(|until| until > tick));
+    }
+
+    pub fn set_resolution(&mut self, resolution: Resolution) {
+        self.resolution = resolution;
+    }
+
+    /// Records an event at the current tick.
+    pub fn log_event(&mut self, event: Event) {
+        info!("Tick {}: {}", self.tick, event);
+        self.events.push((self.tick, event));
+    }
+
+    /// Sets the price index that is recorded every tick.
+    pub fn set_price_index(&mut self, price_index: PriceIndex) {
+        self.price_index = price_index;
+    }
+
+    pub fn price_index_mut(&mut self) -> &mut PriceIndex {
+        &mut self.price_index
+    }
+
+    pub fn set_labor_stats(&mut self, labor_stats: LaborStats) {
+        self.labor_stats = labor_stats;
+    }
+
+    pub fn population_rules_mut(&mut self) -> &mut Vec<PopulationRule> {
+        &mut self.population_rules
+    }
+
+    /// Removes the given entity from the world and cancels its offers and futures orders.
+    /// A removed bank first pays back its deposits from its cash as far as it lasts,
+    /// and the credit lines it granted are closed with their drawn amounts written off.
+    /// Its wares are handled according to the estate policy of the world, and its batches in progress are lost.
+    /// Wares the recipient has no room for are recorded as lost by the recipient.
+    /// Returns the removed entity, or None if the id is stale.
+    pub fn remove_entity(&mut self, entity_id: EntityId) -> Option<Entity> {
+        let mut entity = self.entities.remove(entity_id)?;
+        trace!("Removing entity {} with id {}", entity, entity_id);
+
+        self.market_in_mut(entity.region())
+            .remove_offers_of(entity_id);
+        self.futures.remove_orders_of(entity_id);
+        entity.clear_offer_ids();
+
+        // Auctions of the entity are cancelled, and the bids it placed are no longer held.
+        let auction_ids: Vec<_> = self.auctions.iter().map(|(id, _)| id).collect();
+        for auction_id in auction_ids {
+            let auction = self.auctions.get(auction_id).unwrap();
+            if auction.seller() == entity_id {
+                let (auction, lots) = self.release_auction(auction_id).unwrap();
+                entity.add_ware_lots(auction.lot().clone(), lots);
+            } else if auction.deposit_of(entity_id) > 0 {
+                let deposits = auction
+                    .deposits()
+                    .iter()
+                    .filter(|deposit| deposit.bidder() != entity_id)
+                    .copied()
+                    .collect();
+                self.hold_deposits(auction_id, deposits);
+            }
+        }
+
+        let mut wares = entity.take_wares();
+        let currency = entity.currency();
+        if let Some(bank) = entity.bank_mut() {
+            for (depositor, balance) in bank.take_deposits() {
+                if let Some(depositor) = self.entities.get_mut(depositor) {
+                    let cash = wares.pop_ware_max(&mut Ware::new(currency, balance));
+                    if cash.amount() > 0 {
+                        depositor.add_ware(cash);
+                    }
+                }
+            }
+            for (_, borrower) in self.entities.iter_mut() {
+                if borrower
+                    .credit_line()
+                    .is_some_and(|credit_line| credit_line.bank() == entity_id)
+                {
+                    borrower.set_credit_line(None);
+                }
+            }
+        }
+
+        let recipient = match self.estate {
+            Estate::Inherit => entity.heir(),
+            Estate::Confiscate(recipient) => Some(recipient),
+            Estate::Destroy => None,
+        };
+        if let Some(recipient) = recipient.and_then(|id| self.entities.get_mut(id)) {
+            for ware in wares.iter() {
+                recipient.add_ware(ware);
+            }
+        }
+
+        Some(entity)
+    }
+
+    /// Makes the auction hold the prices of the given bids, and gives back the prices it held before.
+    /// Bids of bidders that do not exist or cannot pay are not held.
+    fn hold_deposits(&mut self, auction_id: AuctionId, deposits: Vec<Bid>) {
+        let auction = self.auctions.get(auction_id).unwrap();
+        let (currency, amount) = (auction.currency(), auction.lot().amount());
+        let mut held = Vec::new();
+        for deposit in &deposits {
+            let previous = auction.deposit_of(deposit.bidder());
+            let entity = match self.entities.get_mut(deposit.bidder()) {
+                Some(entity) => entity,
+                None => continue,
+            };
+            if deposit.price() > previous {
+                let missing = Ware::new(
+                    currency,
+                    (deposit.price() - previous).saturating_mul(amount),
+                );
+                if entity.remove_ware(missing).is_err() {
+                    entity.add_ware(Ware::new(currency, previous.saturating_mul(amount)));
+                    continue;
+                }
+            } else if deposit.price() < previous {
+                entity.add_ware(Ware::new(
+                    currency,
+                    (previous - deposit.price()).saturating_mul(amount),
+                ));
+            }
+            held.push(*deposit);
+        }
+
+        let auction = self.auctions.get_mut(auction_id).unwrap();
+        for previous in auction.replace_deposits(held) {
+            if deposits
+                .iter()
+                .all(|deposit| deposit.bidder() != previous.bidder())
+            {
+                if let Some(entity) = self.entities.get_mut(previous.bidder()) {
+                    entity.add_ware(Ware::new(currency, previous.price().saturating_mul(amount)));
+                }
+            }
+        }
+    }
+
+    /// Removes the auction and gives the held bids back to the bidders.
+    /// Returns the auction with the ages of its lot, which still has to be given back.
+    fn release_auction(&mut self, auction_id: AuctionId) -> Option<(Auction, Vec<Lot>)> {
+        self.auctions.get(auction_id)?;
+        self.hold_deposits(auction_id, Vec::new());
+        let mut auction = self.auctions.remove(auction_id)?;
+        let lots = auction.take_lot_lots();
+        Some((auction, lots))
+    }
+
+    pub fn advance_tick(&mut self) {
+        self.tick += 1;
+        trace!("Advanced to tick {}", self.tick);
+    }
+
+    /// Lends the given amount to the entity from its credit line.
+    /// Fails if the credit line does not allow it or its bank lacks the cash, in which case nothing is lent.
+    pub fn draw_credit(&mut self, entity_id: EntityId, amount: Ware) -> Result<(), ()> {
+        let entity = self.try_get_entity(entity_id).ok_or(())?;
+        if entity.available_credit(amount.ware_type()) < amount.amount() {
+            return Err(());
+        }
+
+        let bank_id = entity.credit_line().ok_or(())?.bank();
+        let cash = self
+            .try_get_entity_mut(bank_id)
+            .ok_or(())?
+            .remove_ware(amount.clone())?;
+        let entity = self.get_entity_mut(entity_id);
+        entity
+            .credit_line_mut()
+            .unwrap()
+            .draw(amount.amount())
+            .unwrap();
+        entity.add_ware(cash);
+        Ok(())
+    }
+
+    /// Transfers the ware of the given fill from its seller to its buyer, and the total price back.
+    /// A buyer that lacks cash draws the rest from its credit line.
+    /// Fails if either party cannot provide its side of the trade or has no room to store the other side,
+    /// in which case nothing is transferred.
+    pub fn settle(&mut self, fill: &Fill) -> Result<(), ()> {
+        self.check_settlement(fill).map_err(|_| ())?;
+        let total_price = fill.total_price();
+        let shortfall = total_price.amount().saturating_sub(
+            self.get_entity(fill.buyer())
+                .wares()
+                .ware_amount(total_price.ware_type()),
+        );
+        if shortfall > 0 {
+            self.draw_credit(fill.buyer(), Ware::new(total_price.ware_type(), shortfall))?;
+        }
+
+        trace!("Settling {}", fill);
+        // The seller delivers its oldest wares, and the buyer keeps track of their age.
+        let lots = self
+            .get_entity_mut(fill.seller())
+            .remove_ware_lots(fill.ware().clone())?;
+        let payment = self.get_entity_mut(fill.buyer()).remove_ware(total_price)?;
+        self.get_entity_mut(fill.buyer())
+            .add_ware_lots(fill.ware().clone(), lots);
+        self.get_entity_mut(fill.seller()).add_ware(payment);
+        Ok(())
+    }
 }
 
 // Getters
 impl World {
-    pub fn entities(&self) -> &[Entity] {
-        &self.entities
+    pub fn tick(&self) -> Tick {
+        self.tick
+    }
+
+    /// Checks whether the given fill can be settled.
+    /// Fails with the side of the party that cannot provide its side of the trade or has no room for the other side.
+    pub fn check_settlement(&self, fill: &Fill) -> Result<(), OfferType> {
+        let total_price = fill.total_price();
+        let buyer = self.try_get_entity(fill.buyer()).ok_or(OfferType::Buy)?;
+        let seller = self.try_get_entity(fill.seller()).ok_or(OfferType::Sell)?;
+        let shortfall = total_price
+            .amount()
+            .saturating_sub(buyer.wares().ware_amount(total_price.ware_type()));
+        if shortfall > buyer.available_credit(total_price.ware_type())
+            || buyer.wares().room_for(fill.ware().ware_type()) < fill.ware().amount()
+        {
+            return Err(OfferType::Buy);
+        }
+        if seller.wares().ware_amount(fill.ware().ware_type()) < fill.ware().amount()
+            || seller.wares().room_for(total_price.ware_type()) < total_price.amount()
+        {
+            return Err(OfferType::Sell);
+        }
+        Ok(())
+    }
+
+    pub fn entities<'a>(&'a self) -> impl Iterator<Item = &'a Entity> + 'a {
+        self.entities.iter().map(|(_, entity)| entity)
+    }
+
+    /// Iterates over the entities of this world together with their ids.
+    pub fn iter_entities<'a>(&'a self) -> impl Iterator<Item = (EntityId, &'a Entity)> + 'a {
+        self.entities.iter()
+    }
+
+    /// A snapshot of the work in progress: every batch that is not complete yet, with the entity running it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use model::{entity::batch::Batch, ware::{Ware, WareType}, world::World};
+    ///
+    /// let mut world = World::new();
+    /// let farmer = world.create_entity("Farmer", &[]);
+    /// let batch = Batch::new(vec![Ware::new(WareType::Food, 2)], 3);
+    /// world.get_entity_mut(farmer).start_batch(batch.clone());
+    /// assert_eq!(vec![(farmer, batch)], world.batches_in_progress());
+    /// ```
+    pub fn batches_in_progress(&self) -> Vec<(EntityId, Batch)> {
+        self.iter_entities()
+            .flat_map(|(entity_id, entity)| {
+                entity
+                    .batches()
+                    .iter()
+                    .map(move |batch| (entity_id, batch.clone()))
+            })
+            .collect()
     }
 
+    pub fn entity_count(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// The market of the first region.
     pub fn market(&self) -> &Market {
-        &self.market
+        self.market_in(0)
+    }
+
+    pub fn market_in(&self, region: RegionId) -> &Market {
+        self.regions[region].market()
+    }
+
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    /// The ware types that spoil, with their decay.
+    pub fn decay(&self) -> &[(WareType, Decay)] {
+        &self.decay
+    }
+
+    /// The auctions of this world that were not removed yet.
+    pub fn auctions<'a>(&'a self) -> impl Iterator<Item = (AuctionId, &'a Auction)> + 'a {
+        self.auctions.iter()
     }
 
-    pub fn entities_mut(&mut self) -> &mut [Entity] {
-        &mut self.entities
+    pub fn auction(&self, auction_id: AuctionId) -> Option<&Auction> {
+        self.auctions.get(auction_id)
     }
 
+    pub fn contracts(&self) -> &ContractRegistry {
+        &self.contracts
+    }
+
+    /// The futures order book and the open forward contracts, which are shared by all regions.
+    pub fn futures(&self) -> &FuturesMarket {
+        &self.futures
+    }
+
+    /// The natural resource stocks shared by all regions.
+    pub fn resources(&self) -> &[ResourceStock] {
+        &self.resources
+    }
+
+    pub fn shocks(&self) -> &[ScheduledShock] {
+        &self.shocks
+    }
+
+    /// The lasting shocks that are in effect, with the ticks they end at.
+    pub fn active_shocks(&self) -> &[(Shock, Option<Tick>)] {
+        &self.active_shocks
+    }
+
+    pub fn routes(&self) -> &[Route] {
+        &self.routes
+    }
+
+    /// The cheapest way between two regions along the routes of this world, if there is one.
+    pub fn route(&self, from: RegionId, to: RegionId) -> Option<Route> {
+        cheapest_route(&self.routes, from, to)
+    }
+
+    pub fn entities_mut<'a>(&'a mut self) -> impl Iterator<Item = &'a mut Entity> + 'a {
+        self.entities.iter_mut().map(|(_, entity)| entity)
+    }
+
+    pub fn iter_entities_mut<'a>(
+        &'a mut self,
+    ) -> impl Iterator<Item = (EntityId, &'a mut Entity)> + 'a {
+        self.entities.iter_mut()
+    }
+
+    /// The market of the first region.
     pub fn market_mut(&mut self) -> &mut Market {
-        &mut self.market
+        self.market_in_mut(0)
+    }
+
+    pub fn market_in_mut(&mut self, region: RegionId) -> &mut Market {
+        self.regions[region].market_mut()
+    }
+
+    pub fn regions_mut(&mut self) -> &mut [Region] {
+        &mut self.regions
+    }
+
+    pub fn entities_regions_mut<'a>(
+        &'a mut self,
+    ) -> (
+        impl Iterator<Item = (EntityId, &'a mut Entity)> + 'a,
+        &'a mut [Region],
+    ) {
+        (self.entities.iter_mut(), &mut self.regions)
+    }
+
+    /// The entities together with the regions and the shared natural resource stocks, to extract resources while producing.
+    pub fn entities_resources_mut<'a>(
+        &'a mut self,
+    ) -> (
+        impl Iterator<Item = (EntityId, &'a mut Entity)> + 'a,
+        &'a mut [Region],
+        &'a mut [ResourceStock],
+    ) {
+        (
+            self.entities.iter_mut(),
+            &mut self.regions,
+            &mut self.resources,
+        )
+    }
+
+    pub fn estate(&self) -> Estate {
+        self.estate
     }
 
-    pub fn entities_market_mut(&mut self) -> (&mut [Entity], &mut Market) {
-        (&mut self.entities, &mut self.market)
+    pub fn population_rules(&self) -> &[PopulationRule] {
+        &self.population_rules
     }
 
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    /// The events that happened in this world, with the ticks they happened at.
+    pub fn events(&self) -> &[(Tick, Event)] {
+        &self.events
+    }
+
+    pub fn price_index(&self) -> &PriceIndex {
+        &self.price_index
+    }
+
+    /// The id of the central bank of this world, if it has one.
+    pub fn central_bank_id(&self) -> Option<EntityId> {
+        self.iter_entities()
+            .find(|(_, entity)| entity.is_central_bank())
+            .map(|(entity_id, _)| entity_id)
+    }
+
+    /// The id of the government of this world, if it has one.
+    pub fn government_id(&self) -> Option<EntityId> {
+        self.iter_entities()
+            .find(|(_, entity)| entity.is_government())
+            .map(|(entity_id, _)| entity_id)
+    }
+
+    /// The labor market in the last tick.
+    pub fn labor_stats(&self) -> &LaborStats {
+        &self.labor_stats
+    }
+
+    /// Returns the entity with the given id.
+    /// Panics if the id is stale.
     pub fn get_entity(&self, entity_id: EntityId) -> &Entity {
-        &self.entities[entity_id as usize]
+        self.try_get_entity(entity_id)
+            .unwrap_or_else(|| panic!("Stale entity id {}", entity_id))
     }
 
+    /// Returns the entity with the given id.
+    /// Panics if the id is stale.
     pub fn get_entity_mut(&mut self, entity_id: EntityId) -> &mut Entity {
-        &mut self.entities[entity_id as usize]
+        self.try_get_entity_mut(entity_id)
+            .unwrap_or_else(|| panic!("Stale entity id {}", entity_id))
+    }
+
+    /// Returns the entity with the given id, or None if the id is stale.
+    pub fn try_get_entity(&self, entity_id: EntityId) -> Option<&Entity> {
+        self.entities.get(entity_id)
+    }
+
+    pub fn try_get_entity_mut(&mut self, entity_id: EntityId) -> Option<&mut Entity> {
+        self.entities.get_mut(entity_id)
+    }
+
+    pub fn contains_entity(&self, entity_id: EntityId) -> bool {
+        self.entities.contains(entity_id)
     }
 }